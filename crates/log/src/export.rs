@@ -0,0 +1,252 @@
+use crate::{Entry, Log, Player};
+use core::fmt::{self, Write};
+
+/// JSON display of a [`Log`], see [`Log::json`].
+pub struct Json<'a>(pub(crate) &'a Log);
+
+/// CSV display of a [`Log`], see [`Log::csv`].
+pub struct Csv<'a>(pub(crate) &'a Log);
+
+const CSV_HEADER: &str = "round,time,kind,attacker,attacker_steam_id,victim,victim_steam_id,\
+    weapon,hit_group,damage,armor_damage,headshot";
+
+impl<'a> fmt::Display for Json<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let log = self.0;
+
+        fmt.write_str("{\n\t\"map\": ")?;
+        json_string(fmt, log.map())?;
+        fmt.write_str(",\n\t\"rounds\": [")?;
+
+        for (index, round) in log.rounds().into_iter().enumerate() {
+            if index != 0 {
+                fmt.write_char(',')?;
+            }
+
+            write!(fmt, "\n\t\t{{\n\t\t\t\"round\": {round},")?;
+            fmt.write_str("\n\t\t\t\"damage_by_hit_group\": ")?;
+            json_sums(fmt, &log.damage_by_hit_group(round))?;
+            fmt.write_str(",\n\t\t\t\"damage_by_weapon\": ")?;
+            json_sums(fmt, &log.damage_by_weapon(round))?;
+            fmt.write_str(",\n\t\t\t\"entries\": [")?;
+
+            for (index, entry) in log.round_entries(round).enumerate() {
+                if index != 0 {
+                    fmt.write_char(',')?;
+                }
+
+                fmt.write_str("\n\t\t\t\t")?;
+                json_entry(fmt, entry)?;
+            }
+
+            fmt.write_str("\n\t\t\t]\n\t\t}")?;
+        }
+
+        fmt.write_str("\n\t]\n}\n")
+    }
+}
+
+fn json_entry(fmt: &mut fmt::Formatter, entry: &Entry) -> fmt::Result {
+    fmt.write_str("{\"kind\": ")?;
+    json_string(fmt, entry.kind.as_str())?;
+    write!(fmt, ", \"time\": {}, \"attacker\": ", entry.time)?;
+
+    match &entry.attacker {
+        Some(attacker) => json_player(fmt, attacker)?,
+        None => fmt.write_str("null")?,
+    }
+
+    fmt.write_str(", \"victim\": ")?;
+    json_player(fmt, &entry.victim)?;
+    fmt.write_str(", \"weapon\": ")?;
+    json_string(fmt, &entry.weapon)?;
+    fmt.write_str(", \"hit_group\": ")?;
+    json_string(fmt, &entry.hit_group)?;
+
+    write!(
+        fmt,
+        ", \"damage\": {}, \"armor_damage\": {}, \"headshot\": {}}}",
+        entry.damage, entry.armor_damage, entry.headshot
+    )
+}
+
+/// SteamIDs are written as strings, as they don't fit in a double.
+fn json_player(fmt: &mut fmt::Formatter, player: &Player) -> fmt::Result {
+    fmt.write_str("{\"name\": ")?;
+    json_string(fmt, &player.name)?;
+    write!(fmt, ", \"steam_id\": \"{}\"}}", player.steam_id)
+}
+
+fn json_sums(fmt: &mut fmt::Formatter, sums: &[(&str, i32)]) -> fmt::Result {
+    fmt.write_char('{')?;
+
+    for (index, (key, damage)) in sums.iter().enumerate() {
+        if index != 0 {
+            fmt.write_str(", ")?;
+        }
+
+        json_string(fmt, key)?;
+        write!(fmt, ": {damage}")?;
+    }
+
+    fmt.write_char('}')
+}
+
+fn json_string(fmt: &mut fmt::Formatter, string: &str) -> fmt::Result {
+    fmt.write_char('"')?;
+
+    for char in string.chars() {
+        match char {
+            '"' => fmt.write_str("\\\"")?,
+            '\\' => fmt.write_str("\\\\")?,
+            '\n' => fmt.write_str("\\n")?,
+            '\r' => fmt.write_str("\\r")?,
+            '\t' => fmt.write_str("\\t")?,
+            char if char.is_control() => write!(fmt, "\\u{:04x}", char as u32)?,
+            char => fmt.write_char(char)?,
+        }
+    }
+
+    fmt.write_char('"')
+}
+
+impl<'a> fmt::Display for Csv<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(CSV_HEADER)?;
+        fmt.write_char('\n')?;
+
+        for entry in self.0.entries() {
+            let (attacker, attacker_steam_id) = match &entry.attacker {
+                Some(attacker) => (attacker.name.as_str(), attacker.steam_id),
+                None => ("", 0),
+            };
+
+            write!(
+                fmt,
+                "{},{},{},",
+                entry.round,
+                entry.time,
+                entry.kind.as_str()
+            )?;
+            csv_field(fmt, attacker)?;
+            write!(fmt, ",{attacker_steam_id},")?;
+            csv_field(fmt, &entry.victim.name)?;
+            write!(fmt, ",{},", entry.victim.steam_id)?;
+            csv_field(fmt, &entry.weapon)?;
+            fmt.write_char(',')?;
+            csv_field(fmt, &entry.hit_group)?;
+
+            writeln!(
+                fmt,
+                ",{},{},{}",
+                entry.damage, entry.armor_damage, entry.headshot
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Write `field`, quoted only if needed (RFC 4180).
+fn csv_field(fmt: &mut fmt::Formatter, field: &str) -> fmt::Result {
+    if !field.contains([',', '"', '\n', '\r']) {
+        return fmt.write_str(field);
+    }
+
+    fmt.write_char('"')?;
+
+    for char in field.chars() {
+        if char == '"' {
+            fmt.write_char('"')?;
+        }
+
+        fmt.write_char(char)?;
+    }
+
+    fmt.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{hit, kill, player};
+    use crate::Log;
+
+    fn log() -> Log {
+        let mut log = Log::new();
+
+        log.start_match("de_dust2");
+        log.start_round();
+        log.push(hit(1, "head", "AK47", 100));
+        log.push(kill(1));
+
+        log
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            log().json().to_string(),
+            "{\n\
+            \t\"map\": \"de_dust2\",\n\
+            \t\"rounds\": [\n\
+            \t\t{\n\
+            \t\t\t\"round\": 1,\n\
+            \t\t\t\"damage_by_hit_group\": {\"head\": 100},\n\
+            \t\t\t\"damage_by_weapon\": {\"AK47\": 100},\n\
+            \t\t\t\"entries\": [\n\
+            \t\t\t\t{\"kind\": \"hit\", \"time\": 1.5, \
+            \"attacker\": {\"name\": \"alice\", \"steam_id\": \"76561197960287930\"}, \
+            \"victim\": {\"name\": \"bob\", \"steam_id\": \"0\"}, \"weapon\": \"AK47\", \
+            \"hit_group\": \"head\", \"damage\": 100, \"armor_damage\": 0, \"headshot\": false},\n\
+            \t\t\t\t{\"kind\": \"kill\", \"time\": 1.5, \
+            \"attacker\": {\"name\": \"alice\", \"steam_id\": \"76561197960287930\"}, \
+            \"victim\": {\"name\": \"bob\", \"steam_id\": \"0\"}, \"weapon\": \"AK47\", \
+            \"hit_group\": \"\", \"damage\": 0, \"armor_damage\": 0, \"headshot\": true}\n\
+            \t\t\t]\n\
+            \t\t}\n\
+            \t]\n\
+            }\n"
+        );
+    }
+
+    #[test]
+    fn json_empty() {
+        assert_eq!(
+            Log::new().json().to_string(),
+            "{\n\t\"map\": \"\",\n\t\"rounds\": [\n\t]\n}\n"
+        );
+    }
+
+    #[test]
+    fn json_escape() {
+        let mut log = Log::new();
+        let mut entry = hit(1, "head", "AK47", 100);
+
+        entry.victim = player("\"bob\"\n\u{1}", 0);
+        log.push(entry);
+
+        assert!(log
+            .json()
+            .to_string()
+            .contains("{\"name\": \"\\\"bob\\\"\\n\\u0001\""));
+    }
+
+    #[test]
+    fn csv() {
+        let mut log = log();
+        let mut entry = hit(2, "left leg", "Glock", 19);
+
+        entry.attacker = None;
+        entry.victim = player("bob, \"the builder\"", 0);
+        log.push(entry);
+
+        assert_eq!(
+            log.csv().to_string(),
+            "round,time,kind,attacker,attacker_steam_id,victim,victim_steam_id,\
+            weapon,hit_group,damage,armor_damage,headshot\n\
+            1,1.5,hit,alice,76561197960287930,bob,0,AK47,head,100,0,false\n\
+            1,1.5,kill,alice,76561197960287930,bob,0,AK47,,0,0,true\n\
+            2,1.5,hit,,0,\"bob, \"\"the builder\"\"\",0,Glock,left leg,19,0,false\n"
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! Per-match record of hits and kills, exportable as JSON or CSV.
+
+#![deny(warnings)]
+
+pub use export::{Csv, Json};
+
+mod export;
+
+use core::fmt;
+
+/// A player as they were when the entry was recorded.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Player {
+    pub name: String,
+    /// SteamID64, zero for bots.
+    pub steam_id: u64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+    Hit,
+    Kill,
+}
+
+impl Kind {
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Kind::Hit => "hit",
+            Kind::Kill => "kill",
+        }
+    }
+}
+
+/// A hit or kill.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub kind: Kind,
+    /// Round the entry happened in, starting at one.
+    pub round: u32,
+    /// Server time.
+    pub time: f32,
+    /// `None` for world damage, such as falling.
+    pub attacker: Option<Player>,
+    pub victim: Player,
+    pub weapon: String,
+    /// Empty for kills.
+    pub hit_group: String,
+    pub damage: i32,
+    pub armor_damage: i32,
+    pub headshot: bool,
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let attacker = self
+            .attacker
+            .as_ref()
+            .map(|attacker| attacker.name.as_str())
+            .unwrap_or("world");
+
+        let victim = &self.victim.name;
+        let weapon = &self.weapon;
+
+        match self.kind {
+            Kind::Hit => {
+                let damage = self.damage;
+                let hit_group = &self.hit_group;
+
+                write!(
+                    fmt,
+                    "{attacker} hit {victim} in the {hit_group} for {damage} ({weapon})"
+                )
+            }
+            Kind::Kill => {
+                write!(fmt, "{attacker} killed {victim} with {weapon}")?;
+
+                if self.headshot {
+                    fmt.write_str(" (headshot)")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Every entry of a match.
+#[derive(Clone, Debug, Default)]
+pub struct Log {
+    map: String,
+    round: u32,
+    entries: Vec<Entry>,
+}
+
+impl Log {
+    pub const fn new() -> Log {
+        Self {
+            map: String::new(),
+            round: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn map(&self) -> &str {
+        &self.map
+    }
+
+    /// Start logging a new match on `map`, forgetting every entry.
+    pub fn start_match(&mut self, map: &str) {
+        self.map.clear();
+        self.map.push_str(map);
+        self.round = 0;
+        self.entries.clear();
+    }
+
+    /// The current round, zero before the first round starts.
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn start_round(&mut self) {
+        self.round += 1;
+    }
+
+    /// Append an entry, returning it.
+    pub fn push(&mut self, entry: Entry) -> &Entry {
+        self.entries.push(entry);
+        self.entries.last().unwrap()
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries of `round`.
+    pub fn round_entries(&self, round: u32) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.round == round)
+    }
+
+    /// Rounds with at least one entry.
+    pub fn rounds(&self) -> Vec<u32> {
+        let mut rounds: Vec<u32> = self.entries.iter().map(|entry| entry.round).collect();
+
+        rounds.dedup();
+        rounds
+    }
+
+    /// Damage dealt during `round`, summed per hit group, in order of first appearance.
+    pub fn damage_by_hit_group(&self, round: u32) -> Vec<(&str, i32)> {
+        sum_hits(self.round_entries(round), |entry| &entry.hit_group)
+    }
+
+    /// Damage dealt during `round`, summed per weapon, in order of first appearance.
+    pub fn damage_by_weapon(&self, round: u32) -> Vec<(&str, i32)> {
+        sum_hits(self.round_entries(round), |entry| &entry.weapon)
+    }
+
+    /// Display as a JSON document.
+    pub fn json(&self) -> Json<'_> {
+        Json(self)
+    }
+
+    /// Display as CSV, one row per entry.
+    pub fn csv(&self) -> Csv<'_> {
+        Csv(self)
+    }
+}
+
+fn sum_hits<'a, I, F>(entries: I, key: F) -> Vec<(&'a str, i32)>
+where
+    I: Iterator<Item = &'a Entry>,
+    F: Fn(&'a Entry) -> &'a String,
+{
+    let mut sums: Vec<(&str, i32)> = Vec::new();
+
+    for entry in entries.filter(|entry| entry.kind == Kind::Hit) {
+        let key = key(entry).as_str();
+
+        match sums.iter_mut().find(|(existing, _damage)| *existing == key) {
+            Some((_key, damage)) => *damage += entry.damage,
+            None => sums.push((key, entry.damage)),
+        }
+    }
+
+    sums
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub fn player(name: &str, steam_id: u64) -> Player {
+        Player {
+            name: name.into(),
+            steam_id,
+        }
+    }
+
+    pub fn hit(round: u32, hit_group: &str, weapon: &str, damage: i32) -> Entry {
+        Entry {
+            kind: Kind::Hit,
+            round,
+            time: 1.5,
+            attacker: Some(player("alice", 76561197960287930)),
+            victim: player("bob", 0),
+            weapon: weapon.into(),
+            hit_group: hit_group.into(),
+            damage,
+            armor_damage: 0,
+            headshot: false,
+        }
+    }
+
+    pub fn kill(round: u32) -> Entry {
+        Entry {
+            kind: Kind::Kill,
+            hit_group: String::new(),
+            damage: 0,
+            headshot: true,
+            ..hit(round, "", "AK47", 0)
+        }
+    }
+
+    #[test]
+    fn rounds() {
+        let mut log = Log::new();
+
+        log.start_match("de_dust2");
+        log.start_round();
+        log.push(hit(1, "head", "AK47", 100));
+        log.start_round();
+        log.start_round();
+        log.push(hit(3, "chest", "AK47", 27));
+
+        assert_eq!(log.round(), 3);
+        assert_eq!(log.rounds(), [1, 3]);
+        assert_eq!(log.round_entries(2).count(), 0);
+
+        log.start_match("de_inferno");
+
+        assert!(log.is_empty());
+        assert_eq!(log.round(), 0);
+        assert_eq!(log.map(), "de_inferno");
+    }
+
+    #[test]
+    fn sums() {
+        let mut log = Log::new();
+
+        log.push(hit(1, "head", "AK47", 100));
+        log.push(hit(1, "chest", "Glock", 20));
+        log.push(hit(1, "head", "Glock", 40));
+        log.push(kill(1));
+        log.push(hit(2, "head", "AWP", 115));
+
+        assert_eq!(log.damage_by_hit_group(1), [("head", 140), ("chest", 20)]);
+        assert_eq!(log.damage_by_weapon(1), [("AK47", 100), ("Glock", 60)]);
+        assert_eq!(log.damage_by_weapon(2), [("AWP", 115)]);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            hit(1, "head", "AK47", 100).to_string(),
+            "alice hit bob in the head for 100 (AK47)"
+        );
+        assert_eq!(kill(1).to_string(), "alice killed bob with AK47 (headshot)");
+    }
+}
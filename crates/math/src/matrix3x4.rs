@@ -103,9 +103,9 @@ impl Matrix3x4 {
     pub const fn with_z_axis(mut self, z: Vec3) -> Matrix3x4 {
         let Vec3 { x, y, z } = z;
 
-        self[0][1] = x;
-        self[1][1] = y;
-        self[2][1] = z;
+        self[0][2] = x;
+        self[1][2] = y;
+        self[2][2] = z;
         self
     }
 
@@ -113,11 +113,59 @@ impl Matrix3x4 {
     pub const fn with_w_axis(mut self, w: Vec3) -> Matrix3x4 {
         let Vec3 { x, y, z } = w;
 
-        self[0][1] = x;
-        self[1][1] = y;
-        self[2][1] = z;
+        self[0][3] = x;
+        self[1][3] = y;
+        self[2][3] = z;
         self
     }
+
+    /// Create a rotation matrix from pitch, yaw and roll in degrees (`AngleMatrix`).
+    pub fn from_angle(angle: Vec3) -> Matrix3x4 {
+        let (forward, right, up) = angle.angle_vector();
+
+        // the engine stores left, rather than right
+        Self::from_xyzw(forward, Vec3::zero() - right, up, Vec3::zero())
+    }
+
+    /// Rotate a direction by this matrix, ignoring the origin.
+    pub const fn rotate(&self, vector: Vec3) -> Vec3 {
+        let Vec3 { x, y, z } = vector;
+
+        Vec3::from_xyz(
+            self[0][0] * x + self[0][1] * y + self[0][2] * z,
+            self[1][0] * x + self[1][1] * y + self[1][2] * z,
+            self[2][0] * x + self[2][1] * y + self[2][2] * z,
+        )
+    }
+
+    /// Rotate a direction by the inverse of this matrix, ignoring the origin.
+    pub const fn inverse_rotate(&self, vector: Vec3) -> Vec3 {
+        Vec3::from_xyz(
+            self.x_axis().dot(vector),
+            self.y_axis().dot(vector),
+            self.z_axis().dot(vector),
+        )
+    }
+
+    /// Transform a point from this matrix's space into its parent space (`VectorTransform`).
+    pub const fn transform(&self, point: Vec3) -> Vec3 {
+        self.rotate(point) + self.w_axis()
+    }
+
+    /// Transform a point from parent space into this matrix's space (`VectorITransform`).
+    pub const fn inverse_transform(&self, point: Vec3) -> Vec3 {
+        self.inverse_rotate(point - self.w_axis())
+    }
+
+    /// Apply `other`, then this matrix (`ConcatTransforms`).
+    pub const fn concat(&self, other: &Matrix3x4) -> Matrix3x4 {
+        Self::from_xyzw(
+            self.rotate(other.x_axis()),
+            self.rotate(other.y_axis()),
+            self.rotate(other.z_axis()),
+            self.transform(other.w_axis()),
+        )
+    }
 }
 
 impl const Deref for Matrix3x4 {
@@ -4,6 +4,7 @@ use iced_native::widget::{
     Rule, Scrollable, Slider, Space, Text, TextInput, Toggler,
 };
 use iced_native::{Alignment, Command, Element, Length, Program};
+use std::cell::Cell;
 
 #[derive(Default)]
 pub struct Controls {
@@ -16,6 +17,27 @@ pub struct Controls {
     checkbox_value: bool,
     toggler_value: bool,
     menu_visibility: bool,
+    force_vars: bool,
+    net_graph: bool,
+    movement: bool,
+    velocity: bool,
+    recording_input: text_input::State,
+    recording_name: String,
+    record_button: button::State,
+    play_button: button::State,
+    stop_button: button::State,
+    recorder_action: Cell<Option<RecorderAction>>,
+}
+
+/// A recorder button press, waiting to be handled by the game thread.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecorderAction {
+    /// Start recording to the named file.
+    Record(String),
+    /// Play the named file back.
+    Play(String),
+    /// Stop recording, saving it, or stop playback.
+    Stop,
 }
 
 #[derive(Debug, Clone)]
@@ -26,12 +48,52 @@ pub enum Message {
     CheckboxToggled(bool),
     TogglerToggled(bool),
     MenuVisibility(bool),
+    ForceVarsToggled(bool),
+    NetGraphToggled(bool),
+    MovementToggled(bool),
+    VelocityToggled(bool),
+    RecordingNameChanged(String),
+    Recorder(RecorderAction),
 }
 
 impl Controls {
     #[inline]
     pub fn new() -> Controls {
-        Default::default()
+        Controls {
+            force_vars: true,
+            recording_name: String::from("practice"),
+            ..Default::default()
+        }
+    }
+
+    /// Whether config variables should be forced to our values.
+    #[inline]
+    pub fn force_vars(&self) -> bool {
+        self.force_vars
+    }
+
+    /// Whether the net graph overlay is shown.
+    #[inline]
+    pub fn net_graph(&self) -> bool {
+        self.net_graph
+    }
+
+    /// Whether movement stats are shown.
+    #[inline]
+    pub fn movement(&self) -> bool {
+        self.movement
+    }
+
+    /// Whether the velocity graph is shown.
+    #[inline]
+    pub fn velocity(&self) -> bool {
+        self.velocity
+    }
+
+    /// Take the last recorder button press, if it hasn't been handled yet.
+    #[inline]
+    pub fn take_recorder_action(&self) -> Option<RecorderAction> {
+        self.recorder_action.take()
     }
 }
 
@@ -48,6 +110,12 @@ impl Program for Controls {
             Message::CheckboxToggled(value) => self.checkbox_value = value,
             Message::TogglerToggled(value) => self.toggler_value = value,
             Message::MenuVisibility(value) => self.menu_visibility = value,
+            Message::ForceVarsToggled(value) => self.force_vars = value,
+            Message::NetGraphToggled(value) => self.net_graph = value,
+            Message::MovementToggled(value) => self.movement = value,
+            Message::VelocityToggled(value) => self.velocity = value,
+            Message::RecordingNameChanged(value) => self.recording_name = value,
+            Message::Recorder(action) => self.recorder_action.set(Some(action)),
         }
 
         Command::none()
@@ -86,6 +154,51 @@ impl Program for Controls {
 
         let checkbox = Checkbox::new(self.checkbox_value, "checkbox", Message::CheckboxToggled);
 
+        let force_vars = Checkbox::new(
+            self.force_vars,
+            "force config variables",
+            Message::ForceVarsToggled,
+        );
+
+        let net_graph = Checkbox::new(self.net_graph, "show net graph", Message::NetGraphToggled);
+
+        let movement = Checkbox::new(
+            self.movement,
+            "show movement stats",
+            Message::MovementToggled,
+        );
+
+        let velocity = Checkbox::new(
+            self.velocity,
+            "show velocity graph",
+            Message::VelocityToggled,
+        );
+
+        let recording_name = TextInput::new(
+            &mut self.recording_input,
+            "recording name",
+            &self.recording_name,
+            Message::RecordingNameChanged,
+        )
+        .padding(10)
+        .size(20);
+
+        let record = Button::new(&mut self.record_button, Text::new("record"))
+            .padding(10)
+            .on_press(Message::Recorder(RecorderAction::Record(
+                self.recording_name.clone(),
+            )));
+
+        let play = Button::new(&mut self.play_button, Text::new("play"))
+            .padding(10)
+            .on_press(Message::Recorder(RecorderAction::Play(
+                self.recording_name.clone(),
+            )));
+
+        let stop = Button::new(&mut self.stop_button, Text::new("stop"))
+            .padding(10)
+            .on_press(Message::Recorder(RecorderAction::Stop));
+
         let toggler = Toggler::new(
             self.toggler_value,
             String::from("togger"),
@@ -102,6 +215,18 @@ impl Program for Controls {
             .push(Row::new().spacing(10).push(text_input).push(button))
             .push(slider)
             .push(progress_bar)
+            .push(force_vars)
+            .push(net_graph)
+            .push(movement)
+            .push(velocity)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(recording_name)
+                    .push(record)
+                    .push(play)
+                    .push(stop),
+            )
             .push(
                 Row::new()
                     .spacing(10)
@@ -0,0 +1,63 @@
+use iced_glow::Renderer;
+use iced_native::widget::{Column, Container, Text};
+use iced_native::{Command, Element, Length, Program};
+use std::collections::VecDeque;
+
+/// How many lines are shown at once.
+const MAX_LINES: usize = 8;
+
+/// Scrolling feed of recent events, drawn whether or not the menu is open.
+#[derive(Default)]
+pub struct Feed {
+    lines: VecDeque<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Feed(String),
+    Clear,
+}
+
+impl Feed {
+    #[inline]
+    pub fn new() -> Feed {
+        Feed::default()
+    }
+}
+
+impl Program for Feed {
+    type Renderer = Renderer;
+    type Message = Message;
+
+    #[inline]
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Feed(line) => {
+                if self.lines.len() == MAX_LINES {
+                    self.lines.pop_front();
+                }
+
+                self.lines.push_back(line);
+            }
+            Message::Clear => self.lines.clear(),
+        }
+
+        Command::none()
+    }
+
+    #[inline]
+    fn view(&mut self) -> Element<Message, Renderer> {
+        let content = self
+            .lines
+            .iter()
+            .fold(Column::new().spacing(4), |column, line| {
+                column.push(Text::new(line.as_str()).size(16))
+            });
+
+        Container::new(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(20)
+            .into()
+    }
+}
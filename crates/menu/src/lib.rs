@@ -1,9 +1,17 @@
-pub use controls::Controls;
+pub use controls::{Controls, RecorderAction};
+pub use feed::Feed;
 pub use menu::Menu;
+pub use movement::Movement;
+pub use net_graph::NetGraph;
 pub use scene::Scene;
+pub use velocity::Velocity;
 
 mod controls;
+mod feed;
 mod menu;
 mod scene;
 
 pub mod assets;
+pub mod movement;
+pub mod net_graph;
+pub mod velocity;
@@ -1,6 +1,8 @@
 //! Menu related functions.
 
-use crate::{assets, Controls};
+use crate::{
+    assets, feed, movement, net_graph, velocity, Controls, Feed, Movement, NetGraph, Velocity,
+};
 use iced_glow::{glow, Backend, Renderer, Settings, Viewport};
 use iced_native::clipboard::Null;
 use iced_native::program::State;
@@ -13,6 +15,10 @@ pub struct Menu {
     renderer: Renderer,
     //scene: Scene,
     state: State<Controls>,
+    feed: State<Feed>,
+    net_graph: State<NetGraph>,
+    movement: State<Movement>,
+    velocity: State<Velocity>,
 }
 
 impl Menu {
@@ -20,6 +26,10 @@ impl Menu {
     pub fn new(context: &glow::Context, viewport: Viewport) -> Self {
         let clipboard = clipboard::Null;
         let controls = Controls::new();
+        let feed = Feed::new();
+        let net_graph = NetGraph::new();
+        let movement = Movement::new();
+        let velocity = Velocity::new();
         let mut debug = Debug::new();
         let mut renderer = Renderer::new(Backend::new(
             context,
@@ -30,6 +40,15 @@ impl Menu {
         ));
 
         let state = State::new(controls, viewport.logical_size(), &mut renderer, &mut debug);
+        let feed = State::new(feed, viewport.logical_size(), &mut renderer, &mut debug);
+        let net_graph = State::new(
+            net_graph,
+            viewport.logical_size(),
+            &mut renderer,
+            &mut debug,
+        );
+        let movement = State::new(movement, viewport.logical_size(), &mut renderer, &mut debug);
+        let velocity = State::new(velocity, viewport.logical_size(), &mut renderer, &mut debug);
         let debug = debug;
         let renderer = renderer;
 
@@ -39,6 +58,10 @@ impl Menu {
             renderer,
             //scene,
             state,
+            feed,
+            net_graph,
+            movement,
+            velocity,
         }
     }
 
@@ -67,6 +90,174 @@ impl Menu {
         );
     }
 
+    /// Update and draw the feed.
+    #[inline]
+    pub fn draw_feed(&mut self, context: &glow::Context, viewport: Viewport) {
+        // the feed isn't interactive, keep the cursor away from it
+        let cursor_position = Point::new(-1.0, -1.0);
+
+        self.feed.update(
+            viewport.logical_size(),
+            cursor_position,
+            &mut self.renderer,
+            &iced_glow::Theme::Dark,
+            &renderer::Style {
+                text_color: Color::WHITE,
+            },
+            &mut self.clipboard,
+            &mut self.debug,
+        );
+
+        self.draw(context, viewport);
+    }
+
+    /// Update and draw the net graph.
+    #[inline]
+    pub fn draw_net_graph(&mut self, context: &glow::Context, viewport: Viewport) {
+        // nor is the net graph
+        let cursor_position = Point::new(-1.0, -1.0);
+
+        self.net_graph.update(
+            viewport.logical_size(),
+            cursor_position,
+            &mut self.renderer,
+            &iced_glow::Theme::Dark,
+            &renderer::Style {
+                text_color: Color::WHITE,
+            },
+            &mut self.clipboard,
+            &mut self.debug,
+        );
+
+        self.draw(context, viewport);
+    }
+
+    /// Append a line to the feed, dropping the oldest if full.
+    #[inline]
+    pub fn push_feed(&mut self, line: String) {
+        self.feed.queue_message(feed::Message::Feed(line));
+    }
+
+    #[inline]
+    pub fn clear_feed(&mut self) {
+        self.feed.queue_message(feed::Message::Clear);
+    }
+
+    /// Update and draw the movement stats.
+    #[inline]
+    pub fn draw_movement(&mut self, context: &glow::Context, viewport: Viewport) {
+        let cursor_position = Point::new(-1.0, -1.0);
+
+        self.movement.update(
+            viewport.logical_size(),
+            cursor_position,
+            &mut self.renderer,
+            &iced_glow::Theme::Dark,
+            &renderer::Style {
+                text_color: Color::WHITE,
+            },
+            &mut self.clipboard,
+            &mut self.debug,
+        );
+
+        self.draw(context, viewport);
+    }
+
+    /// Update and draw the velocity graph.
+    #[inline]
+    pub fn draw_velocity(&mut self, context: &glow::Context, viewport: Viewport) {
+        let cursor_position = Point::new(-1.0, -1.0);
+
+        self.velocity.update(
+            viewport.logical_size(),
+            cursor_position,
+            &mut self.renderer,
+            &iced_glow::Theme::Dark,
+            &renderer::Style {
+                text_color: Color::WHITE,
+            },
+            &mut self.clipboard,
+            &mut self.debug,
+        );
+
+        self.draw(context, viewport);
+    }
+
+    /// Show the latest network channel stats.
+    ///
+    /// Ignored while the net graph is hidden, as messages are only handled when it's drawn.
+    #[inline]
+    pub fn update_net_graph(&mut self, stats: net_graph::Stats) {
+        if self.controls().net_graph() {
+            self.net_graph
+                .queue_message(net_graph::Message::Update(stats));
+        }
+    }
+
+    #[inline]
+    pub fn clear_net_graph(&mut self) {
+        if self.controls().net_graph() {
+            self.net_graph.queue_message(net_graph::Message::Clear);
+        }
+    }
+
+    /// Show the local player's current speed.
+    ///
+    /// Like the rest of the movement stats, ignored while they're hidden.
+    #[inline]
+    pub fn update_speed(&mut self, speed: f32) {
+        if self.controls().movement() {
+            self.movement.queue_message(movement::Message::Speed(speed));
+        }
+    }
+
+    /// Show a jump's stats, moving the previous one to the history list.
+    #[inline]
+    pub fn push_jump(&mut self, jump: String) {
+        if self.controls().movement() {
+            self.movement.queue_message(movement::Message::Jump(jump));
+        }
+    }
+
+    #[inline]
+    pub fn clear_movement(&mut self) {
+        if self.controls().movement() {
+            self.movement.queue_message(movement::Message::Clear);
+        }
+    }
+
+    /// Add a tick to the velocity graph, dropping the oldest if full.
+    ///
+    /// Like the rest of the velocity graph's messages, ignored while it's hidden.
+    #[inline]
+    pub fn push_velocity(&mut self, sample: velocity::Sample) {
+        if self.controls().velocity() {
+            self.velocity.queue_message(velocity::Message::Push(sample));
+        }
+    }
+
+    /// Resize, move, or change the history length of the velocity graph.
+    #[inline]
+    pub fn configure_velocity(&mut self, config: velocity::Config) {
+        if self.controls().velocity() {
+            self.velocity
+                .queue_message(velocity::Message::Configure(config));
+        }
+    }
+
+    #[inline]
+    pub fn clear_velocity(&mut self) {
+        if self.controls().velocity() {
+            self.velocity.queue_message(velocity::Message::Clear);
+        }
+    }
+
+    /// The menu's controls.
+    #[inline]
+    pub fn controls(&self) -> &Controls {
+        self.state.program()
+    }
+
     #[inline]
     pub fn queue_event(&mut self, event: Event) {
         let state = &mut self.state;
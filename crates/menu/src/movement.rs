@@ -0,0 +1,81 @@
+use iced_glow::Renderer;
+use iced_native::alignment::{Horizontal, Vertical};
+use iced_native::widget::{Column, Container, Text};
+use iced_native::{Alignment, Command, Element, Length, Program};
+use std::collections::VecDeque;
+
+/// How many previous jumps are listed.
+const MAX_HISTORY: usize = 6;
+
+/// Current speed, the last jump's statistics and a list of the jumps before it.
+#[derive(Default)]
+pub struct Movement {
+    speed: Option<f32>,
+    jumps: VecDeque<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Speed(f32),
+    Jump(String),
+    Clear,
+}
+
+impl Movement {
+    #[inline]
+    pub fn new() -> Movement {
+        Movement::default()
+    }
+}
+
+impl Program for Movement {
+    type Renderer = Renderer;
+    type Message = Message;
+
+    #[inline]
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Speed(speed) => self.speed = Some(speed),
+            Message::Jump(jump) => {
+                if self.jumps.len() == MAX_HISTORY + 1 {
+                    self.jumps.pop_back();
+                }
+
+                self.jumps.push_front(jump);
+            }
+            Message::Clear => {
+                self.speed = None;
+                self.jumps.clear();
+            }
+        }
+
+        Command::none()
+    }
+
+    #[inline]
+    fn view(&mut self) -> Element<Message, Renderer> {
+        let mut content = Column::new().spacing(4).align_items(Alignment::Center);
+
+        if let Some(speed) = self.speed {
+            content = content.push(Text::new(format!("{speed:.0}")).size(28));
+        }
+
+        let mut jumps = self.jumps.iter();
+
+        if let Some(last) = jumps.next() {
+            content = content.push(Text::new(last.as_str()).size(18));
+        }
+
+        let content = jumps.fold(content, |column, jump| {
+            column.push(Text::new(jump.as_str()).size(14))
+        });
+
+        Container::new(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(80)
+            .align_x(Horizontal::Center)
+            .align_y(Vertical::Bottom)
+            .into()
+    }
+}
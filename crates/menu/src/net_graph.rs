@@ -0,0 +1,121 @@
+use iced_glow::Renderer;
+use iced_native::alignment::{Horizontal, Vertical};
+use iced_native::widget::{Column, Container, ProgressBar, Row, Text};
+use iced_native::{Command, Element, Length, Program};
+
+/// Width of the loss and choke bars.
+const BAR_WIDTH: u16 = 160;
+
+/// Network channel health, averaged over the sampled ticks.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats {
+    /// Round trip time, in milliseconds.
+    pub ping: f32,
+    /// Mean change in ping between ticks, in milliseconds.
+    pub jitter: f32,
+    /// Incoming packet loss, in percent.
+    pub loss: f32,
+    /// Incoming packet choke, in percent.
+    pub choke: f32,
+    /// Incoming bytes per second.
+    pub incoming: f32,
+    /// Outgoing bytes per second.
+    pub outgoing: f32,
+    /// Whether loss is over the alert threshold.
+    pub loss_alert: bool,
+    /// Whether choke is over the alert threshold.
+    pub choke_alert: bool,
+}
+
+/// Net graph style overlay of the network channel's health.
+#[derive(Default)]
+pub struct NetGraph {
+    stats: Option<Stats>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Update(Stats),
+    Clear,
+}
+
+impl NetGraph {
+    #[inline]
+    pub fn new() -> NetGraph {
+        NetGraph::default()
+    }
+}
+
+impl Program for NetGraph {
+    type Renderer = Renderer;
+    type Message = Message;
+
+    #[inline]
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Update(stats) => self.stats = Some(stats),
+            Message::Clear => self.stats = None,
+        }
+
+        Command::none()
+    }
+
+    #[inline]
+    fn view(&mut self) -> Element<Message, Renderer> {
+        let content = match self.stats {
+            Some(stats) => Column::new()
+                .spacing(4)
+                .push(line(format!(
+                    "ping {:.0} ms, jitter {:.1} ms",
+                    stats.ping, stats.jitter
+                )))
+                .push(bar("loss", stats.loss, stats.loss_alert))
+                .push(bar("choke", stats.choke, stats.choke_alert))
+                .push(line(format!(
+                    "in {} out {}",
+                    bytes(stats.incoming),
+                    bytes(stats.outgoing)
+                ))),
+            None => Column::new(),
+        };
+
+        Container::new(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(20)
+            .align_x(Horizontal::Right)
+            .align_y(Vertical::Bottom)
+            .into()
+    }
+}
+
+#[inline]
+fn line<'a>(text: String) -> Element<'a, Message, Renderer> {
+    Text::new(text).size(16).into()
+}
+
+/// A percentage and its bar, marked when over the alert threshold.
+#[inline]
+fn bar<'a>(label: &str, percent: f32, alert: bool) -> Element<'a, Message, Renderer> {
+    let marker = if alert { " !" } else { "" };
+
+    Row::new()
+        .spacing(10)
+        .push(line(format!("{label} {percent:.1}%{marker}")))
+        .push(
+            ProgressBar::new(0.0..=100.0, percent)
+                .width(Length::Units(BAR_WIDTH))
+                .height(Length::Units(8)),
+        )
+        .into()
+}
+
+/// Format a rate in bytes per second, as `12.3 KB/s`.
+#[inline]
+fn bytes(rate: f32) -> String {
+    if rate >= 1024.0 {
+        format!("{:.1} KB/s", rate / 1024.0)
+    } else {
+        format!("{rate:.0} B/s")
+    }
+}
@@ -0,0 +1,258 @@
+use iced_glow::widget::canvas::{self, Canvas, Cursor, Frame, Geometry, Path, Stroke};
+use iced_glow::{Renderer, Theme};
+use iced_native::alignment::{Horizontal, Vertical};
+use iced_native::{Color, Command, Element, Length, Point, Program, Rectangle, Size};
+use std::collections::VecDeque;
+
+/// Speed the graph's scale never drops below, roughly running speed with a knife.
+const MIN_SCALE: f32 = 320.0;
+
+const BACKGROUND: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.4);
+const LINE: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.8);
+const JUMP: Color = Color::from_rgb(0.4, 0.8, 1.0);
+const LAND: Color = Color::from_rgb(1.0, 0.6, 0.2);
+const GAIN: Color = Color::from_rgb(0.4, 1.0, 0.4);
+const LOSS: Color = Color::from_rgb(1.0, 0.4, 0.4);
+
+/// Size, position and history length of the graph.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    pub width: f32,
+    pub height: f32,
+    /// Horizontal centre of the graph, as a fraction of the screen's width.
+    pub x: f32,
+    /// Vertical centre of the graph, as a fraction of the screen's height.
+    pub y: f32,
+    /// How many ticks are graphed.
+    pub history: usize,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            width: 320.0,
+            height: 80.0,
+            x: 0.5,
+            y: 0.8,
+            history: 128,
+        }
+    }
+}
+
+/// A point of interest on the graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mark {
+    Jump,
+    Land,
+}
+
+/// The local player's horizontal speed as of a tick.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Sample {
+    pub speed: f32,
+    pub mark: Option<Mark>,
+}
+
+/// Scrolling graph of horizontal speed, with the current and takeoff speed below it.
+#[derive(Default)]
+pub struct Velocity {
+    config: Config,
+    samples: VecDeque<Sample>,
+    /// Speed at the last jump.
+    takeoff: Option<f32>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Push(Sample),
+    Configure(Config),
+    Clear,
+}
+
+impl Velocity {
+    #[inline]
+    pub fn new() -> Velocity {
+        Velocity::default()
+    }
+
+    #[inline]
+    fn truncate(&mut self) {
+        while self.samples.len() > self.config.history {
+            self.samples.pop_front();
+        }
+    }
+}
+
+impl Program for Velocity {
+    type Renderer = Renderer;
+    type Message = Message;
+
+    #[inline]
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Push(sample) => {
+                if sample.mark == Some(Mark::Jump) {
+                    self.takeoff = Some(sample.speed);
+                }
+
+                self.samples.push_back(sample);
+                self.truncate();
+            }
+            Message::Configure(config) => {
+                self.config = config;
+                self.truncate();
+            }
+            Message::Clear => {
+                self.samples.clear();
+                self.takeoff = None;
+            }
+        }
+
+        Command::none()
+    }
+
+    #[inline]
+    fn view(&mut self) -> Element<Message, Renderer> {
+        let graph = Graph {
+            config: &self.config,
+            samples: &self.samples,
+            takeoff: self.takeoff,
+        };
+
+        Canvas::new(graph)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
+
+/// Draws the graph, borrowing the overlay's samples.
+struct Graph<'a> {
+    config: &'a Config,
+    samples: &'a VecDeque<Sample>,
+    takeoff: Option<f32>,
+}
+
+impl<'a> canvas::Program<Message> for Graph<'a> {
+    fn draw(&self, _theme: &Theme, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        let mut frame = Frame::new(bounds.size());
+        let config = self.config;
+
+        if self.samples.is_empty() || config.history < 2 {
+            return vec![frame.into_geometry()];
+        }
+
+        let size = Size::new(config.width, config.height);
+        let top_left = Point::new(
+            bounds.width * config.x - size.width / 2.0,
+            bounds.height * config.y - size.height / 2.0,
+        );
+
+        frame.fill_rectangle(top_left, size, BACKGROUND);
+
+        let scale = self
+            .samples
+            .iter()
+            .map(|sample| sample.speed)
+            .fold(MIN_SCALE, f32::max);
+
+        // newest sample on the right edge, scrolling left
+        let step = size.width / (config.history - 1) as f32;
+        let start = (config.history - self.samples.len()) as f32 * step;
+        let point = |index: usize, speed: f32| {
+            Point::new(
+                top_left.x + start + index as f32 * step,
+                top_left.y + size.height * (1.0 - speed / scale),
+            )
+        };
+
+        let line = Path::new(|builder| {
+            for (index, sample) in self.samples.iter().enumerate() {
+                let point = point(index, sample.speed);
+
+                if index == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+        });
+
+        frame.stroke(
+            &line,
+            Stroke {
+                color: LINE,
+                width: 1.5,
+                ..Stroke::default()
+            },
+        );
+
+        for (index, sample) in self.samples.iter().enumerate() {
+            let color = match sample.mark {
+                Some(Mark::Jump) => JUMP,
+                Some(Mark::Land) => LAND,
+                None => continue,
+            };
+
+            let point = point(index, sample.speed);
+            let marker = Path::line(
+                Point::new(point.x, top_left.y),
+                Point::new(point.x, top_left.y + size.height),
+            );
+
+            frame.stroke(
+                &marker,
+                Stroke {
+                    color,
+                    width: 1.0,
+                    ..Stroke::default()
+                },
+            );
+
+            if sample.mark == Some(Mark::Jump) {
+                frame.fill_text(canvas::Text {
+                    content: format!("{:.0}", sample.speed),
+                    position: Point::new(point.x, top_left.y),
+                    color,
+                    size: 12.0,
+                    horizontal_alignment: Horizontal::Center,
+                    vertical_alignment: Vertical::Bottom,
+                    ..canvas::Text::default()
+                });
+            }
+        }
+
+        let mut samples = self.samples.iter().rev();
+        let current = samples.next().map(|sample| sample.speed).unwrap_or(0.0);
+        let previous = samples.next().map(|sample| sample.speed).unwrap_or(current);
+
+        let color = if current > previous {
+            GAIN
+        } else if current < previous {
+            LOSS
+        } else {
+            Color::WHITE
+        };
+
+        let content = match self.takeoff {
+            Some(takeoff) => format!("{current:.0} ({takeoff:.0})"),
+            None => format!("{current:.0}"),
+        };
+
+        frame.fill_text(canvas::Text {
+            content,
+            position: Point::new(
+                top_left.x + size.width / 2.0,
+                top_left.y + size.height + 4.0,
+            ),
+            color,
+            size: 24.0,
+            horizontal_alignment: Horizontal::Center,
+            vertical_alignment: Vertical::Top,
+            ..canvas::Text::default()
+        });
+
+        vec![frame.into_geometry()]
+    }
+}
@@ -0,0 +1,486 @@
+use crate::Bones;
+use elysium_math::{Matrix3x4, Vec3};
+
+/// A hitbox as stored in a model (`mstudiobbox_t`), relative to its bone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HitboxDef {
+    pub bone: usize,
+    pub group: i32,
+    pub min: Vec3,
+    pub max: Vec3,
+    /// Rotation of an oriented box relative to its bone, in degrees.
+    pub orientation: Vec3,
+    /// Capsule radius, a box when not positive.
+    pub radius: f32,
+}
+
+/// A ray starting at `origin`, heading in `direction`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+/// A line segment swept by a sphere.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capsule {
+    pub start: Vec3,
+    pub end: Vec3,
+    pub radius: f32,
+}
+
+/// An oriented bounding box, `min`/`max` are in `transform` space.
+#[derive(Clone, Copy, Debug)]
+pub struct Obb {
+    pub transform: Matrix3x4,
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+/// A hitbox in world space.
+#[derive(Clone, Copy, Debug)]
+pub enum Hitbox {
+    Capsule(Capsule),
+    Obb(Obb),
+}
+
+impl HitboxDef {
+    pub const fn new(bone: usize, min: Vec3, max: Vec3, radius: f32) -> HitboxDef {
+        Self {
+            bone,
+            group: 0,
+            min,
+            max,
+            orientation: Vec3::zero(),
+            radius,
+        }
+    }
+
+    pub const fn is_capsule(&self) -> bool {
+        self.radius > 0.0
+    }
+
+    /// Place this hitbox in world space using `bones`.
+    pub fn to_world(&self, bones: &Bones) -> Option<Hitbox> {
+        let bone = bones.get(self.bone)?;
+
+        let hitbox = if self.is_capsule() {
+            Hitbox::Capsule(Capsule {
+                start: bone.transform(self.min),
+                end: bone.transform(self.max),
+                radius: self.radius,
+            })
+        } else {
+            Hitbox::Obb(Obb {
+                transform: bone.concat(&Matrix3x4::from_angle(self.orientation)),
+                min: self.min,
+                max: self.max,
+            })
+        };
+
+        Some(hitbox)
+    }
+}
+
+impl Ray {
+    /// A ray from `start` towards `end`, `direction` is normalized.
+    pub fn between(start: Vec3, end: Vec3) -> Ray {
+        Self {
+            origin: start,
+            direction: (end - start).normalize(),
+        }
+    }
+
+    /// The point `distance` units along the ray.
+    pub fn at(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * Vec3::splat(distance)
+    }
+}
+
+impl Capsule {
+    pub fn center(&self) -> Vec3 {
+        (self.start + self.end) * Vec3::splat(0.5)
+    }
+
+    /// Closest point on the capsule's segment to `point`.
+    pub fn closest_point_on_segment(&self, point: Vec3) -> Vec3 {
+        let axis = self.end - self.start;
+        let len_squared = axis.magnitude_squared();
+
+        if len_squared == 0.0 {
+            return self.start;
+        }
+
+        let t = ((point - self.start).dot(axis) / len_squared).clamp(0.0, 1.0);
+
+        self.start + axis * Vec3::splat(t)
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        self.closest_point_on_segment(point).distance_squared(point) <= self.radius * self.radius
+    }
+
+    /// Closest point on or in the capsule to `point`.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        let on_segment = self.closest_point_on_segment(point);
+        let offset = point - on_segment;
+        let distance = offset.magnitude();
+
+        if distance <= self.radius {
+            point
+        } else {
+            on_segment + offset * Vec3::splat(self.radius / distance)
+        }
+    }
+
+    /// Distance along `ray` to the first intersection.
+    pub fn intersect(&self, ray: &Ray) -> Option<f32> {
+        let radius_squared = self.radius * self.radius;
+        let axis = self.end - self.start;
+        let axis_len_squared = axis.magnitude_squared();
+        let offset = ray.origin - self.start;
+
+        let mut nearest = None;
+        let mut hit = |t: f32| {
+            if t >= 0.0 && !matches!(nearest, Some(nearest) if nearest <= t) {
+                nearest = Some(t);
+            }
+        };
+
+        // infinite cylinder, limited to the segment
+        if axis_len_squared > 0.0 {
+            let axis_dot_dir = axis.dot(ray.direction);
+            let axis_dot_offset = axis.dot(offset);
+
+            let a =
+                axis_len_squared * ray.direction.magnitude_squared() - axis_dot_dir * axis_dot_dir;
+            let b = axis_len_squared * offset.dot(ray.direction) - axis_dot_offset * axis_dot_dir;
+            let c = axis_len_squared * (offset.magnitude_squared() - radius_squared)
+                - axis_dot_offset * axis_dot_offset;
+
+            if let Some((t0, t1)) = solve_quadratic(a, b, c) {
+                for t in [t0, t1] {
+                    let along = axis_dot_offset + t * axis_dot_dir;
+
+                    if (0.0..=axis_len_squared).contains(&along) {
+                        hit(t);
+                    }
+                }
+            }
+        }
+
+        // end caps
+        for center in [self.start, self.end] {
+            let offset = ray.origin - center;
+            let a = ray.direction.magnitude_squared();
+            let b = offset.dot(ray.direction);
+            let c = offset.magnitude_squared() - radius_squared;
+
+            if let Some((t0, t1)) = solve_quadratic(a, b, c) {
+                hit(t0);
+                hit(t1);
+            }
+        }
+
+        if self.contains(ray.origin) {
+            return Some(0.0);
+        }
+
+        nearest
+    }
+}
+
+impl Obb {
+    pub fn center(&self) -> Vec3 {
+        self.transform
+            .transform((self.min + self.max) * Vec3::splat(0.5))
+    }
+
+    /// The eight corners of the box in world space.
+    pub fn corners(&self) -> [Vec3; 8] {
+        let Obb { min, max, .. } = *self;
+
+        [
+            Vec3::from_xyz(min.x, min.y, min.z),
+            Vec3::from_xyz(max.x, min.y, min.z),
+            Vec3::from_xyz(max.x, max.y, min.z),
+            Vec3::from_xyz(min.x, max.y, min.z),
+            Vec3::from_xyz(min.x, min.y, max.z),
+            Vec3::from_xyz(max.x, min.y, max.z),
+            Vec3::from_xyz(max.x, max.y, max.z),
+            Vec3::from_xyz(min.x, max.y, max.z),
+        ]
+        .map(|corner| self.transform.transform(corner))
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        let local = self.transform.inverse_transform(point);
+
+        (self.min.x..=self.max.x).contains(&local.x)
+            && (self.min.y..=self.max.y).contains(&local.y)
+            && (self.min.z..=self.max.z).contains(&local.z)
+    }
+
+    /// Closest point on or in the box to `point`.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        let local = self.transform.inverse_transform(point);
+        let clamped = Vec3::from_xyz(
+            local.x.clamp(self.min.x, self.max.x),
+            local.y.clamp(self.min.y, self.max.y),
+            local.z.clamp(self.min.z, self.max.z),
+        );
+
+        self.transform.transform(clamped)
+    }
+
+    /// Distance along `ray` to the first intersection (slab test in box space).
+    pub fn intersect(&self, ray: &Ray) -> Option<f32> {
+        let origin = self.transform.inverse_transform(ray.origin);
+        let direction = self.transform.inverse_rotate(ray.direction);
+
+        let mut near = 0.0_f32;
+        let mut far = f32::INFINITY;
+
+        let slabs = [
+            (origin.x, direction.x, self.min.x, self.max.x),
+            (origin.y, direction.y, self.min.y, self.max.y),
+            (origin.z, direction.z, self.min.z, self.max.z),
+        ];
+
+        for (origin, direction, min, max) in slabs {
+            if direction == 0.0 {
+                if origin < min || origin > max {
+                    return None;
+                }
+
+                continue;
+            }
+
+            let t0 = (min - origin) / direction;
+            let t1 = (max - origin) / direction;
+
+            near = near.max(t0.min(t1));
+            far = far.min(t0.max(t1));
+
+            if near > far {
+                return None;
+            }
+        }
+
+        Some(near)
+    }
+}
+
+impl Hitbox {
+    pub fn center(&self) -> Vec3 {
+        match self {
+            Hitbox::Capsule(capsule) => capsule.center(),
+            Hitbox::Obb(obb) => obb.center(),
+        }
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        match self {
+            Hitbox::Capsule(capsule) => capsule.contains(point),
+            Hitbox::Obb(obb) => obb.contains(point),
+        }
+    }
+
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        match self {
+            Hitbox::Capsule(capsule) => capsule.closest_point(point),
+            Hitbox::Obb(obb) => obb.closest_point(point),
+        }
+    }
+
+    pub fn intersect(&self, ray: &Ray) -> Option<f32> {
+        match self {
+            Hitbox::Capsule(capsule) => capsule.intersect(ray),
+            Hitbox::Obb(obb) => obb.intersect(ray),
+        }
+    }
+}
+
+/// Roots of `a*t^2 + 2*b*t + c`, smallest first.
+fn solve_quadratic(a: f32, b: f32, c: f32) -> Option<(f32, f32)> {
+    if a == 0.0 {
+        return None;
+    }
+
+    let discriminant = b * b - a * c;
+
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let root = discriminant.sqrt();
+
+    Some(((-b - root) / a, (-b + root) / a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bones() -> Bones {
+        let mut bones = Bones::zero();
+
+        // identity rotation, at (10, 0, 0)
+        bones[0] = Matrix3x4::from_xyzw(
+            Vec3::from_xyz(1.0, 0.0, 0.0),
+            Vec3::from_xyz(0.0, 1.0, 0.0),
+            Vec3::from_xyz(0.0, 0.0, 1.0),
+            Vec3::from_xyz(10.0, 0.0, 0.0),
+        );
+
+        // rotated 90 degrees around z, at the origin
+        bones[1] = Matrix3x4::from_xyzw(
+            Vec3::from_xyz(0.0, 1.0, 0.0),
+            Vec3::from_xyz(-1.0, 0.0, 0.0),
+            Vec3::from_xyz(0.0, 0.0, 1.0),
+            Vec3::zero(),
+        );
+
+        bones
+    }
+
+    fn capsule() -> Capsule {
+        let def = HitboxDef::new(
+            0,
+            Vec3::from_xyz(0.0, 0.0, 0.0),
+            Vec3::from_xyz(0.0, 0.0, 10.0),
+            2.0,
+        );
+
+        match def.to_world(&bones()) {
+            Some(Hitbox::Capsule(capsule)) => capsule,
+            hitbox => panic!("expected a capsule, got {hitbox:?}"),
+        }
+    }
+
+    fn obb() -> Obb {
+        let def = HitboxDef::new(
+            1,
+            Vec3::from_xyz(0.0, -1.0, -1.0),
+            Vec3::from_xyz(4.0, 1.0, 1.0),
+            -1.0,
+        );
+
+        match def.to_world(&bones()) {
+            Some(Hitbox::Obb(obb)) => obb,
+            hitbox => panic!("expected an obb, got {hitbox:?}"),
+        }
+    }
+
+    fn assert_close(a: Vec3, b: Vec3) {
+        assert!(a.distance(b) < 1e-4, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn capsule_world_space() {
+        let capsule = capsule();
+
+        assert_close(capsule.start, Vec3::from_xyz(10.0, 0.0, 0.0));
+        assert_close(capsule.end, Vec3::from_xyz(10.0, 0.0, 10.0));
+        assert_close(capsule.center(), Vec3::from_xyz(10.0, 0.0, 5.0));
+    }
+
+    #[test]
+    fn capsule_ray() {
+        let capsule = capsule();
+        let side = Ray::between(
+            Vec3::from_xyz(0.0, 0.0, 5.0),
+            Vec3::from_xyz(20.0, 0.0, 5.0),
+        );
+        let above = Ray::between(
+            Vec3::from_xyz(10.0, 0.0, 20.0),
+            Vec3::from_xyz(10.0, 0.0, 0.0),
+        );
+        let miss = Ray::between(
+            Vec3::from_xyz(0.0, 5.0, 5.0),
+            Vec3::from_xyz(20.0, 5.0, 5.0),
+        );
+        let away = Ray::between(
+            Vec3::from_xyz(0.0, 0.0, 5.0),
+            Vec3::from_xyz(-20.0, 0.0, 5.0),
+        );
+
+        assert!((capsule.intersect(&side).unwrap() - 8.0).abs() < 1e-4);
+        assert!((capsule.intersect(&above).unwrap() - 8.0).abs() < 1e-4);
+        assert_eq!(capsule.intersect(&miss), None);
+        assert_eq!(capsule.intersect(&away), None);
+    }
+
+    #[test]
+    fn capsule_closest_point() {
+        let capsule = capsule();
+
+        assert_close(
+            capsule.closest_point(Vec3::from_xyz(0.0, 0.0, 5.0)),
+            Vec3::from_xyz(8.0, 0.0, 5.0),
+        );
+        assert_close(
+            capsule.closest_point(Vec3::from_xyz(10.0, 0.0, 20.0)),
+            Vec3::from_xyz(10.0, 0.0, 12.0),
+        );
+        assert!(capsule.contains(Vec3::from_xyz(11.0, 0.0, 3.0)));
+    }
+
+    #[test]
+    fn obb_world_space() {
+        let obb = obb();
+
+        // +x in bone space is +y in world space
+        assert!(obb.contains(Vec3::from_xyz(0.0, 3.0, 0.0)));
+        assert!(!obb.contains(Vec3::from_xyz(3.0, 0.0, 0.0)));
+        assert_close(obb.center(), Vec3::from_xyz(0.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn obb_ray() {
+        let obb = obb();
+        let hit = Ray::between(
+            Vec3::from_xyz(0.0, 2.0, 10.0),
+            Vec3::from_xyz(0.0, 2.0, 0.0),
+        );
+        let miss = Ray::between(
+            Vec3::from_xyz(2.0, 0.0, 10.0),
+            Vec3::from_xyz(2.0, 0.0, 0.0),
+        );
+
+        assert!((obb.intersect(&hit).unwrap() - 9.0).abs() < 1e-4);
+        assert_eq!(obb.intersect(&miss), None);
+    }
+
+    #[test]
+    fn obb_closest_point() {
+        let obb = obb();
+
+        assert_close(
+            obb.closest_point(Vec3::from_xyz(0.0, 10.0, 0.0)),
+            Vec3::from_xyz(0.0, 4.0, 0.0),
+        );
+        assert_close(
+            obb.closest_point(Vec3::from_xyz(0.0, 2.0, 5.0)),
+            Vec3::from_xyz(0.0, 2.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn oriented_box() {
+        let mut def = HitboxDef::new(
+            0,
+            Vec3::from_xyz(0.0, -1.0, -1.0),
+            Vec3::from_xyz(4.0, 1.0, 1.0),
+            -1.0,
+        );
+
+        // yaw 90 degrees, box extends along +y from the bone
+        def.orientation = Vec3::from_xyz(0.0, 90.0, 0.0);
+
+        let hitbox = def.to_world(&bones()).unwrap();
+
+        assert!(hitbox.contains(Vec3::from_xyz(10.0, 3.0, 0.0)));
+        assert!(!hitbox.contains(Vec3::from_xyz(13.0, 0.0, 0.0)));
+    }
+}
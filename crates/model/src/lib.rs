@@ -1,6 +1,12 @@
 use core::ops;
 use elysium_math::{Matrix3x4, Vec3};
 
+pub use hitbox::{Capsule, Hitbox, HitboxDef, Obb, Ray};
+pub use skeleton::Skeleton;
+
+mod hitbox;
+mod skeleton;
+
 pub const MAX_BONES: usize = 256;
 
 #[derive(Clone, Copy, Debug)]
@@ -43,6 +49,11 @@ impl Bones {
         self.get(index).map(|bone| bone.w_axis())
     }
 
+    /// Place each hitbox in world space, skipping any with an invalid bone.
+    pub fn hitboxes<'a>(&'a self, defs: &'a [HitboxDef]) -> impl Iterator<Item = Hitbox> + 'a {
+        defs.iter().filter_map(|def| def.to_world(self))
+    }
+
     pub unsafe fn get_unchecked(&self, index: usize) -> &Matrix3x4 {
         &*self.as_ptr().add(index)
     }
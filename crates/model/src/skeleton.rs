@@ -0,0 +1,176 @@
+use crate::Bones;
+use elysium_math::Vec3;
+
+/// Bone hierarchy of a model, indexed the same as [`Bones`].
+#[derive(Clone, Debug, Default)]
+pub struct Skeleton {
+    names: Vec<Box<str>>,
+    parents: Vec<Option<usize>>,
+    used_by: Vec<i32>,
+}
+
+impl Skeleton {
+    pub const fn new() -> Skeleton {
+        Self {
+            names: Vec::new(),
+            parents: Vec::new(),
+            used_by: Vec::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Skeleton {
+        Self {
+            names: Vec::with_capacity(capacity),
+            parents: Vec::with_capacity(capacity),
+            used_by: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Append a bone, returning its index.
+    ///
+    /// `used_by` is a mask of `sdk::model::UsedBy` flags, a `parent` which doesn't precede this
+    /// bone is treated as no parent.
+    pub fn push(&mut self, name: &str, parent: Option<usize>, used_by: i32) -> usize {
+        let index = self.len();
+
+        self.names.push(name.into());
+        self.parents.push(parent.filter(|parent| *parent < index));
+        self.used_by.push(used_by);
+
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.names.get(index).map(|name| &**name)
+    }
+
+    pub fn parent(&self, index: usize) -> Option<usize> {
+        self.parents.get(index).copied().flatten()
+    }
+
+    pub fn used_by(&self, index: usize) -> i32 {
+        self.used_by.get(index).copied().unwrap_or(0)
+    }
+
+    /// Whether the bone is used by anything in `mask`.
+    pub fn is_used_by(&self, index: usize, mask: i32) -> bool {
+        self.used_by(index) & mask != 0
+    }
+
+    /// Index of the bone named `name`.
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|bone| &**bone == name)
+    }
+
+    /// Indices of the bones whose parent is `index`.
+    pub fn children(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter_map(move |(child, parent)| (*parent == Some(index)).then_some(child))
+    }
+
+    /// Every `(parent, child)` pair.
+    pub fn segments(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter_map(|(child, parent)| Some(((*parent)?, child)))
+    }
+
+    /// `(parent, child)` pairs where the child is used by anything in `mask`.
+    pub fn segments_used_by(&self, mask: i32) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.segments()
+            .filter(move |(_parent, child)| self.is_used_by(*child, mask))
+    }
+
+    /// World-space lines for drawing the skeleton, limited to bones used by anything in `mask`.
+    pub fn lines<'a>(
+        &'a self,
+        bones: &'a Bones,
+        mask: i32,
+    ) -> impl Iterator<Item = (Vec3, Vec3)> + 'a {
+        self.segments_used_by(mask).filter_map(|(parent, child)| {
+            Some((bones.get_origin(parent)?, bones.get_origin(child)?))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elysium_math::Matrix3x4;
+
+    const HITBOX: i32 = 0x100;
+    const ATTACHMENT: i32 = 0x200;
+
+    fn skeleton() -> Skeleton {
+        let mut skeleton = Skeleton::new();
+
+        skeleton.push("pelvis", None, HITBOX);
+        skeleton.push("spine_0", Some(0), HITBOX);
+        skeleton.push("head_0", Some(1), HITBOX);
+        skeleton.push("weapon_hand_R", Some(1), ATTACHMENT);
+
+        skeleton
+    }
+
+    #[test]
+    fn lookup() {
+        let skeleton = skeleton();
+
+        assert_eq!(skeleton.len(), 4);
+        assert_eq!(skeleton.index("head_0"), Some(2));
+        assert_eq!(skeleton.index("head"), None);
+        assert_eq!(skeleton.name(1), Some("spine_0"));
+        assert_eq!(skeleton.parent(0), None);
+        assert_eq!(skeleton.parent(2), Some(1));
+        assert_eq!(skeleton.children(1).collect::<Vec<_>>(), [2, 3]);
+    }
+
+    #[test]
+    fn invalid_parent() {
+        let mut skeleton = skeleton();
+        let index = skeleton.push("broken", Some(10), HITBOX);
+
+        assert_eq!(skeleton.parent(index), None);
+    }
+
+    #[test]
+    fn segments() {
+        let skeleton = skeleton();
+
+        assert_eq!(
+            skeleton.segments().collect::<Vec<_>>(),
+            [(0, 1), (1, 2), (1, 3)]
+        );
+        assert_eq!(
+            skeleton.segments_used_by(HITBOX).collect::<Vec<_>>(),
+            [(0, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn lines() {
+        let skeleton = skeleton();
+        let mut bones = Bones::zero();
+
+        for (index, bone) in bones.iter_mut().take(4).enumerate() {
+            *bone = Matrix3x4::zero().with_w_axis(Vec3::from_xyz(0.0, 0.0, index as f32));
+        }
+
+        let lines = skeleton.lines(&bones, HITBOX).collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].0, Vec3::from_xyz(0.0, 0.0, 1.0));
+        assert_eq!(lines[1].1, Vec3::from_xyz(0.0, 0.0, 2.0));
+    }
+}
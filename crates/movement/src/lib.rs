@@ -0,0 +1,377 @@
+//! Per-jump movement statistics, for movement training on practice maps.
+
+#![deny(warnings)]
+
+use core::fmt;
+use elysium_math::Vec3;
+
+/// Width of the player's hull, which jump distances include, like KZ plugins measure them.
+pub const HULL_WIDTH: f32 = 32.0;
+
+/// The local player as of a tick.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Tick {
+    pub origin: Vec3,
+    pub velocity: Vec3,
+    pub on_ground: bool,
+    /// Whether jump is held.
+    pub jumping: bool,
+    /// Side move of the command, negative for left.
+    pub side_move: f32,
+}
+
+/// Statistics of a completed jump.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Jump {
+    /// Horizontal speed at takeoff.
+    pub pre: f32,
+    /// Highest horizontal speed in the air.
+    pub max: f32,
+    /// Horizontal speed gained in the air, summed over every tick it went up.
+    pub gain: f32,
+    /// Changes of strafe direction, including the first strafe.
+    pub strafes: u32,
+    /// Percentage of air ticks gaining speed.
+    pub sync: f32,
+    /// Horizontal distance from the last tick on the ground to landing, including the hull.
+    pub distance: f32,
+    /// Highest point above the takeoff.
+    pub height: f32,
+    /// Distance from the front of the hull to the edge jumped from, if one was found.
+    pub edge: Option<f32>,
+    /// Ticks spent in the air.
+    pub air_ticks: u32,
+}
+
+impl fmt::Display for Jump {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "{:.1} units, pre {:.0}, max {:.0}, gain {:.0}, {} strafes, {:.0}% sync, height {:.1}",
+            self.distance, self.pre, self.max, self.gain, self.strafes, self.sync, self.height
+        )?;
+
+        if let Some(edge) = self.edge {
+            write!(fmt, ", edge {edge:.1}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// What happened this tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Event {
+    /// Left the ground by jumping, the edge can now be set with [`Tracker::set_edge`].
+    Takeoff,
+    Landed(Jump),
+}
+
+/// The jump in progress.
+#[derive(Clone, Copy, Debug)]
+struct Air {
+    takeoff: Vec3,
+    pre: f32,
+    max: f32,
+    gain: f32,
+    top: f32,
+    last_speed: f32,
+    ticks: u32,
+    gaining_ticks: u32,
+    strafes: u32,
+    /// Sign of the last non-zero side move.
+    last_side: f32,
+    edge: Option<f32>,
+}
+
+impl Air {
+    #[inline]
+    fn new(takeoff: Vec3, tick: &Tick, speed: f32) -> Self {
+        Self {
+            takeoff,
+            pre: speed,
+            max: speed,
+            gain: 0.0,
+            top: tick.origin.z,
+            last_speed: speed,
+            ticks: 0,
+            gaining_ticks: 0,
+            strafes: 0,
+            last_side: 0.0,
+            edge: None,
+        }
+    }
+
+    #[inline]
+    fn track(&mut self, tick: &Tick, speed: f32) {
+        self.ticks += 1;
+        self.max = self.max.max(speed);
+        self.top = self.top.max(tick.origin.z);
+
+        if speed > self.last_speed {
+            self.gain += speed - self.last_speed;
+            self.gaining_ticks += 1;
+        }
+
+        self.last_speed = speed;
+
+        if tick.side_move != 0.0 {
+            let side = tick.side_move.signum();
+
+            if side != self.last_side {
+                self.strafes += 1;
+                self.last_side = side;
+            }
+        }
+    }
+
+    #[inline]
+    fn finish(&self, landing: Vec3) -> Jump {
+        let sync = if self.ticks == 0 {
+            0.0
+        } else {
+            self.gaining_ticks as f32 / self.ticks as f32 * 100.0
+        };
+
+        Jump {
+            pre: self.pre,
+            max: self.max,
+            gain: self.gain,
+            strafes: self.strafes,
+            sync,
+            distance: landing.distance2d(self.takeoff) + HULL_WIDTH,
+            height: self.top - self.takeoff.z,
+            edge: self.edge,
+            air_ticks: self.ticks,
+        }
+    }
+}
+
+/// Tracks jumps tick by tick.
+#[derive(Clone, Debug)]
+pub struct Tracker {
+    air: Option<Air>,
+    was_on_ground: bool,
+    /// Jump was held while on the ground, so leaving it is a jump rather than a fall.
+    jump_queued: bool,
+    /// Origin as of the last tick on the ground.
+    last_ground: Vec3,
+    speed: f32,
+}
+
+const NEW: Tracker = Tracker {
+    air: None,
+    was_on_ground: false,
+    jump_queued: false,
+    last_ground: Vec3::zero(),
+    speed: 0.0,
+};
+
+impl Tracker {
+    #[inline]
+    pub const fn new() -> Self {
+        NEW
+    }
+
+    /// Horizontal speed as of the last tick.
+    #[inline]
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    #[inline]
+    pub fn is_jumping(&self) -> bool {
+        self.air.is_some()
+    }
+
+    /// Where the jump in progress left the ground.
+    #[inline]
+    pub fn takeoff(&self) -> Option<Vec3> {
+        self.air.as_ref().map(|air| air.takeoff)
+    }
+
+    /// Forget the jump in progress, such as when on a ladder or respawning.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = NEW;
+    }
+
+    /// Set the edge distance of the jump in progress.
+    #[inline]
+    pub fn set_edge(&mut self, edge: Option<f32>) {
+        if let Some(air) = self.air.as_mut() {
+            air.edge = edge;
+        }
+    }
+
+    pub fn update(&mut self, tick: Tick) -> Option<Event> {
+        let speed = tick.velocity.magnitude2d();
+        let was_on_ground = self.was_on_ground;
+        let jump_queued = self.jump_queued;
+        let last_ground = self.last_ground;
+
+        self.speed = speed;
+        self.was_on_ground = tick.on_ground;
+        self.jump_queued = tick.on_ground && tick.jumping;
+
+        if tick.on_ground {
+            self.last_ground = tick.origin;
+        }
+
+        match (was_on_ground, tick.on_ground) {
+            // predicted ticks leave the ground on the tick jump is pressed
+            (true, false) if jump_queued || tick.jumping => {
+                self.air = Some(Air::new(last_ground, &tick, speed));
+
+                Some(Event::Takeoff)
+            }
+            (_, false) => {
+                if let Some(air) = self.air.as_mut() {
+                    air.track(&tick, speed);
+                }
+
+                None
+            }
+            (false, true) => {
+                let air = self.air.take()?;
+
+                Some(Event::Landed(air.finish(tick.origin)))
+            }
+            (true, true) => None,
+        }
+    }
+}
+
+impl Default for Tracker {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ground(x: f32, speed: f32, jumping: bool) -> Tick {
+        Tick {
+            origin: Vec3::from_xyz(x, 0.0, 0.0),
+            velocity: Vec3::from_xyz(speed, 0.0, 0.0),
+            on_ground: true,
+            jumping,
+            side_move: 0.0,
+        }
+    }
+
+    fn air(x: f32, z: f32, speed: f32, side_move: f32) -> Tick {
+        Tick {
+            origin: Vec3::from_xyz(x, 0.0, z),
+            velocity: Vec3::from_xyz(speed, 0.0, 0.0),
+            on_ground: false,
+            jumping: false,
+            side_move,
+        }
+    }
+
+    #[test]
+    fn jump() {
+        let mut tracker = Tracker::new();
+
+        assert_eq!(tracker.update(ground(0.0, 250.0, false)), None);
+        assert_eq!(tracker.update(ground(4.0, 250.0, true)), None);
+        assert_eq!(
+            tracker.update(air(8.0, 4.0, 250.0, 0.0)),
+            Some(Event::Takeoff)
+        );
+
+        assert_eq!(tracker.takeoff(), Some(Vec3::from_xyz(4.0, 0.0, 0.0)));
+
+        tracker.set_edge(Some(2.5));
+
+        // left, left, right, right, left, losing speed once
+        let ticks = [
+            (60.0, 30.0, 260.0, -450.0),
+            (120.0, 50.0, 270.0, -450.0),
+            (180.0, 56.0, 265.0, 450.0),
+            (240.0, 30.0, 280.0, 450.0),
+            (250.0, 10.0, 290.0, -450.0),
+        ];
+
+        for (x, z, speed, side_move) in ticks {
+            assert_eq!(tracker.update(air(x, z, speed, side_move)), None);
+            assert!(tracker.is_jumping());
+        }
+
+        let jump = match tracker.update(ground(258.0, 290.0, false)) {
+            Some(Event::Landed(jump)) => jump,
+            event => panic!("expected a landing, got {event:?}"),
+        };
+
+        assert_eq!(
+            jump,
+            Jump {
+                pre: 250.0,
+                max: 290.0,
+                gain: 45.0,
+                strafes: 3,
+                sync: 80.0,
+                distance: 254.0 + HULL_WIDTH,
+                height: 56.0,
+                edge: Some(2.5),
+                air_ticks: 5,
+            }
+        );
+
+        assert!(!tracker.is_jumping());
+        assert_eq!(tracker.speed(), 290.0);
+    }
+
+    #[test]
+    fn predicted_jump() {
+        let mut tracker = Tracker::new();
+
+        tracker.update(ground(0.0, 250.0, false));
+
+        let mut takeoff = air(8.0, 4.0, 250.0, 0.0);
+
+        takeoff.jumping = true;
+
+        assert_eq!(tracker.update(takeoff), Some(Event::Takeoff));
+        assert_eq!(tracker.takeoff(), Some(Vec3::zero()));
+    }
+
+    #[test]
+    fn falling_is_not_a_jump() {
+        let mut tracker = Tracker::new();
+
+        tracker.update(ground(0.0, 250.0, false));
+
+        assert_eq!(tracker.update(air(8.0, -4.0, 250.0, 0.0)), None);
+        assert!(!tracker.is_jumping());
+        assert_eq!(tracker.update(ground(16.0, 250.0, false)), None);
+    }
+
+    #[test]
+    fn display() {
+        let jump = Jump {
+            pre: 250.4,
+            max: 290.0,
+            gain: 45.0,
+            strafes: 3,
+            sync: 80.0,
+            distance: 240.26,
+            height: 52.0,
+            edge: Some(2.5),
+            air_ticks: 5,
+        };
+
+        assert_eq!(
+            jump.to_string(),
+            "240.3 units, pre 250, max 290, gain 45, 3 strafes, 80% sync, height 52.0, edge 2.5"
+        );
+
+        let jump = Jump { edge: None, ..jump };
+
+        assert!(jump.to_string().ends_with("height 52.0"));
+    }
+}
@@ -0,0 +1,301 @@
+//! Recorded movement input, for replaying practice routines such as jump spots and strafe lines.
+//!
+//! A recording is saved as a small header followed by each tick's origin and its command, delta
+//! encoded against the previous one like the engine sends them.
+
+#![deny(warnings)]
+
+pub use playback::{Playback, Step};
+
+mod playback;
+
+use core::fmt;
+use elysium_math::Vec3;
+use elysium_sdk::bf::{Overflow, Reader, Writer};
+use elysium_sdk::Command;
+
+/// Start of every recording file.
+const MAGIC: &[u8; 4] = b"EREC";
+
+/// Bumped whenever the layout changes.
+const VERSION: u8 = 1;
+
+/// Failed to load a recording.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Not a recording.
+    Magic,
+    /// Recorded by an incompatible version.
+    Version(u8),
+    /// Ended part way through.
+    Truncated,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Magic => fmt.write_str("not a recording"),
+            Error::Version(version) => write!(fmt, "unsupported recording version {version}"),
+            Error::Truncated => fmt.write_str("recording is truncated"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Overflow> for Error {
+    #[inline]
+    fn from(_overflow: Overflow) -> Self {
+        Error::Truncated
+    }
+}
+
+/// A tick of a recording.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frame {
+    /// The local player's origin before the command ran.
+    pub origin: Vec3,
+    pub command: Command,
+}
+
+impl Frame {
+    /// Replace the angles, movement and buttons of `command` with the recorded ones.
+    ///
+    /// The command number and tick count are left alone, as they belong to the current game.
+    #[inline]
+    pub fn apply(&self, command: &mut Command) {
+        command.view_angle = self.command.view_angle;
+        command.movement = self.command.movement;
+        command.set_buttons(self.command.buttons());
+        command.mouse_dx = self.command.mouse_dx;
+        command.mouse_dy = self.command.mouse_dy;
+    }
+}
+
+/// A sequence of commands, and where they started.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recording {
+    /// Map the recording was made on.
+    pub map: String,
+    /// Seconds per tick of the server the recording was made on.
+    pub tick_interval: f32,
+    /// The local player's origin at the start.
+    pub origin: Vec3,
+    /// The local player's view angle at the start.
+    pub view_angle: Vec3,
+    frames: Vec<Frame>,
+}
+
+impl Recording {
+    #[inline]
+    pub fn new(map: &str, tick_interval: f32, origin: Vec3, view_angle: Vec3) -> Self {
+        Self {
+            map: map.into(),
+            tick_interval,
+            origin,
+            view_angle,
+            frames: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, origin: Vec3, command: &Command) {
+        self.frames.push(Frame {
+            origin,
+            command: *command,
+        });
+    }
+
+    #[inline]
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Length in seconds.
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        self.frames.len() as f32 * self.tick_interval
+    }
+
+    /// Serialize to the recording file format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer::new();
+
+        writer.write_bytes(MAGIC);
+        writer.write_u8(VERSION);
+        writer.write_string(&self.map);
+        writer.write_f32(self.tick_interval);
+        write_vec3(&mut writer, self.origin);
+        write_vec3(&mut writer, self.view_angle);
+        writer.write_varint32(self.frames.len() as u32);
+
+        let mut previous = Command::new();
+
+        for frame in self.frames.iter() {
+            write_vec3(&mut writer, frame.origin);
+            frame.command.write_delta(&mut writer, &previous);
+            previous = frame.command;
+        }
+
+        writer.into_bytes()
+    }
+
+    /// Deserialize from the recording file format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+
+        if reader.read_bytes(MAGIC.len()).map_err(|_| Error::Magic)? != MAGIC {
+            return Err(Error::Magic);
+        }
+
+        let version = reader.read_u8()?;
+
+        if version != VERSION {
+            return Err(Error::Version(version));
+        }
+
+        let map = reader.read_string()?;
+        let tick_interval = reader.read_f32()?;
+        let origin = read_vec3(&mut reader)?;
+        let view_angle = read_vec3(&mut reader)?;
+        let len = reader.read_varint32()? as usize;
+
+        // every frame is at least its origin, don't trust `len` further than that
+        let mut frames = Vec::with_capacity(len.min(reader.bits_left() / 96));
+        let mut previous = Command::new();
+
+        for _ in 0..len {
+            let origin = read_vec3(&mut reader)?;
+            let command = Command::read_delta(&mut reader, &previous)?;
+
+            frames.push(Frame { origin, command });
+            previous = command;
+        }
+
+        Ok(Self {
+            map,
+            tick_interval,
+            origin,
+            view_angle,
+            frames,
+        })
+    }
+}
+
+#[inline]
+fn write_vec3(writer: &mut Writer, vec: Vec3) {
+    writer.write_f32(vec.x);
+    writer.write_f32(vec.y);
+    writer.write_f32(vec.z);
+}
+
+#[inline]
+fn read_vec3(reader: &mut Reader<'_>) -> Result<Vec3, Overflow> {
+    Ok(Vec3::from_xyz(
+        reader.read_f32()?,
+        reader.read_f32()?,
+        reader.read_f32()?,
+    ))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use elysium_sdk::Buttons;
+
+    /// A recording of walking forward along x, jumping half way.
+    pub fn recording() -> Recording {
+        let mut recording = Recording::new(
+            "de_dust2",
+            1.0 / 64.0,
+            Vec3::zero(),
+            Vec3::from_xyz(0.0, 90.0, 0.0),
+        );
+
+        for tick in 0..8 {
+            let mut command = Command::new();
+            let mut buttons = Buttons::FORWARD;
+
+            buttons.set(Buttons::JUMP, tick == 4);
+
+            command.command = tick + 1;
+            command.tick_count = tick + 100;
+            command.view_angle = Vec3::from_xyz(0.0, 90.0 + tick as f32, 0.0);
+            command.movement = Vec3::from_xyz(450.0, 0.0, 0.0);
+            command.set_buttons(buttons);
+
+            recording.push(Vec3::from_xyz(tick as f32 * 4.0, 0.0, 0.0), &command);
+        }
+
+        recording
+    }
+
+    #[test]
+    fn round_trip() {
+        let recording = recording();
+        let bytes = recording.to_bytes();
+        let mut loaded = Recording::from_bytes(&bytes).unwrap();
+
+        // the seed isn't sent, it's derived from the command number
+        for (loaded, frame) in loaded.frames.iter_mut().zip(recording.frames()) {
+            assert_eq!(
+                loaded.command.random_seed,
+                (elysium_sdk::md5::pseudo_random(frame.command.command as u32) & 0x7FFF_FFFF)
+                    as i32
+            );
+
+            loaded.command.random_seed = 0;
+        }
+
+        assert_eq!(loaded, recording);
+        assert_eq!(loaded.duration(), 8.0 / 64.0);
+    }
+
+    #[test]
+    fn errors() {
+        let bytes = recording().to_bytes();
+
+        assert_eq!(Recording::from_bytes(b"nope"), Err(Error::Magic));
+        assert_eq!(Recording::from_bytes(b""), Err(Error::Magic));
+        assert_eq!(
+            Recording::from_bytes(&bytes[..bytes.len() - 8]),
+            Err(Error::Truncated)
+        );
+
+        let mut future = bytes.clone();
+
+        future[4] = VERSION + 1;
+
+        assert_eq!(
+            Recording::from_bytes(&future),
+            Err(Error::Version(VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn apply() {
+        let recording = recording();
+        let frame = &recording.frames()[4];
+        let mut command = Command::new();
+
+        command.command = 1000;
+        command.tick_count = 2000;
+        frame.apply(&mut command);
+
+        assert_eq!(command.command, 1000);
+        assert_eq!(command.tick_count, 2000);
+        assert_eq!(command.view_angle, frame.command.view_angle);
+        assert_eq!(command.movement, frame.command.movement);
+        assert_eq!(command.buttons(), Buttons::FORWARD | Buttons::JUMP);
+    }
+}
@@ -0,0 +1,124 @@
+use super::{Frame, Recording};
+use elysium_math::Vec3;
+
+/// What to do this tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Step {
+    /// Apply this frame to the command.
+    Play(Frame),
+    /// Every frame has been played.
+    Finished,
+    /// The player is `distance` units from where the recording was at `tick`, playback stops.
+    Drifted { tick: usize, distance: f32 },
+}
+
+/// Plays a recording back tick by tick, stopping if the player strays from its path.
+#[derive(Clone, Debug)]
+pub struct Playback {
+    recording: Recording,
+    next: usize,
+    max_drift: f32,
+}
+
+impl Playback {
+    /// Play `recording`, stopping if the player ends up more than `max_drift` units off its path.
+    #[inline]
+    pub fn new(recording: Recording, max_drift: f32) -> Self {
+        Self {
+            recording,
+            next: 0,
+            max_drift,
+        }
+    }
+
+    #[inline]
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Frames played so far.
+    #[inline]
+    pub fn tick(&self) -> usize {
+        self.next
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.recording.len()
+    }
+
+    /// How far `origin` is from where the recording starts.
+    #[inline]
+    pub fn distance_from_start(&self, origin: Vec3) -> f32 {
+        origin.distance(self.recording.origin)
+    }
+
+    /// The next frame, given the player's `origin` before this tick's command runs.
+    pub fn next(&mut self, origin: Vec3) -> Step {
+        let frame = match self.recording.frames().get(self.next) {
+            Some(frame) => *frame,
+            None => return Step::Finished,
+        };
+
+        let distance = origin.distance(frame.origin);
+
+        if distance > self.max_drift {
+            // don't pick up where we left off
+            let tick = self.next;
+
+            self.next = self.recording.len();
+
+            return Step::Drifted { tick, distance };
+        }
+
+        self.next += 1;
+
+        Step::Play(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::recording;
+
+    #[test]
+    fn play() {
+        let recording = recording();
+        let mut playback = Playback::new(recording.clone(), 8.0);
+
+        for frame in recording.frames() {
+            assert_eq!(playback.next(frame.origin), Step::Play(*frame));
+        }
+
+        assert!(playback.is_finished());
+        assert_eq!(playback.next(Vec3::zero()), Step::Finished);
+    }
+
+    #[test]
+    fn drift() {
+        let recording = recording();
+        let mut playback = Playback::new(recording.clone(), 8.0);
+        let frames = recording.frames();
+
+        assert!(matches!(playback.next(frames[0].origin), Step::Play(_)));
+
+        // within tolerance
+        let origin = frames[1].origin + Vec3::from_xyz(0.0, 6.0, 0.0);
+
+        assert!(matches!(playback.next(origin), Step::Play(_)));
+
+        let origin = frames[2].origin + Vec3::from_xyz(0.0, 10.0, 0.0);
+
+        assert_eq!(
+            playback.next(origin),
+            Step::Drifted {
+                tick: 2,
+                distance: 10.0
+            }
+        );
+
+        assert!(playback.is_finished());
+        assert_eq!(playback.next(frames[3].origin), Step::Finished);
+    }
+}
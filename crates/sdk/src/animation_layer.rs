@@ -2,6 +2,7 @@ use crate::Pad;
 use core::fmt;
 
 /// An animation layer.
+#[derive(Clone, Copy)]
 #[repr(C)]
 pub struct AnimationLayer {
     _pad0: Pad<24>,
@@ -15,6 +16,23 @@ pub struct AnimationLayer {
     _pad1: Pad<16>,
 }
 
+impl AnimationLayer {
+    #[inline]
+    pub const fn zero() -> AnimationLayer {
+        AnimationLayer {
+            _pad0: Pad::uninit(),
+            order: 0,
+            sequence: 0,
+            previous_cycle: 0.0,
+            weight: 0.0,
+            weight_delta_rate: 0.0,
+            playback_rate: 0.0,
+            cycle: 0.0,
+            _pad1: Pad::uninit(),
+        }
+    }
+}
+
 impl fmt::Debug for AnimationLayer {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
@@ -0,0 +1,686 @@
+//! Bit buffers, like the engine's `bf_read` and `bf_write`.
+//!
+//! Bits are read and written least significant first, bytes in order, the same as `CBitRead`
+//! and `CBitWrite`.
+
+use core::fmt;
+
+/// `COORD_INTEGER_BITS`
+const COORD_INTEGER_BITS: u32 = 14;
+/// `COORD_FRACTIONAL_BITS`
+const COORD_FRACTIONAL_BITS: u32 = 5;
+/// `COORD_RESOLUTION`
+const COORD_RESOLUTION: f32 = 1.0 / (1 << COORD_FRACTIONAL_BITS) as f32;
+
+/// `COORD_INTEGER_BITS_MP`
+const COORD_INTEGER_BITS_MP: u32 = 11;
+/// `COORD_FRACTIONAL_BITS_MP_LOWPRECISION`
+const COORD_FRACTIONAL_BITS_LOW_PRECISION: u32 = 3;
+/// `COORD_RESOLUTION_LOWPRECISION`
+const COORD_RESOLUTION_LOW_PRECISION: f32 = 1.0 / (1 << COORD_FRACTIONAL_BITS_LOW_PRECISION) as f32;
+
+/// `NORMAL_FRACTIONAL_BITS`
+const NORMAL_FRACTIONAL_BITS: u32 = 11;
+/// `NORMAL_RESOLUTION`
+const NORMAL_RESOLUTION: f32 = 1.0 / ((1 << NORMAL_FRACTIONAL_BITS) - 1) as f32;
+
+/// attempted to read past the end of a buffer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("read past the end of the buffer")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// `bf_read`
+#[derive(Clone, Debug)]
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    /// in bits
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    #[inline]
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    /// position in bits
+    #[inline]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    #[inline]
+    pub const fn bits_left(&self) -> usize {
+        self.bytes.len() * 8 - self.position
+    }
+
+    #[inline]
+    pub fn skip(&mut self, bits: usize) -> Result<(), Overflow> {
+        if bits > self.bits_left() {
+            return Err(Overflow);
+        }
+
+        self.position += bits;
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn read_bit(&mut self) -> Result<bool, Overflow> {
+        let byte = self.bytes.get(self.position / 8).ok_or(Overflow)?;
+        let bit = (byte >> (self.position % 8)) & 1;
+
+        self.position += 1;
+
+        Ok(bit != 0)
+    }
+
+    /// read an unsigned integer of up to 32 bits (`ReadUBitLong`)
+    #[inline]
+    pub fn read_ubits(&mut self, bits: u32) -> Result<u32, Overflow> {
+        debug_assert!(bits <= 32);
+
+        if bits as usize > self.bits_left() {
+            return Err(Overflow);
+        }
+
+        let mut value = 0_u64;
+        let mut read = 0;
+
+        while read < bits {
+            let byte = self.bytes[self.position / 8] as u64;
+            let offset = (self.position % 8) as u32;
+            let take = (8 - offset).min(bits - read);
+            let chunk = (byte >> offset) & ((1 << take) - 1);
+
+            value |= chunk << read;
+            read += take;
+            self.position += take as usize;
+        }
+
+        Ok(value as u32)
+    }
+
+    /// read a sign-extended integer of up to 32 bits (`ReadSBitLong`)
+    #[inline]
+    pub fn read_sbits(&mut self, bits: u32) -> Result<i32, Overflow> {
+        let value = self.read_ubits(bits)?;
+
+        if bits == 0 || bits == 32 {
+            return Ok(value as i32);
+        }
+
+        let shift = 32 - bits;
+
+        Ok(((value << shift) as i32) >> shift)
+    }
+
+    #[inline]
+    pub fn read_u8(&mut self) -> Result<u8, Overflow> {
+        Ok(self.read_ubits(8)? as u8)
+    }
+
+    #[inline]
+    pub fn read_u16(&mut self) -> Result<u16, Overflow> {
+        Ok(self.read_ubits(16)? as u16)
+    }
+
+    #[inline]
+    pub fn read_i16(&mut self) -> Result<i16, Overflow> {
+        Ok(self.read_ubits(16)? as i16)
+    }
+
+    #[inline]
+    pub fn read_u32(&mut self) -> Result<u32, Overflow> {
+        self.read_ubits(32)
+    }
+
+    #[inline]
+    pub fn read_i32(&mut self) -> Result<i32, Overflow> {
+        Ok(self.read_ubits(32)? as i32)
+    }
+
+    /// `ReadFloat`, the raw bits of an `f32`
+    #[inline]
+    pub fn read_f32(&mut self) -> Result<f32, Overflow> {
+        Ok(f32::from_bits(self.read_ubits(32)?))
+    }
+
+    #[inline]
+    pub fn read_varint32(&mut self) -> Result<u32, Overflow> {
+        let mut value = 0;
+
+        for index in 0..5 {
+            let byte = self.read_u8()? as u32;
+
+            value |= (byte & 0x7F) << (7 * index);
+
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// zigzag encoded
+    #[inline]
+    pub fn read_signed_varint32(&mut self) -> Result<i32, Overflow> {
+        let value = self.read_varint32()?;
+
+        Ok(((value >> 1) as i32) ^ -((value & 1) as i32))
+    }
+
+    #[inline]
+    pub fn read_varint64(&mut self) -> Result<u64, Overflow> {
+        let mut value = 0;
+
+        for index in 0..10 {
+            let byte = self.read_u8()? as u64;
+
+            value |= (byte & 0x7F) << (7 * index);
+
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// zigzag encoded
+    #[inline]
+    pub fn read_signed_varint64(&mut self) -> Result<i64, Overflow> {
+        let value = self.read_varint64()?;
+
+        Ok(((value >> 1) as i64) ^ -((value & 1) as i64))
+    }
+
+    /// `ReadUBitVar`, a 4 to 32 bit integer prefixed with its size
+    #[inline]
+    pub fn read_ubitvar(&mut self) -> Result<u32, Overflow> {
+        let value = self.read_ubits(6)?;
+
+        let value = match value & (16 | 32) {
+            16 => (value & 15) | (self.read_ubits(4)? << 4),
+            32 => (value & 15) | (self.read_ubits(8)? << 4),
+            48 => (value & 15) | (self.read_ubits(32 - 4)? << 4),
+            _ => value,
+        };
+
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, Overflow> {
+        if len * 8 > self.bits_left() {
+            return Err(Overflow);
+        }
+
+        // fast path for byte-aligned reads
+        if self.position % 8 == 0 {
+            let start = self.position / 8;
+
+            self.position += len * 8;
+
+            return Ok(self.bytes[start..start + len].to_vec());
+        }
+
+        (0..len).map(|_| self.read_u8()).collect()
+    }
+
+    /// read a nul-terminated string, replacing invalid utf-8
+    #[inline]
+    pub fn read_string(&mut self) -> Result<String, Overflow> {
+        let mut bytes = Vec::new();
+
+        loop {
+            match self.read_u8()? {
+                0 => break,
+                byte => bytes.push(byte),
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// `ReadBitCoord`
+    #[inline]
+    pub fn read_bit_coord(&mut self) -> Result<f32, Overflow> {
+        let has_integer = self.read_bit()?;
+        let has_fraction = self.read_bit()?;
+
+        if !has_integer && !has_fraction {
+            return Ok(0.0);
+        }
+
+        let sign = self.read_bit()?;
+        let integer = if has_integer {
+            self.read_ubits(COORD_INTEGER_BITS)? + 1
+        } else {
+            0
+        };
+
+        let fraction = if has_fraction {
+            self.read_ubits(COORD_FRACTIONAL_BITS)?
+        } else {
+            0
+        };
+
+        let value = integer as f32 + fraction as f32 * COORD_RESOLUTION;
+
+        Ok(if sign { -value } else { value })
+    }
+
+    /// `ReadBitCoordMP`
+    #[inline]
+    pub fn read_bit_coord_mp(
+        &mut self,
+        integral: bool,
+        low_precision: bool,
+    ) -> Result<f32, Overflow> {
+        let in_bounds = self.read_bit()?;
+        let integer_bits = if in_bounds {
+            COORD_INTEGER_BITS_MP
+        } else {
+            COORD_INTEGER_BITS
+        };
+
+        if integral {
+            if !self.read_bit()? {
+                return Ok(0.0);
+            }
+
+            let sign = self.read_bit()?;
+            let value = (self.read_ubits(integer_bits)? + 1) as f32;
+
+            return Ok(if sign { -value } else { value });
+        }
+
+        let has_integer = self.read_bit()?;
+        let sign = self.read_bit()?;
+        let integer = if has_integer {
+            self.read_ubits(integer_bits)? + 1
+        } else {
+            0
+        };
+
+        let (fraction_bits, resolution) = if low_precision {
+            (
+                COORD_FRACTIONAL_BITS_LOW_PRECISION,
+                COORD_RESOLUTION_LOW_PRECISION,
+            )
+        } else {
+            (COORD_FRACTIONAL_BITS, COORD_RESOLUTION)
+        };
+
+        let fraction = self.read_ubits(fraction_bits)?;
+        let value = integer as f32 + fraction as f32 * resolution;
+
+        Ok(if sign { -value } else { value })
+    }
+
+    /// `ReadBitCellCoord`
+    #[inline]
+    pub fn read_bit_cell_coord(
+        &mut self,
+        bits: u32,
+        integral: bool,
+        low_precision: bool,
+    ) -> Result<f32, Overflow> {
+        let integer = self.read_ubits(bits)? as f32;
+
+        if integral {
+            return Ok(integer);
+        }
+
+        let fraction = if low_precision {
+            self.read_ubits(COORD_FRACTIONAL_BITS_LOW_PRECISION)? as f32
+                * COORD_RESOLUTION_LOW_PRECISION
+        } else {
+            self.read_ubits(COORD_FRACTIONAL_BITS)? as f32 * COORD_RESOLUTION
+        };
+
+        Ok(integer + fraction)
+    }
+
+    /// `ReadBitNormal`, a value between -1 and 1
+    #[inline]
+    pub fn read_bit_normal(&mut self) -> Result<f32, Overflow> {
+        let sign = self.read_bit()?;
+        let value = self.read_ubits(NORMAL_FRACTIONAL_BITS)? as f32 * NORMAL_RESOLUTION;
+
+        Ok(if sign { -value } else { value })
+    }
+}
+
+/// `bf_write`, growing as needed
+#[derive(Clone, Debug, Default)]
+pub struct Writer {
+    bytes: Vec<u8>,
+    /// in bits
+    position: usize,
+}
+
+impl Writer {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// position in bits
+    #[inline]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// written bytes, the last one zero padded
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    #[inline]
+    pub fn write_bit(&mut self, value: bool) {
+        if self.position % 8 == 0 {
+            self.bytes.push(0);
+        }
+
+        if value {
+            *self.bytes.last_mut().unwrap() |= 1 << (self.position % 8);
+        }
+
+        self.position += 1;
+    }
+
+    /// write the low `bits` of `value`, up to 32 (`WriteUBitLong`)
+    #[inline]
+    pub fn write_ubits(&mut self, value: u32, bits: u32) {
+        debug_assert!(bits <= 32);
+
+        let mut written = 0;
+
+        while written < bits {
+            if self.position % 8 == 0 {
+                self.bytes.push(0);
+            }
+
+            let offset = (self.position % 8) as u32;
+            let take = (8 - offset).min(bits - written);
+            let chunk = ((value as u64 >> written) & ((1 << take) - 1)) as u8;
+
+            *self.bytes.last_mut().unwrap() |= chunk << offset;
+            written += take;
+            self.position += take as usize;
+        }
+    }
+
+    /// `WriteSBitLong`
+    #[inline]
+    pub fn write_sbits(&mut self, value: i32, bits: u32) {
+        self.write_ubits(value as u32, bits);
+    }
+
+    #[inline]
+    pub fn write_u8(&mut self, value: u8) {
+        self.write_ubits(value as u32, 8);
+    }
+
+    #[inline]
+    pub fn write_u16(&mut self, value: u16) {
+        self.write_ubits(value as u32, 16);
+    }
+
+    #[inline]
+    pub fn write_i16(&mut self, value: i16) {
+        self.write_ubits(value as u16 as u32, 16);
+    }
+
+    #[inline]
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_ubits(value, 32);
+    }
+
+    #[inline]
+    pub fn write_i32(&mut self, value: i32) {
+        self.write_ubits(value as u32, 32);
+    }
+
+    /// `WriteFloat`, the raw bits of an `f32`
+    #[inline]
+    pub fn write_f32(&mut self, value: f32) {
+        self.write_ubits(value.to_bits(), 32);
+    }
+
+    #[inline]
+    pub fn write_varint32(&mut self, value: u32) {
+        self.write_varint64(value as u64);
+    }
+
+    /// zigzag encoded
+    #[inline]
+    pub fn write_signed_varint32(&mut self, value: i32) {
+        self.write_varint32(((value << 1) ^ (value >> 31)) as u32);
+    }
+
+    #[inline]
+    pub fn write_varint64(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.write_u8(value as u8 | 0x80);
+            value >>= 7;
+        }
+
+        self.write_u8(value as u8);
+    }
+
+    /// zigzag encoded
+    #[inline]
+    pub fn write_signed_varint64(&mut self, value: i64) {
+        self.write_varint64(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    /// `WriteUBitVar`
+    #[inline]
+    pub fn write_ubitvar(&mut self, value: u32) {
+        let (size, bits) = match value >> 4 {
+            0 => (0, 0),
+            high if high < 1 << 4 => (16, 4),
+            high if high < 1 << 8 => (32, 8),
+            _ => (48, 32 - 4),
+        };
+
+        self.write_ubits((value & 15) | size, 6);
+        self.write_ubits(value >> 4, bits);
+    }
+
+    #[inline]
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        // fast path for byte-aligned writes
+        if self.position % 8 == 0 {
+            self.bytes.extend_from_slice(bytes);
+            self.position += bytes.len() * 8;
+
+            return;
+        }
+
+        for byte in bytes {
+            self.write_u8(*byte);
+        }
+    }
+
+    /// write `string`, then a nul
+    #[inline]
+    pub fn write_string(&mut self, string: &str) {
+        self.write_bytes(string.as_bytes());
+        self.write_u8(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits() {
+        let mut reader = Reader::new(&[0b1010_1101, 0xFF, 0x01]);
+
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert_eq!(reader.read_ubits(4).unwrap(), 0b1011);
+        // crosses into the second byte
+        assert_eq!(reader.read_ubits(4).unwrap(), 0b1110);
+        assert_eq!(reader.position(), 10);
+        assert_eq!(reader.read_ubits(14).unwrap(), 0b00_0111_1111);
+        assert_eq!(reader.read_ubits(1), Err(Overflow));
+    }
+
+    #[test]
+    fn signed() {
+        let mut reader = Reader::new(&[0b0000_0111, 0xFF, 0xFF, 0xFF, 0xFF]);
+
+        assert_eq!(reader.read_sbits(3).unwrap(), -1);
+        assert_eq!(reader.read_sbits(5).unwrap(), 0);
+        assert_eq!(reader.read_i32().unwrap(), -1);
+    }
+
+    #[test]
+    fn varint() {
+        let mut reader = Reader::new(&[0xAC, 0x02, 0x03, 0x04]);
+
+        assert_eq!(reader.read_varint32().unwrap(), 300);
+        assert_eq!(reader.read_signed_varint32().unwrap(), -2);
+        assert_eq!(reader.read_signed_varint32().unwrap(), 2);
+    }
+
+    #[test]
+    fn ubitvar() {
+        // 6 bits: low nibble 5, size 0
+        assert_eq!(Reader::new(&[0b0000_0101]).read_ubitvar().unwrap(), 5);
+        // 6 bits: low nibble 1, size 16 (4 more bits), then 0b1010
+        assert_eq!(
+            Reader::new(&[0b1001_0001, 0b0000_0010])
+                .read_ubitvar()
+                .unwrap(),
+            0b1010_0001
+        );
+    }
+
+    #[test]
+    fn unaligned_bytes() {
+        let mut reader = Reader::new(&[0b0000_0001, 0b0000_0001, 0]);
+
+        assert!(reader.read_bit().unwrap());
+        assert_eq!(reader.read_bytes(2).unwrap(), [0b1000_0000, 0]);
+    }
+
+    #[test]
+    fn string() {
+        let mut reader = Reader::new(b"de_dust2\0rest");
+
+        assert_eq!(reader.read_string().unwrap(), "de_dust2");
+        assert_eq!(reader.read_u8().unwrap(), b'r');
+    }
+
+    #[test]
+    fn coord() {
+        // integer and fraction present, positive, integer 1 + 1, fraction 16 / 32
+        let bits = 0b011 | (1 << 3) | (16 << 17);
+        let bytes = (bits as u32).to_le_bytes();
+
+        assert_eq!(Reader::new(&bytes).read_bit_coord().unwrap(), 2.5);
+        assert_eq!(Reader::new(&[0]).read_bit_coord().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn normal() {
+        // negative, full fraction
+        let bits = 1 | (2047 << 1);
+        let bytes = (bits as u16).to_le_bytes();
+
+        let value = Reader::new(&bytes).read_bit_normal().unwrap();
+
+        assert!((value + 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn write() {
+        let mut writer = Writer::new();
+
+        writer.write_bit(true);
+        writer.write_bit(false);
+        writer.write_ubits(0b1011, 4);
+        writer.write_ubits(0b1110, 4);
+        writer.write_ubits(0b00_0111_1111, 14);
+
+        assert_eq!(writer.position(), 24);
+        assert_eq!(writer.as_bytes(), [0b1010_1101, 0xFF, 0x01]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut writer = Writer::new();
+
+        writer.write_bit(true);
+        writer.write_sbits(-3, 5);
+        writer.write_u8(0xAB);
+        writer.write_i16(-2);
+        writer.write_i32(i32::MIN);
+        writer.write_f32(-1.5);
+        writer.write_varint32(300);
+        writer.write_signed_varint32(-2);
+        writer.write_signed_varint64(i64::MIN);
+        writer.write_bytes(&[1, 2, 3]);
+        writer.write_string("de_dust2");
+
+        for value in [0, 15, 16, 0xFFF, 0x1000, u32::MAX] {
+            writer.write_ubitvar(value);
+        }
+
+        let bytes = writer.into_bytes();
+        let mut reader = Reader::new(&bytes);
+
+        assert!(reader.read_bit().unwrap());
+        assert_eq!(reader.read_sbits(5).unwrap(), -3);
+        assert_eq!(reader.read_u8().unwrap(), 0xAB);
+        assert_eq!(reader.read_i16().unwrap(), -2);
+        assert_eq!(reader.read_i32().unwrap(), i32::MIN);
+        assert_eq!(reader.read_f32().unwrap(), -1.5);
+        assert_eq!(reader.read_varint32().unwrap(), 300);
+        assert_eq!(reader.read_signed_varint32().unwrap(), -2);
+        assert_eq!(reader.read_signed_varint64().unwrap(), i64::MIN);
+        assert_eq!(reader.read_bytes(3).unwrap(), [1, 2, 3]);
+        assert_eq!(reader.read_string().unwrap(), "de_dust2");
+
+        for value in [0, 15, 16, 0xFFF, 0x1000, u32::MAX] {
+            assert_eq!(reader.read_ubitvar().unwrap(), value);
+        }
+
+        assert!(reader.bits_left() < 8);
+    }
+
+    #[test]
+    fn write_varint() {
+        let mut writer = Writer::new();
+
+        writer.write_varint32(300);
+        writer.write_signed_varint32(-2);
+        writer.write_signed_varint32(2);
+
+        assert_eq!(writer.as_bytes(), [0xAC, 0x02, 0x03, 0x04]);
+    }
+}
@@ -4,7 +4,7 @@ use core::{mem, ptr};
 
 pub use class::Class;
 pub use classes::Classes;
-pub use property::Property;
+pub use property::{Property, PropertyKind};
 pub use table::Table;
 
 mod class;
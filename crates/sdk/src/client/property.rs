@@ -51,6 +51,7 @@ pub enum PropertyKind {
     String,
     Array,
     DataTable,
+    Int64,
 }
 
 #[non_exhaustive]
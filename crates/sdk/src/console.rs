@@ -1,13 +1,36 @@
-use crate::ffi;
+use crate::convar::{self, Base, ChangeCallback, Iter, RawIter};
+use crate::{ffi, vtable_validate};
 use cake::ffi::vtable;
 use std::ffi::OsStr;
 
 #[repr(C)]
 pub struct VTable {
-    _pad0: vtable::Pad<15>,
+    _pad0: vtable::Pad<10>,
+    register: unsafe extern "thiscall" fn(this: *const Console, base: *const Base),
+    unregister: unsafe extern "thiscall" fn(this: *const Console, base: *const Base),
+    _pad1: vtable::Pad<2>,
+    base: unsafe extern "thiscall" fn(this: *const Console, name: *const u8) -> *const Base,
     var: unsafe extern "thiscall" fn(this: *const Console, var: *const u8) -> *const (),
-    _pad1: vtable::Pad<11>,
+    _pad2: vtable::Pad<4>,
+    install_global_change_callback:
+        unsafe extern "thiscall" fn(this: *const Console, callback: ChangeCallback),
+    remove_global_change_callback:
+        unsafe extern "thiscall" fn(this: *const Console, callback: ChangeCallback),
+    _pad3: vtable::Pad<5>,
     write: unsafe extern "thiscall" fn(this: *const Console, fmt: *const u8, txt: *const u8),
+    _pad4: vtable::Pad<14>,
+    iter: unsafe extern "thiscall" fn(this: *const Console) -> *mut RawIter,
+}
+
+vtable_validate! {
+    register => 10,
+    unregister => 11,
+    base => 14,
+    var => 15,
+    install_global_change_callback => 20,
+    remove_global_change_callback => 21,
+    write => 27,
+    iter => 42,
 }
 
 #[repr(C)]
@@ -27,6 +50,56 @@ impl Console {
         unsafe { (self.vtable.var)(self, ptr) }
     }
 
+    /// find a config variable or console command by name
+    #[inline]
+    pub fn base<S>(&self, name: S) -> Option<&Base>
+    where
+        S: AsRef<OsStr>,
+    {
+        let cstr = ffi::osstr_to_cstr_cow(name);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+
+        unsafe { (self.vtable.base)(self, ptr).as_ref() }
+    }
+
+    /// register a config variable or console command
+    ///
+    /// `base` must stay alive until it is unregistered
+    #[inline]
+    pub fn register(&self, base: &Base) {
+        unsafe { (self.vtable.register)(self, base) }
+    }
+
+    /// unregister a config variable or console command
+    #[inline]
+    pub fn unregister(&self, base: &Base) {
+        unsafe { (self.vtable.unregister)(self, base) }
+    }
+
+    /// iterate every registered config variable and console command
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        unsafe { Iter::from_raw((self.vtable.iter)(self)).expect("console iterator is null") }
+    }
+
+    /// iterate every registered config variable
+    #[inline]
+    pub fn vars(&self) -> impl Iterator<Item = &convar::Var<()>> {
+        self.iter().filter_map(Base::as_var)
+    }
+
+    /// install a callback invoked whenever any config variable changes
+    #[inline]
+    pub fn install_global_change_callback(&self, callback: ChangeCallback) {
+        unsafe { (self.vtable.install_global_change_callback)(self, callback) }
+    }
+
+    /// remove a callback previously installed by `install_global_change_callback`
+    #[inline]
+    pub fn remove_global_change_callback(&self, callback: ChangeCallback) {
+        unsafe { (self.vtable.remove_global_change_callback)(self, callback) }
+    }
+
     #[inline]
     pub fn write<S>(&self, string: S)
     where
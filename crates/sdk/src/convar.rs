@@ -1,7 +1,23 @@
-use crate::{Pad, UtlVec};
+use crate::{ffi, object_validate, UtlVec};
 use cake::ffi::vtable;
 use core::fmt;
 use core::marker::PhantomData;
+use core::mem;
+use std::ffi::CStr;
+
+pub use args::Args;
+pub use base::Base;
+pub use command::{Command, CommandCallback};
+pub use flags::Flags;
+pub use iter::Iter;
+
+pub(crate) use iter::RawIter;
+
+mod args;
+mod base;
+mod command;
+mod flags;
+mod iter;
 
 mod sealed {
     use super::Var;
@@ -49,45 +65,102 @@ mod sealed {
 }
 
 /// valid types config variables can store
-pub trait Kind: sealed::Sealed {}
+pub trait Kind: sealed::Sealed + Copy + PartialEq {}
 
 impl Kind for f32 {}
 impl Kind for i32 {}
 impl Kind for bool {}
 
+/// `FnChangeCallback_t`
+pub type ChangeCallback =
+    unsafe extern "C" fn(var: *const Interface, old_string: *const u8, old_value: f32);
+
 #[repr(C)]
 struct VTable<T> {
     _pad0: vtable::Pad<15>,
     read_f32: unsafe extern "thiscall" fn(this: *const Var<T>) -> f32,
     write_f32: unsafe extern "thiscall" fn(this: *const Var<T>, value: f32),
-    _pad1: vtable::Pad<1>,
+    write_string: unsafe extern "thiscall" fn(this: *const Var<T>, value: *const u8),
     read_i32: unsafe extern "thiscall" fn(this: *const Var<T>) -> i32,
     write_i32: unsafe extern "thiscall" fn(this: *const Var<T>, value: i32),
 }
 
+/// the `IConVar` sub-object of a config variable
+///
+/// this is what the engine passes to change callbacks
+#[repr(C)]
+pub struct Interface {
+    vtable: *const (),
+}
+
+impl Interface {
+    /// offset of the `IConVar` sub-object within `ConVar`
+    const OFFSET: usize = 48;
+
+    /// the config variable this interface belongs to
+    #[inline]
+    pub fn var(&self) -> &Var<()> {
+        unsafe { &*(self as *const Self).byte_sub(Self::OFFSET).cast() }
+    }
+}
+
 /// config variable
 #[repr(C)]
 pub struct Var<T> {
     /// blah blah static
     vtable: *const VTable<T>,
-    _pad0: Pad<40>,
-    pub change_callback: Option<unsafe extern "thiscall" fn()>,
-    pub parent: *const Var<()>,
-    pub default_value: *const u8,
-    pub string: *const u8,
-    _pad1: Pad<28>,
-    pub on_change_callbacks: UtlVec<unsafe extern "thiscall" fn()>,
+    next: *const Base,
+    registered: bool,
+    name: *const u8,
+    help: *const u8,
+    flags: Flags,
+    interface: Interface,
+    parent: *const Var<()>,
+    default_value: *const u8,
+    string: *const u8,
+    string_len: i32,
+    f32_value: f32,
+    i32_value: i32,
+    has_min: bool,
+    min: f32,
+    has_max: bool,
+    max: f32,
+    on_change_callbacks: UtlVec<ChangeCallback>,
     // we do be owning T, tho
     _phantom: PhantomData<T>,
 }
 
+type AnyVar = Var<()>;
+
+object_validate! {
+    AnyVar;
+    next => 8,
+    registered => 16,
+    name => 24,
+    help => 32,
+    flags => 40,
+    interface => 48,
+    parent => 56,
+    default_value => 64,
+    string => 72,
+    string_len => 80,
+    f32_value => 84,
+    i32_value => 88,
+    has_min => 92,
+    min => 96,
+    has_max => 100,
+    max => 104,
+    on_change_callbacks => 112,
+}
+
 impl<T> fmt::Debug for Var<T> {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("Var")
-            .field("parent", &self.parent)
-            .field("default_value", &self.default_value)
-            .field("string", &self.string)
+            .field("name", &self.name())
+            .field("flags", &self.flags())
+            .field("default_value", &self.default_value())
+            .field("string", &self.string())
             .finish()
     }
 }
@@ -112,6 +185,88 @@ impl<T> Var<T> {
     fn write_i32(&self, value: i32) {
         unsafe { ((*self.vtable).write_i32)(self, value) }
     }
+
+    /// the variable which actually holds the value
+    ///
+    /// split-screen copies of a variable point at their original, otherwise this is `self`
+    #[inline]
+    pub fn parent(&self) -> &Var<()> {
+        if self.parent.is_null() {
+            unsafe { &*(self as *const Self).cast() }
+        } else {
+            unsafe { &*self.parent }
+        }
+    }
+
+    /// reinterpret this variable as storing `U`
+    #[inline]
+    pub fn cast<U>(&self) -> &Var<U> {
+        unsafe { &*(self as *const Self).cast() }
+    }
+
+    /// this variable as a generic console command base
+    #[inline]
+    pub fn as_base(&self) -> &Base {
+        unsafe { &*(self as *const Self).cast() }
+    }
+
+    /// the name of the variable
+    #[inline]
+    pub fn name(&self) -> &str {
+        unsafe { ffi::str_from_ptr_nullable(self.name) }
+    }
+
+    /// the help text of the variable
+    #[inline]
+    pub fn help(&self) -> &str {
+        unsafe { ffi::str_from_ptr_nullable(self.help) }
+    }
+
+    /// the variable's flags
+    #[inline]
+    pub fn flags(&self) -> Flags {
+        self.parent().flags
+    }
+
+    /// the minimum value, if there is one
+    #[inline]
+    pub fn min(&self) -> Option<f32> {
+        let parent = self.parent();
+
+        parent.has_min.then(|| parent.min)
+    }
+
+    /// the maximum value, if there is one
+    #[inline]
+    pub fn max(&self) -> Option<f32> {
+        let parent = self.parent();
+
+        parent.has_max.then(|| parent.max)
+    }
+
+    /// the current value, as a string
+    #[inline]
+    pub fn string(&self) -> &str {
+        unsafe { ffi::str_from_ptr_nullable(self.parent().string) }
+    }
+
+    /// write `value` as a string, exactly as typed into the console
+    #[inline]
+    pub fn write_string(&self, value: &CStr) {
+        unsafe { ((*self.vtable).write_string)(self, value.as_ptr().cast()) }
+    }
+
+    /// the default value, as a string
+    #[inline]
+    pub fn default_value(&self) -> &str {
+        unsafe { ffi::str_from_ptr_nullable(self.parent().default_value) }
+    }
+
+    /// callbacks attached to this specific variable
+    #[inline]
+    pub fn change_callbacks(&self) -> &[ChangeCallback] {
+        &self.parent().on_change_callbacks
+    }
 }
 
 impl<T> Var<T>
@@ -129,6 +284,34 @@ where
     pub fn write(&self, value: T) {
         <T as sealed::Sealed>::write(value, self)
     }
+
+    /// create a new config variable with the vtables of `template`
+    ///
+    /// the variable isn't visible to the engine until it is passed to `Console::register`, the
+    /// default value is set through the engine, which allocates the string as it would its own
+    #[inline]
+    pub fn new(
+        template: &Var<()>,
+        name: &'static CStr,
+        help: &'static CStr,
+        flags: Flags,
+        default_value: &'static CStr,
+    ) -> Box<Self> {
+        let mut var: Box<Self> = Box::new(unsafe { mem::zeroed() });
+
+        var.vtable = template.vtable.cast();
+        var.name = name.as_ptr().cast();
+        var.help = help.as_ptr().cast();
+        var.flags = flags;
+        var.interface.vtable = template.interface.vtable;
+        var.parent = (&*var as *const Self).cast();
+        var.default_value = default_value.as_ptr().cast();
+
+        // the string starts out null, so the engine `new[]`s it, and may later `delete[]` it
+        var.write_string(default_value);
+
+        var
+    }
 }
 
 macro_rules! vars {
@@ -0,0 +1,96 @@
+use crate::object_validate;
+use core::{fmt, str};
+use std::ffi::CStr;
+
+const MAX_ARGC: usize = 64;
+const MAX_LENGTH: usize = 512;
+
+/// console command arguments (`CCommand`)
+#[repr(C)]
+pub struct Args {
+    argc: i32,
+    argv0_size: i32,
+    arg_string: [u8; MAX_LENGTH],
+    argv_buffer: [u8; MAX_LENGTH],
+    argv: [*const u8; MAX_ARGC],
+}
+
+object_validate! {
+    Args;
+    argv0_size => 4,
+    arg_string => 8,
+    argv_buffer => 520,
+    argv => 1032,
+}
+
+impl Args {
+    /// number of arguments, including the command name
+    #[inline]
+    pub fn len(&self) -> usize {
+        (self.argc.max(0) as usize).min(MAX_ARGC)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// argument at `index`, where `0` is the command name
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let ptr = self.argv[index];
+
+        if ptr.is_null() {
+            return None;
+        }
+
+        unsafe { CStr::from_ptr(ptr.cast()).to_str().ok() }
+    }
+
+    /// the name of the command being executed
+    #[inline]
+    pub fn command(&self) -> &str {
+        self.get(0).unwrap_or("")
+    }
+
+    /// arguments after the command name
+    #[inline]
+    pub fn args(&self) -> impl Iterator<Item = &str> {
+        (1..self.len()).filter_map(|index| self.get(index))
+    }
+
+    /// parse the argument at `index`
+    #[inline]
+    pub fn parse<T>(&self, index: usize) -> Option<T>
+    where
+        T: str::FromStr,
+    {
+        self.get(index)?.parse().ok()
+    }
+
+    /// everything after the command name, exactly as it was typed
+    #[inline]
+    pub fn arg_string(&self) -> &str {
+        let start = (self.argv0_size.max(0) as usize).min(MAX_LENGTH);
+        let bytes = &self.arg_string[start..];
+        let len = bytes
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(bytes.len());
+
+        str::from_utf8(&bytes[..len]).unwrap_or("").trim()
+    }
+}
+
+impl fmt::Debug for Args {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_list()
+            .entries((0..self.len()).filter_map(|index| self.get(index)))
+            .finish()
+    }
+}
@@ -0,0 +1,87 @@
+use super::{Flags, Var};
+use crate::{ffi, object_validate, vtable_validate};
+use cake::ffi::vtable;
+use core::fmt;
+
+#[repr(C)]
+pub(super) struct VTable {
+    _pad0: vtable::Pad<2>,
+    is_command: unsafe extern "thiscall" fn(this: *const Base) -> bool,
+}
+
+vtable_validate! {
+    is_command => 2,
+}
+
+/// common base of config variables and console commands (`ConCommandBase`)
+#[repr(C)]
+pub struct Base {
+    pub(super) vtable: &'static VTable,
+    pub(super) next: *const Base,
+    pub(super) registered: bool,
+    pub(super) name: *const u8,
+    pub(super) help: *const u8,
+    pub(super) flags: Flags,
+}
+
+object_validate! {
+    Base;
+    next => 8,
+    registered => 16,
+    name => 24,
+    help => 32,
+    flags => 40,
+}
+
+impl fmt::Debug for Base {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Base")
+            .field("name", &self.name())
+            .field("flags", &self.flags())
+            .field("is_command", &self.is_command())
+            .finish()
+    }
+}
+
+impl Base {
+    /// the name of the variable or command
+    #[inline]
+    pub fn name(&self) -> &str {
+        unsafe { ffi::str_from_ptr_nullable(self.name) }
+    }
+
+    /// the help text of the variable or command
+    #[inline]
+    pub fn help(&self) -> &str {
+        unsafe { ffi::str_from_ptr_nullable(self.help) }
+    }
+
+    /// the flags this was registered with
+    #[inline]
+    pub fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    /// whether this has been registered with the console
+    #[inline]
+    pub fn is_registered(&self) -> bool {
+        self.registered
+    }
+
+    /// whether this is a console command, rather than a config variable
+    #[inline]
+    pub fn is_command(&self) -> bool {
+        unsafe { (self.vtable.is_command)(self) }
+    }
+
+    /// this as a config variable, if it is one
+    #[inline]
+    pub fn as_var(&self) -> Option<&Var<()>> {
+        if self.is_command() {
+            None
+        } else {
+            Some(unsafe { &*(self as *const Self).cast() })
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use super::{Args, Base, Flags};
+use crate::object_validate;
+use core::fmt;
+use core::ops::Deref;
+use core::ptr;
+use std::ffi::CStr;
+
+/// `FnCommandCallback_t`
+pub type CommandCallback = unsafe extern "C" fn(args: *const Args);
+
+/// `m_bUsingNewCommandCallback`
+const USING_NEW_COMMAND_CALLBACK: u8 = 1 << 1;
+
+/// console command (`ConCommand`)
+#[repr(C)]
+pub struct Command {
+    base: Base,
+    callback: Option<CommandCallback>,
+    completion_callback: *const (),
+    bits: u8,
+}
+
+object_validate! {
+    Command;
+    base => 0,
+    callback => 48,
+    completion_callback => 56,
+    bits => 64,
+}
+
+impl Command {
+    /// create a new console command with the vtable of `template`, which must be a command
+    ///
+    /// the command isn't visible to the engine until it is passed to `Console::register`
+    #[inline]
+    pub fn new(
+        template: &Base,
+        name: &'static CStr,
+        help: &'static CStr,
+        flags: Flags,
+        callback: CommandCallback,
+    ) -> Box<Self> {
+        assert!(template.is_command(), "template must be a console command");
+
+        Box::new(Self {
+            base: Base {
+                vtable: template.vtable,
+                next: ptr::null(),
+                registered: false,
+                name: name.as_ptr().cast(),
+                help: help.as_ptr().cast(),
+                flags,
+            },
+            callback: Some(callback),
+            completion_callback: ptr::null(),
+            bits: USING_NEW_COMMAND_CALLBACK,
+        })
+    }
+}
+
+impl Deref for Command {
+    type Target = Base;
+
+    #[inline]
+    fn deref(&self) -> &Base {
+        &self.base
+    }
+}
+
+impl fmt::Debug for Command {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Command")
+            .field("name", &self.name())
+            .field("flags", &self.flags())
+            .finish()
+    }
+}
@@ -0,0 +1,124 @@
+use core::ops;
+
+/// config variable and command flags (`FCVAR_*`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(transparent)]
+pub struct Flags(pub i32);
+
+impl Flags {
+    pub const NONE: Self = Self::new(0);
+    pub const UNREGISTERED: Self = Self::new(1 << 0);
+    pub const DEVELOPMENT_ONLY: Self = Self::new(1 << 1);
+    pub const GAME_DLL: Self = Self::new(1 << 2);
+    pub const CLIENT_DLL: Self = Self::new(1 << 3);
+    pub const HIDDEN: Self = Self::new(1 << 4);
+    pub const PROTECTED: Self = Self::new(1 << 5);
+    pub const SPONLY: Self = Self::new(1 << 6);
+    pub const ARCHIVE: Self = Self::new(1 << 7);
+    pub const NOTIFY: Self = Self::new(1 << 8);
+    pub const USER_INFO: Self = Self::new(1 << 9);
+    pub const PRINTABLE_ONLY: Self = Self::new(1 << 10);
+    pub const UNLOGGED: Self = Self::new(1 << 11);
+    pub const NEVER_AS_STRING: Self = Self::new(1 << 12);
+    pub const REPLICATED: Self = Self::new(1 << 13);
+    pub const CHEAT: Self = Self::new(1 << 14);
+    pub const SS: Self = Self::new(1 << 15);
+    pub const DEMO: Self = Self::new(1 << 16);
+    pub const DONT_RECORD: Self = Self::new(1 << 17);
+    pub const SS_ADDED: Self = Self::new(1 << 18);
+    pub const RELEASE: Self = Self::new(1 << 19);
+    pub const RELOAD_MATERIALS: Self = Self::new(1 << 20);
+    pub const RELOAD_TEXTURES: Self = Self::new(1 << 21);
+    pub const NOT_CONNECTED: Self = Self::new(1 << 22);
+    pub const MATERIAL_SYSTEM_THREAD: Self = Self::new(1 << 23);
+    pub const ARCHIVE_GAME_CONSOLE: Self = Self::new(1 << 24);
+    pub const SERVER_CAN_EXECUTE: Self = Self::new(1 << 28);
+    pub const SERVER_CANNOT_QUERY: Self = Self::new(1 << 29);
+    pub const CLIENT_CMD_CAN_EXECUTE: Self = Self::new(1 << 30);
+
+    const fn new(flags: i32) -> Self {
+        Self(flags)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn is_unregistered(&self) -> bool {
+        self.contains(Self::UNREGISTERED)
+    }
+
+    pub const fn is_development_only(&self) -> bool {
+        self.contains(Self::DEVELOPMENT_ONLY)
+    }
+
+    pub const fn is_hidden(&self) -> bool {
+        self.contains(Self::HIDDEN)
+    }
+
+    pub const fn is_archive(&self) -> bool {
+        self.contains(Self::ARCHIVE)
+    }
+
+    pub const fn is_replicated(&self) -> bool {
+        self.contains(Self::REPLICATED)
+    }
+
+    pub const fn is_cheat(&self) -> bool {
+        self.contains(Self::CHEAT)
+    }
+}
+
+impl const ops::BitAnd for Flags {
+    type Output = Flags;
+
+    fn bitand(self, rhs: Flags) -> Flags {
+        Flags(self.0 & rhs.0)
+    }
+}
+
+impl const ops::BitAndAssign for Flags {
+    fn bitand_assign(&mut self, rhs: Flags) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl const ops::BitOr for Flags {
+    type Output = Flags;
+
+    fn bitor(self, rhs: Flags) -> Flags {
+        Flags(self.0 | rhs.0)
+    }
+}
+
+impl const ops::BitOrAssign for Flags {
+    fn bitor_assign(&mut self, rhs: Flags) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl const ops::BitXor for Flags {
+    type Output = Flags;
+
+    fn bitxor(self, rhs: Flags) -> Flags {
+        Flags(self.0 ^ rhs.0)
+    }
+}
+
+impl const ops::BitXorAssign for Flags {
+    fn bitxor_assign(&mut self, rhs: Flags) {
+        self.0 ^= rhs.0;
+    }
+}
+
+impl const ops::Not for Flags {
+    type Output = Flags;
+
+    fn not(self) -> Flags {
+        Self(!self.0)
+    }
+}
@@ -0,0 +1,88 @@
+use super::Base;
+use crate::vtable_validate;
+use cake::ffi::vtable;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<1>,
+    delete: unsafe extern "thiscall" fn(this: *const RawIter),
+    set_first: unsafe extern "thiscall" fn(this: *const RawIter),
+    next: unsafe extern "thiscall" fn(this: *const RawIter),
+    is_valid: unsafe extern "thiscall" fn(this: *const RawIter) -> bool,
+    get: unsafe extern "thiscall" fn(this: *const RawIter) -> *const Base,
+}
+
+vtable_validate! {
+    delete => 1,
+    set_first => 2,
+    next => 3,
+    is_valid => 4,
+    get => 5,
+}
+
+/// `ICVarIteratorInternal`
+#[repr(C)]
+pub(crate) struct RawIter {
+    vtable: &'static VTable,
+}
+
+/// iterator over every registered config variable and console command
+pub struct Iter<'a> {
+    raw: NonNull<RawIter>,
+    started: bool,
+    _phantom: PhantomData<&'a Base>,
+}
+
+impl<'a> Iter<'a> {
+    /// take ownership of an iterator returned by `FactoryInternalIterator`
+    #[inline]
+    pub(crate) unsafe fn from_raw(raw: *mut RawIter) -> Option<Self> {
+        let raw = NonNull::new(raw)?;
+
+        Some(Self {
+            raw,
+            started: false,
+            _phantom: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn raw(&self) -> &RawIter {
+        unsafe { self.raw.as_ref() }
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Base;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a Base> {
+        let raw = self.raw();
+
+        unsafe {
+            if self.started {
+                (raw.vtable.next)(raw);
+            } else {
+                (raw.vtable.set_first)(raw);
+                self.started = true;
+            }
+
+            if !(raw.vtable.is_valid)(raw) {
+                return None;
+            }
+
+            (raw.vtable.get)(raw).as_ref()
+        }
+    }
+}
+
+impl<'a> Drop for Iter<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        let raw = self.raw();
+
+        unsafe { (raw.vtable.delete)(raw) }
+    }
+}
@@ -0,0 +1,95 @@
+//! CRC-32, as used by the engine for usercmd checksums (`CRC32_ProcessBuffer`).
+
+/// reflected `0x04C11DB7`
+const POLYNOMIAL: u32 = 0xEDB8_8320;
+
+const TABLE: [u32; 256] = table();
+
+const fn table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut index = 0;
+
+    while index < 256 {
+        let mut crc = index as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+
+            bit += 1;
+        }
+
+        table[index] = crc;
+        index += 1;
+    }
+
+    table
+}
+
+/// incremental checksum, `CRC32_Init`, `CRC32_ProcessBuffer` and `CRC32_Final`
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32(u32);
+
+impl Crc32 {
+    #[inline]
+    pub const fn new() -> Self {
+        Self(0xFFFF_FFFF)
+    }
+
+    #[inline]
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = TABLE[((self.0 ^ *byte as u32) & 0xFF) as usize] ^ (self.0 >> 8);
+        }
+    }
+
+    #[inline]
+    pub const fn finish(self) -> u32 {
+        !self.0
+    }
+}
+
+impl Default for Crc32 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// checksum of `bytes`
+#[inline]
+pub fn checksum(bytes: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+
+    crc.update(bytes);
+    crc.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check() {
+        assert_eq!(checksum(b""), 0);
+        assert_eq!(checksum(b"123456789"), 0xCBF4_3926);
+        assert_eq!(
+            checksum(b"The quick brown fox jumps over the lazy dog"),
+            0x414F_A339
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        let mut crc = Crc32::new();
+
+        crc.update(b"1234");
+        crc.update(b"56789");
+
+        assert_eq!(crc.finish(), checksum(b"123456789"));
+    }
+}
@@ -0,0 +1,733 @@
+//! Offline reader for `.dem` demo files.
+//!
+//! ```ignore
+//! let bytes = std::fs::read("match.dem")?;
+//! let mut demo = Demo::new(&bytes)?;
+//!
+//! while let Some(event) = demo.next_event()? {
+//!     if let Event::EntityUpdated { index, .. } = event {
+//!         let health = demo.property(index, "m_iHealth");
+//!     }
+//! }
+//! ```
+
+use crate::bf::Reader;
+use crate::input::Command;
+use message::{CreateStringTable, Messages, PacketEntities, ServerInfo, UpdateStringTable};
+use std::collections::{BTreeMap, VecDeque};
+
+pub use entity::{Entity, Value};
+pub use error::Error;
+pub use frame::{Frame, FrameKind, Frames};
+pub use header::Header;
+pub use string_table::{StringEntry, StringTable};
+pub use table::{Class, FlattenedProperty, SendProperty, SendTable, Tables};
+
+mod entity;
+mod error;
+mod frame;
+mod header;
+mod message;
+mod protobuf;
+mod string_table;
+mod table;
+
+/// name of the string table holding entity baselines, keyed by class id
+const INSTANCE_BASELINE: &str = "instancebaseline";
+
+/// something that happened in the demo, in recording order
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// `net_Tick`, the following events happened on this server tick
+    Tick(i32),
+    /// `svc_ServerInfo`
+    ServerInfo {
+        map_name: String,
+        tick_interval: f32,
+    },
+    /// a command sent by the recording player
+    UserCommand {
+        sequence: i32,
+        command: Command,
+    },
+    ConsoleCommand(String),
+    /// an entity was created, or entered the recording player's PVS
+    ///
+    /// its properties follow as an [`Event::EntityUpdated`].
+    EntityEntered {
+        index: i32,
+        class: u32,
+        serial: u32,
+    },
+    /// properties of an entity changed, by index in its class's flattened properties
+    EntityUpdated {
+        index: i32,
+        changes: Vec<(usize, Value)>,
+    },
+    /// an entity left the recording player's PVS, or was deleted
+    EntityLeft {
+        index: i32,
+        deleted: bool,
+    },
+}
+
+/// a demo being read
+pub struct Demo<'a> {
+    header: Header,
+    frames: Frames<'a>,
+    tables: Tables,
+    string_tables: Vec<StringTable>,
+    entities: BTreeMap<i32, Entity>,
+    events: VecDeque<Event>,
+    tick: i32,
+}
+
+impl<'a> Demo<'a> {
+    pub fn new(bytes: &'a [u8]) -> Result<Self, Error> {
+        let header = Header::parse(bytes)?;
+
+        Ok(Self {
+            header,
+            frames: Frames::new(&bytes[Header::LEN..]),
+            tables: Tables::default(),
+            string_tables: Vec::new(),
+            entities: BTreeMap::new(),
+            events: VecDeque::new(),
+            tick: 0,
+        })
+    }
+
+    #[inline]
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// the last server tick
+    #[inline]
+    pub fn tick(&self) -> i32 {
+        self.tick
+    }
+
+    /// send tables and server classes, empty until the data tables frame is read
+    #[inline]
+    pub fn tables(&self) -> &Tables {
+        &self.tables
+    }
+
+    #[inline]
+    pub fn string_tables(&self) -> &[StringTable] {
+        &self.string_tables
+    }
+
+    #[inline]
+    pub fn string_table(&self, name: &str) -> Option<&StringTable> {
+        self.string_tables.iter().find(|table| table.name() == name)
+    }
+
+    #[inline]
+    pub fn entity(&self, index: i32) -> Option<&Entity> {
+        self.entities.get(&index)
+    }
+
+    #[inline]
+    pub fn entities(&self) -> impl Iterator<Item = (i32, &Entity)> {
+        self.entities.iter().map(|(index, entity)| (*index, entity))
+    }
+
+    #[inline]
+    pub fn class(&self, index: i32) -> Option<&Class> {
+        self.tables.class(self.entity(index)?.class)
+    }
+
+    /// value of the property named `name` of entity `index`, see [`Class::property_index`]
+    #[inline]
+    pub fn property(&self, index: i32, name: &str) -> Option<&Value> {
+        let entity = self.entity(index)?;
+        let class = self.tables.class(entity.class)?;
+
+        entity.property(class.property_index(name)?)
+    }
+
+    /// read frames until the next event, `None` at the end of the demo
+    pub fn next_event(&mut self) -> Result<Option<Event>, Error> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(Some(event));
+            }
+
+            let frame = match self.frames.next() {
+                Some(frame) => frame?,
+                None => return Ok(None),
+            };
+
+            self.read_frame(frame)?;
+        }
+    }
+
+    fn read_frame(&mut self, frame: Frame<'a>) -> Result<(), Error> {
+        match frame.kind {
+            FrameKind::SignOn(data) | FrameKind::Packet(data) => self.read_packet(data)?,
+            FrameKind::ConsoleCommand(data) => {
+                let command = String::from_utf8_lossy(data);
+                let command = command.trim_end_matches('\0').into();
+
+                self.events.push_back(Event::ConsoleCommand(command));
+            }
+            FrameKind::UserCommand { sequence, data } => {
+                let command = Command::read_delta(&mut Reader::new(data), &Command::new())?;
+
+                self.events
+                    .push_back(Event::UserCommand { sequence, command });
+            }
+            FrameKind::DataTables(data) => self.tables = Tables::parse(data)?,
+            FrameKind::StringTables(data) => {
+                string_table::parse_snapshot(data, &mut self.string_tables)?
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn read_packet(&mut self, data: &[u8]) -> Result<(), Error> {
+        for message in Messages::new(data) {
+            let (kind, bytes) = message?;
+
+            match kind {
+                message::NET_TICK => {
+                    self.tick = message::tick(bytes)?;
+                    self.events.push_back(Event::Tick(self.tick));
+                }
+                message::SVC_SERVER_INFO => {
+                    let info = ServerInfo::decode(bytes)?;
+
+                    self.events.push_back(Event::ServerInfo {
+                        map_name: info.map_name,
+                        tick_interval: info.tick_interval,
+                    });
+                }
+                message::SVC_CREATE_STRING_TABLE => {
+                    let message = CreateStringTable::decode(bytes)?;
+                    let mut table = StringTable::new(
+                        message.name,
+                        message.max_entries,
+                        message.user_data_fixed_size,
+                        message.user_data_bits,
+                    );
+
+                    table.update(message.data, message.entries)?;
+                    self.string_tables.push(table);
+                }
+                message::SVC_UPDATE_STRING_TABLE => {
+                    let message = UpdateStringTable::decode(bytes)?;
+                    let table = usize::try_from(message.table_id)
+                        .ok()
+                        .and_then(|id| self.string_tables.get_mut(id))
+                        .ok_or(Error::UnknownStringTable(message.table_id))?;
+
+                    table.update(message.data, message.entries)?;
+                }
+                message::SVC_PACKET_ENTITIES => {
+                    self.read_packet_entities(PacketEntities::decode(bytes)?)?
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `CL_ParsePacketEntities`
+    fn read_packet_entities(&mut self, message: PacketEntities<'_>) -> Result<(), Error> {
+        let mut reader = Reader::new(message.data);
+        let mut index = -1;
+
+        for _ in 0..message.updated_entries {
+            index += 1 + reader.read_ubitvar()? as i32;
+
+            // leave, then delete, or enter
+            if reader.read_bit()? {
+                let deleted = reader.read_bit()?;
+
+                if deleted {
+                    self.entities.remove(&index);
+                }
+
+                self.events.push_back(Event::EntityLeft { index, deleted });
+
+                continue;
+            }
+
+            if reader.read_bit()? {
+                let class_id = reader.read_ubits(self.tables.class_bits())?;
+                let serial = reader.read_ubits(entity::SERIAL_BITS)?;
+                let class = self
+                    .tables
+                    .class(class_id)
+                    .ok_or(Error::UnknownClass(class_id))?;
+
+                let mut entity = Entity::new(class, serial);
+                let baseline = self
+                    .string_table(INSTANCE_BASELINE)
+                    .and_then(|table| table.user_data(&class_id.to_string()));
+
+                if let Some(baseline) = baseline {
+                    entity.apply(&entity::read_changes(&mut Reader::new(baseline), class)?);
+                }
+
+                let changes = entity::read_changes(&mut reader, class)?;
+
+                entity.apply(&changes);
+
+                self.entities.insert(index, entity);
+                self.events.push_back(Event::EntityEntered {
+                    index,
+                    class: class_id,
+                    serial,
+                });
+
+                self.events
+                    .push_back(Event::EntityUpdated { index, changes });
+
+                continue;
+            }
+
+            let entity = self
+                .entities
+                .get_mut(&index)
+                .ok_or(Error::UnknownEntity(index))?;
+
+            let class = self
+                .tables
+                .class(entity.class)
+                .ok_or(Error::UnknownClass(entity.class))?;
+
+            let changes = entity::read_changes(&mut reader, class)?;
+
+            entity.apply(&changes);
+
+            self.events
+                .push_back(Event::EntityUpdated { index, changes });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::client::PropertyKind;
+    use elysium_math::Vec3;
+
+    /// builds bit streams, least significant bit first
+    #[derive(Default)]
+    pub struct Bits {
+        bytes: Vec<u8>,
+        len: usize,
+    }
+
+    impl Bits {
+        pub fn bit(&mut self, value: bool) -> &mut Self {
+            if self.len % 8 == 0 {
+                self.bytes.push(0);
+            }
+
+            *self.bytes.last_mut().unwrap() |= (value as u8) << (self.len % 8);
+            self.len += 1;
+            self
+        }
+
+        pub fn ubits(&mut self, value: u32, bits: u32) -> &mut Self {
+            for bit in 0..bits {
+                self.bit(value >> bit & 1 != 0);
+            }
+
+            self
+        }
+
+        pub fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+            for byte in bytes {
+                self.ubits(*byte as u32, 8);
+            }
+
+            self
+        }
+
+        /// `-1` ends the list, see `ReadFieldIndex`
+        pub fn indices(&mut self, indices: &[usize]) -> &mut Self {
+            let mut last = -1;
+
+            self.bit(true);
+
+            for index in indices {
+                let offset = *index as i32 - last - 1;
+
+                if offset == 0 {
+                    self.bit(true);
+                } else {
+                    self.bit(false).bit(true).ubits(offset as u32, 3);
+                }
+
+                last = *index as i32;
+            }
+
+            self.bit(false).bit(false).ubits(127, 7).ubits(127, 7)
+        }
+
+        pub fn finish(&mut self) -> Vec<u8> {
+            self.bytes.clone()
+        }
+    }
+
+    /// builds protobuf messages
+    #[derive(Default)]
+    pub struct Proto(pub Vec<u8>);
+
+    impl Proto {
+        fn raw_varint(&mut self, mut value: u64) {
+            while value >= 0x80 {
+                self.0.push(value as u8 | 0x80);
+                value >>= 7;
+            }
+
+            self.0.push(value as u8);
+        }
+
+        pub fn varint(&mut self, field: u32, value: u64) -> &mut Self {
+            self.raw_varint((field << 3) as u64);
+            self.raw_varint(value);
+            self
+        }
+
+        pub fn float(&mut self, field: u32, value: f32) -> &mut Self {
+            self.raw_varint((field << 3 | 5) as u64);
+            self.0.extend_from_slice(&value.to_le_bytes());
+            self
+        }
+
+        pub fn bytes(&mut self, field: u32, value: &[u8]) -> &mut Self {
+            self.raw_varint((field << 3 | 2) as u64);
+            self.raw_varint(value.len() as u64);
+            self.0.extend_from_slice(value);
+            self
+        }
+
+        /// a message of a packet or data tables frame
+        pub fn message(&mut self, kind: u32, message: &Proto) -> &mut Self {
+            self.raw_varint(kind as u64);
+            self.raw_varint(message.0.len() as u64);
+            self.0.extend_from_slice(&message.0);
+            self
+        }
+    }
+
+    pub fn send_property(name: &str, kind: PropertyKind, flags: i32, bits: i32) -> Proto {
+        let mut property = Proto::default();
+
+        property
+            .varint(1, kind as u64)
+            .bytes(2, name.as_bytes())
+            .varint(3, flags as u64)
+            .varint(4, 128)
+            .varint(9, bits as u64);
+
+        property
+    }
+
+    pub fn header(map: &str, time: f32, ticks: i32) -> Vec<u8> {
+        let mut bytes = b"HL2DEMO\0".to_vec();
+
+        bytes.extend_from_slice(&4_i32.to_le_bytes());
+        bytes.extend_from_slice(&13_780_i32.to_le_bytes());
+
+        for name in ["localhost:27015", "player", map, "csgo"] {
+            let mut path = [0; 260];
+
+            path[..name.len()].copy_from_slice(name.as_bytes());
+            bytes.extend_from_slice(&path);
+        }
+
+        bytes.extend_from_slice(&time.to_le_bytes());
+        bytes.extend_from_slice(&ticks.to_le_bytes());
+        bytes.extend_from_slice(&ticks.to_le_bytes());
+        bytes.extend_from_slice(&0_i32.to_le_bytes());
+        bytes
+    }
+
+    fn frame(demo: &mut Vec<u8>, kind: u8, tick: i32) {
+        demo.push(kind);
+        demo.extend_from_slice(&tick.to_le_bytes());
+        demo.push(0);
+    }
+
+    /// length prefixed frame data
+    fn data(demo: &mut Vec<u8>, data: &[u8]) {
+        demo.extend_from_slice(&(data.len() as i32).to_le_bytes());
+        demo.extend_from_slice(data);
+    }
+
+    fn packet(demo: &mut Vec<u8>, kind: u8, tick: i32, messages: &Proto) {
+        frame(demo, kind, tick);
+        demo.extend_from_slice(&[0; 160]);
+        data(demo, &messages.0);
+    }
+
+    fn data_tables() -> Vec<u8> {
+        let mut base = Proto::default();
+        let mut player = Proto::default();
+        let mut end = Proto::default();
+
+        base.bytes(2, b"DT_Base")
+            .bytes(4, &send_property("m_iHealth", PropertyKind::Int, 1, 8).0)
+            .bytes(
+                4,
+                &send_property("m_vecOrigin", PropertyKind::Vector, (1 << 1) | (1 << 18), 0).0,
+            )
+            .bytes(4, &send_property("m_szName", PropertyKind::String, 0, 0).0);
+
+        player
+            .bytes(2, b"DT_Player")
+            .bytes(4, &{
+                let mut property = send_property("baseclass", PropertyKind::DataTable, 0, 0);
+
+                property.bytes(5, b"DT_Base");
+                property.0
+            })
+            .bytes(4, &send_property("m_ArmorValue", PropertyKind::Int, 1, 8).0);
+
+        end.varint(1, 1);
+
+        let mut data = Proto::default();
+
+        data.message(9, &base).message(9, &player).message(9, &end);
+
+        let mut data = data.0;
+
+        data.extend_from_slice(&2_i16.to_le_bytes());
+
+        for (id, name, table) in [
+            (0_i16, "CBaseEntity", "DT_Base"),
+            (1, "CCSPlayer", "DT_Player"),
+        ] {
+            data.extend_from_slice(&id.to_le_bytes());
+            data.extend_from_slice(name.as_bytes());
+            data.push(0);
+            data.extend_from_slice(table.as_bytes());
+            data.push(0);
+        }
+
+        data
+    }
+
+    fn demo() -> Vec<u8> {
+        let mut demo = header("de_dust2", 2.0, 128);
+
+        frame(&mut demo, 6, 0);
+        data(&mut demo, &data_tables());
+
+        // health 100 and armor 50 for players
+        let baseline = Bits::default()
+            .indices(&[1, 3])
+            .ubits(100, 8)
+            .ubits(50, 8)
+            .finish();
+
+        let string_data = Bits::default()
+            .bit(false)
+            .bit(true)
+            .bit(true)
+            .bit(false)
+            .bytes(b"1\0")
+            .bit(true)
+            .ubits(baseline.len() as u32, 14)
+            .bytes(&baseline)
+            .finish();
+
+        let mut server_info = Proto::default();
+        let mut create = Proto::default();
+
+        server_info.float(14, 1.0 / 64.0).bytes(16, b"de_dust2");
+
+        create
+            .bytes(1, b"instancebaseline")
+            .varint(2, 8)
+            .varint(3, 1)
+            .bytes(8, &string_data);
+
+        let mut signon = Proto::default();
+
+        signon.message(8, &server_info).message(12, &create);
+        packet(&mut demo, 1, 0, &signon);
+
+        // player 1 enters, entity 3 is deleted
+        let entities = Bits::default()
+            .ubits(1, 6)
+            .bit(false)
+            .bit(true)
+            .ubits(1, 2)
+            .ubits(7, 10)
+            .indices(&[0, 2])
+            .bit(true)
+            .bit(true)
+            .bit(false)
+            .ubits(1, 14)
+            .ubits(16, 5)
+            .bit(false)
+            .bit(false)
+            .bit(true)
+            .bit(false)
+            .bit(true)
+            .ubits(0, 14)
+            .ubits(3, 9)
+            .bytes(b"bob")
+            .ubits(1, 6)
+            .bit(true)
+            .bit(true)
+            .finish();
+
+        let mut tick = Proto::default();
+        let mut packet_entities = Proto::default();
+        let mut messages = Proto::default();
+
+        tick.varint(1, 100);
+        packet_entities.varint(2, 2).bytes(7, &entities);
+        messages.message(4, &tick).message(26, &packet_entities);
+        packet(&mut demo, 2, 100, &messages);
+
+        // player 1 takes damage
+        let entities = Bits::default()
+            .ubits(1, 6)
+            .bit(false)
+            .bit(false)
+            .indices(&[1])
+            .ubits(90, 8)
+            .finish();
+
+        let mut tick = Proto::default();
+        let mut packet_entities = Proto::default();
+        let mut messages = Proto::default();
+
+        tick.varint(1, 101);
+        packet_entities.varint(2, 1).bytes(7, &entities);
+        messages.message(4, &tick).message(26, &packet_entities);
+        packet(&mut demo, 2, 101, &messages);
+
+        let command = Bits::default().bit(true).ubits(6, 32).ubits(0, 21).finish();
+
+        frame(&mut demo, 5, 101);
+        demo.extend_from_slice(&6_i32.to_le_bytes());
+        data(&mut demo, &command);
+        frame(&mut demo, 4, 101);
+        data(&mut demo, b"say hi\0");
+        frame(&mut demo, 7, 101);
+        demo
+    }
+
+    #[test]
+    fn header_fields() {
+        let header = Header::parse(&header("de_mirage", 2.0, 128)).unwrap();
+
+        assert_eq!(header.map_name, "de_mirage");
+        assert_eq!(header.server_name, "localhost:27015");
+        assert_eq!(header.game_directory, "csgo");
+        assert_eq!(header.tick_rate(), 64.0);
+
+        assert_eq!(Header::parse(&[0; 16]), Err(Error::UnexpectedEnd));
+        assert_eq!(Header::parse(&[0; Header::LEN]), Err(Error::InvalidHeader));
+    }
+
+    #[test]
+    fn events() {
+        let bytes = demo();
+        let mut demo = Demo::new(&bytes).unwrap();
+
+        assert_eq!(
+            demo.next_event().unwrap(),
+            Some(Event::ServerInfo {
+                map_name: "de_dust2".into(),
+                tick_interval: 1.0 / 64.0,
+            })
+        );
+
+        assert_eq!(demo.next_event().unwrap(), Some(Event::Tick(100)));
+        assert_eq!(
+            demo.next_event().unwrap(),
+            Some(Event::EntityEntered {
+                index: 1,
+                class: 1,
+                serial: 7,
+            })
+        );
+
+        assert_eq!(
+            demo.next_event().unwrap(),
+            Some(Event::EntityUpdated {
+                index: 1,
+                changes: vec![
+                    (0, Value::Vector(Vec3::from_xyz(2.5, 0.0, -1.0))),
+                    (2, Value::String("bob".into())),
+                ],
+            })
+        );
+
+        assert_eq!(
+            demo.next_event().unwrap(),
+            Some(Event::EntityLeft {
+                index: 3,
+                deleted: true,
+            })
+        );
+
+        assert_eq!(demo.class(1).unwrap().name, "CCSPlayer");
+        assert_eq!(demo.property(1, "m_iHealth"), Some(&Value::Int(100)));
+        assert_eq!(
+            demo.property(1, "DT_Player.m_ArmorValue"),
+            Some(&Value::Int(50))
+        );
+
+        assert_eq!(demo.next_event().unwrap(), Some(Event::Tick(101)));
+        assert_eq!(
+            demo.next_event().unwrap(),
+            Some(Event::EntityUpdated {
+                index: 1,
+                changes: vec![(1, Value::Int(90))],
+            })
+        );
+
+        assert_eq!(demo.property(1, "m_iHealth"), Some(&Value::Int(90)));
+
+        match demo.next_event().unwrap() {
+            Some(Event::UserCommand { sequence, command }) => {
+                assert_eq!(sequence, 6);
+                assert_eq!(command.command, 6);
+                assert_eq!(command.tick_count, 1);
+            }
+            event => panic!("expected a user command, got {event:?}"),
+        }
+
+        assert_eq!(
+            demo.next_event().unwrap(),
+            Some(Event::ConsoleCommand("say hi".into()))
+        );
+
+        assert_eq!(demo.next_event().unwrap(), None);
+        assert_eq!(demo.tick(), 101);
+    }
+
+    #[test]
+    fn truncated() {
+        let bytes = demo();
+        let mut demo = Demo::new(&bytes[..bytes.len() - 1]).unwrap();
+
+        let error = loop {
+            match demo.next_event() {
+                Ok(Some(_event)) => continue,
+                Ok(None) => panic!("expected an error"),
+                Err(error) => break error,
+            }
+        };
+
+        assert_eq!(error, Error::UnexpectedEnd);
+    }
+}
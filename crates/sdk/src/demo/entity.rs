@@ -0,0 +1,252 @@
+use super::table::{self, bit_len, Class, FlattenedProperty, SendProperty};
+use super::Error;
+use crate::bf::Reader;
+use crate::client::PropertyKind;
+use elysium_math::Vec3;
+
+/// `NUM_NETWORKED_EHANDLE_SERIAL_NUMBER_BITS`
+pub(super) const SERIAL_BITS: u32 = 10;
+/// `DT_MAX_STRING_BITS`
+const STRING_LEN_BITS: u32 = 9;
+/// ends a list of property indices
+const END_INDEX: u32 = 0xFFF;
+
+/// a decoded property value
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Int(i32),
+    Int64(i64),
+    Float(f32),
+    /// `VectorXY` properties leave `z` zeroed
+    Vector(Vec3),
+    String(String),
+    Array(Vec<Value>),
+}
+
+/// an entity and the last value of each of its properties
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entity {
+    pub class: u32,
+    pub serial: u32,
+    properties: Vec<Option<Value>>,
+}
+
+impl Entity {
+    #[inline]
+    pub(super) fn new(class: &Class, serial: u32) -> Self {
+        Self {
+            class: class.id,
+            serial,
+            properties: vec![None; class.properties.len()],
+        }
+    }
+
+    /// value of the property at `index` in its class's flattened properties
+    #[inline]
+    pub fn property(&self, index: usize) -> Option<&Value> {
+        self.properties.get(index)?.as_ref()
+    }
+
+    /// every property received so far, by index
+    #[inline]
+    pub fn properties(&self) -> impl Iterator<Item = (usize, &Value)> {
+        self.properties
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| Some((index, value.as_ref()?)))
+    }
+
+    #[inline]
+    pub(super) fn apply(&mut self, changes: &[(usize, Value)]) {
+        for (index, value) in changes {
+            if let Some(property) = self.properties.get_mut(*index) {
+                *property = Some(value.clone());
+            }
+        }
+    }
+}
+
+/// read changed property indices, then their values (`ReadNewEntity`)
+pub(super) fn read_changes(
+    reader: &mut Reader<'_>,
+    class: &Class,
+) -> Result<Vec<(usize, Value)>, Error> {
+    let new_way = reader.read_bit()?;
+    let mut indices = Vec::new();
+    let mut last_index = -1_i64;
+
+    loop {
+        match read_index(reader, new_way)? {
+            END_INDEX => break,
+            offset => last_index += 1 + offset as i64,
+        }
+
+        indices.push(last_index as usize);
+    }
+
+    indices
+        .into_iter()
+        .map(|index| {
+            let property = class
+                .properties
+                .get(index)
+                .ok_or(Error::UnknownProperty(index))?;
+
+            Ok((index, read_flattened(reader, property)?))
+        })
+        .collect()
+}
+
+/// offset from the previous index, minus one (`ReadFieldIndex`)
+fn read_index(reader: &mut Reader<'_>, new_way: bool) -> Result<u32, Error> {
+    if new_way && reader.read_bit()? {
+        return Ok(0);
+    }
+
+    if new_way && reader.read_bit()? {
+        return Ok(reader.read_ubits(3)?);
+    }
+
+    let index = reader.read_ubits(7)?;
+    let index = match index & (32 | 64) {
+        32 => (index & !96) | (reader.read_ubits(2)? << 5),
+        64 => (index & !96) | (reader.read_ubits(4)? << 5),
+        96 => (index & !96) | (reader.read_ubits(7)? << 5),
+        _ => index,
+    };
+
+    Ok(index)
+}
+
+fn read_flattened(reader: &mut Reader<'_>, flattened: &FlattenedProperty) -> Result<Value, Error> {
+    let property = &flattened.property;
+
+    if property.kind != PropertyKind::Array {
+        return read_value(reader, property);
+    }
+
+    let element = flattened
+        .element
+        .as_ref()
+        .ok_or(Error::InvalidMessage("CSVCMsg_SendTable"))?;
+
+    let len = reader.read_ubits(bit_len(property.elements.max(0) as u32))?;
+    let values = (0..len)
+        .map(|_| read_value(reader, element))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Value::Array(values))
+}
+
+/// `DecodeProp`
+fn read_value(reader: &mut Reader<'_>, property: &SendProperty) -> Result<Value, Error> {
+    let value = match property.kind {
+        PropertyKind::Int => Value::Int(read_int(reader, property)?),
+        PropertyKind::Int64 => Value::Int64(read_int64(reader, property)?),
+        PropertyKind::Float => Value::Float(read_float(reader, property)?),
+        PropertyKind::Vector => {
+            let x = read_float(reader, property)?;
+            let y = read_float(reader, property)?;
+
+            let z = if property.flags & table::NORMAL != 0 {
+                let sign = reader.read_bit()?;
+                let z = (1.0 - x * x - y * y).max(0.0).sqrt();
+
+                if sign {
+                    -z
+                } else {
+                    z
+                }
+            } else {
+                read_float(reader, property)?
+            };
+
+            Value::Vector(Vec3::from_xyz(x, y, z))
+        }
+        PropertyKind::VectorXY => {
+            let x = read_float(reader, property)?;
+            let y = read_float(reader, property)?;
+
+            Value::Vector(Vec3::from_xyz(x, y, 0.0))
+        }
+        PropertyKind::String => {
+            let len = reader.read_ubits(STRING_LEN_BITS)? as usize;
+            let bytes = reader.read_bytes(len)?;
+
+            Value::String(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        _ => return Err(Error::InvalidMessage("CSVCMsg_SendTable")),
+    };
+
+    Ok(value)
+}
+
+fn read_int(reader: &mut Reader<'_>, property: &SendProperty) -> Result<i32, Error> {
+    let unsigned = property.flags & table::UNSIGNED != 0;
+    let bits = property.bits as u32;
+
+    let value = match (property.flags & table::VARINT != 0, unsigned) {
+        (true, true) => reader.read_varint32()? as i32,
+        (true, false) => reader.read_signed_varint32()?,
+        (false, true) => reader.read_ubits(bits)? as i32,
+        (false, false) => reader.read_sbits(bits)?,
+    };
+
+    Ok(value)
+}
+
+fn read_int64(reader: &mut Reader<'_>, property: &SendProperty) -> Result<i64, Error> {
+    let unsigned = property.flags & table::UNSIGNED != 0;
+
+    if property.flags & table::VARINT != 0 {
+        let value = if unsigned {
+            reader.read_varint64()? as i64
+        } else {
+            reader.read_signed_varint64()?
+        };
+
+        return Ok(value);
+    }
+
+    // sign bit, then the low and high words
+    let negative = !unsigned && reader.read_bit()?;
+    let high_bits = (property.bits as u32).saturating_sub(32 + !unsigned as u32);
+    let low = reader.read_u32()? as u64;
+    let high = reader.read_ubits(high_bits)? as u64;
+    let value = ((high << 32) | low) as i64;
+
+    Ok(if negative { -value } else { value })
+}
+
+/// `Decode_Float`
+fn read_float(reader: &mut Reader<'_>, property: &SendProperty) -> Result<f32, Error> {
+    let flags = property.flags;
+    let bits = property.bits as u32;
+
+    let value = if flags & table::COORD != 0 {
+        reader.read_bit_coord()?
+    } else if flags & table::COORD_MP != 0 {
+        reader.read_bit_coord_mp(false, false)?
+    } else if flags & table::COORD_MP_LOW_PRECISION != 0 {
+        reader.read_bit_coord_mp(false, true)?
+    } else if flags & table::COORD_MP_INTEGRAL != 0 {
+        reader.read_bit_coord_mp(true, false)?
+    } else if flags & table::NO_SCALE != 0 {
+        reader.read_f32()?
+    } else if flags & table::NORMAL != 0 {
+        reader.read_bit_normal()?
+    } else if flags & table::CELL_COORD != 0 {
+        reader.read_bit_cell_coord(bits, false, false)?
+    } else if flags & table::CELL_COORD_LOW_PRECISION != 0 {
+        reader.read_bit_cell_coord(bits, false, true)?
+    } else if flags & table::CELL_COORD_INTEGRAL != 0 {
+        reader.read_bit_cell_coord(bits, true, false)?
+    } else {
+        let max = ((1_u64 << bits) - 1) as f32;
+        let fraction = reader.read_ubits(bits)? as f32 / max;
+
+        property.low + (property.high - property.low) * fraction
+    };
+
+    Ok(value)
+}
@@ -0,0 +1,54 @@
+use crate::bf::Overflow;
+use core::fmt;
+
+/// failed to read a demo
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// missing the `HL2DEMO` stamp
+    InvalidHeader,
+    /// the file, a frame, or a message ended early
+    UnexpectedEnd,
+    /// unknown frame command
+    UnknownFrame(u8),
+    /// a message failed to decode, named by its protobuf type
+    InvalidMessage(&'static str),
+    /// a data table referenced a table that was never sent
+    UnknownTable(String),
+    /// an entity referenced a class id that was never sent
+    UnknownClass(u32),
+    /// an update referenced an entity that does not exist
+    UnknownEntity(i32),
+    /// an update referenced a string table that does not exist
+    UnknownStringTable(i32),
+    /// an update referenced a property past the end of its class
+    UnknownProperty(usize),
+    /// the data uses an encoding this reader does not support
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidHeader => fmt.write_str("not a demo file"),
+            Error::UnexpectedEnd => fmt.write_str("unexpected end of data"),
+            Error::UnknownFrame(kind) => write!(fmt, "unknown frame command {kind}"),
+            Error::InvalidMessage(name) => write!(fmt, "invalid {name} message"),
+            Error::UnknownTable(name) => write!(fmt, "unknown data table {name:?}"),
+            Error::UnknownClass(id) => write!(fmt, "unknown class {id}"),
+            Error::UnknownEntity(index) => write!(fmt, "unknown entity {index}"),
+            Error::UnknownStringTable(id) => write!(fmt, "unknown string table {id}"),
+            Error::UnknownProperty(index) => write!(fmt, "unknown property {index}"),
+            Error::Unsupported(what) => write!(fmt, "unsupported {what}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Overflow> for Error {
+    #[inline]
+    fn from(_: Overflow) -> Self {
+        Error::UnexpectedEnd
+    }
+}
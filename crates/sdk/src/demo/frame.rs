@@ -0,0 +1,139 @@
+use super::Error;
+use crate::bf::Reader;
+
+/// `democmdinfo_t`, view origin and angles for both split screen slots
+const COMMAND_INFO_LEN: usize = 152;
+
+/// a frame of the demo stream, `demo_command_t` and its payload
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frame<'a> {
+    pub tick: i32,
+    /// split screen slot
+    pub slot: u8,
+    pub kind: FrameKind<'a>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrameKind<'a> {
+    /// `dem_signon`, messages sent while connecting
+    SignOn(&'a [u8]),
+    /// `dem_packet`, messages sent during play
+    Packet(&'a [u8]),
+    /// `dem_synctick`
+    SyncTick,
+    /// `dem_consolecmd`
+    ConsoleCommand(&'a [u8]),
+    /// `dem_usercmd`, a delta encoded command
+    UserCommand { sequence: i32, data: &'a [u8] },
+    /// `dem_datatables`, send tables and server classes
+    DataTables(&'a [u8]),
+    /// `dem_stop`
+    Stop,
+    /// `dem_customdata`
+    CustomData { kind: i32, data: &'a [u8] },
+    /// `dem_stringtables`, a snapshot of every string table
+    StringTables(&'a [u8]),
+}
+
+/// frames following the header, ending at `dem_stop`
+#[derive(Clone, Debug)]
+pub struct Frames<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    stopped: bool,
+}
+
+impl<'a> Frames<'a> {
+    /// `bytes` are the frames, without the header
+    #[inline]
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            stopped: false,
+        }
+    }
+
+    fn read_frame(&mut self) -> Result<Frame<'a>, Error> {
+        let kind = self.take(1)?[0];
+        let tick = self.read_i32()?;
+        let slot = self.take(1)?[0];
+
+        let kind = match kind {
+            1 | 2 => {
+                // command info, then the in and out sequence numbers
+                self.take(COMMAND_INFO_LEN + 8)?;
+
+                let data = self.read_data()?;
+
+                if kind == 1 {
+                    FrameKind::SignOn(data)
+                } else {
+                    FrameKind::Packet(data)
+                }
+            }
+            3 => FrameKind::SyncTick,
+            4 => FrameKind::ConsoleCommand(self.read_data()?),
+            5 => FrameKind::UserCommand {
+                sequence: self.read_i32()?,
+                data: self.read_data()?,
+            },
+            6 => FrameKind::DataTables(self.read_data()?),
+            7 => FrameKind::Stop,
+            8 => FrameKind::CustomData {
+                kind: self.read_i32()?,
+                data: self.read_data()?,
+            },
+            9 => FrameKind::StringTables(self.read_data()?),
+            kind => return Err(Error::UnknownFrame(kind)),
+        };
+
+        Ok(Frame { tick, slot, kind })
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + len)
+            .ok_or(Error::UnexpectedEnd)?;
+
+        self.position += len;
+
+        Ok(bytes)
+    }
+
+    fn read_i32(&mut self) -> Result<i32, Error> {
+        Ok(Reader::new(self.take(4)?).read_i32()?)
+    }
+
+    /// length prefixed data
+    fn read_data(&mut self) -> Result<&'a [u8], Error> {
+        let len = self.read_i32()?;
+        let len = usize::try_from(len).map_err(|_| Error::UnexpectedEnd)?;
+
+        self.take(len)
+    }
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Result<Frame<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // unfinished recordings have no stop frame
+        if self.stopped || self.position >= self.bytes.len() {
+            return None;
+        }
+
+        let frame = self.read_frame();
+
+        self.stopped = matches!(
+            frame,
+            Ok(Frame {
+                kind: FrameKind::Stop,
+                ..
+            }) | Err(_)
+        );
+
+        Some(frame)
+    }
+}
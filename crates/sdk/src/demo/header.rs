@@ -0,0 +1,73 @@
+use super::Error;
+use crate::bf::Reader;
+
+/// `DEMO_HEADER_ID`
+const STAMP: &[u8; 8] = b"HL2DEMO\0";
+
+/// `MAX_OSPATH`
+const PATH_LEN: usize = 260;
+
+/// `demoheader_t`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Header {
+    pub demo_protocol: i32,
+    pub network_protocol: i32,
+    pub server_name: String,
+    pub client_name: String,
+    pub map_name: String,
+    pub game_directory: String,
+    /// in seconds
+    pub playback_time: f32,
+    pub playback_ticks: i32,
+    pub playback_frames: i32,
+    /// bytes of signon data at the start of the file
+    pub signon_length: i32,
+}
+
+impl Header {
+    /// size of the header in bytes
+    pub const LEN: usize = 8 + 4 + 4 + PATH_LEN * 4 + 4 + 4 + 4 + 4;
+
+    pub fn parse(bytes: &[u8]) -> Result<Header, Error> {
+        if bytes.len() < Header::LEN {
+            return Err(Error::UnexpectedEnd);
+        }
+
+        if !bytes.starts_with(STAMP) {
+            return Err(Error::InvalidHeader);
+        }
+
+        let mut reader = Reader::new(&bytes[STAMP.len()..Header::LEN]);
+
+        Ok(Header {
+            demo_protocol: reader.read_i32()?,
+            network_protocol: reader.read_i32()?,
+            server_name: read_path(&mut reader)?,
+            client_name: read_path(&mut reader)?,
+            map_name: read_path(&mut reader)?,
+            game_directory: read_path(&mut reader)?,
+            playback_time: reader.read_f32()?,
+            playback_ticks: reader.read_i32()?,
+            playback_frames: reader.read_i32()?,
+            signon_length: reader.read_i32()?,
+        })
+    }
+
+    /// ticks per second, zero for demos that were not finished
+    #[inline]
+    pub fn tick_rate(&self) -> f32 {
+        if self.playback_time > 0.0 {
+            self.playback_ticks as f32 / self.playback_time
+        } else {
+            0.0
+        }
+    }
+}
+
+/// a nul-padded `char[MAX_OSPATH]`
+fn read_path(reader: &mut Reader<'_>) -> Result<String, Error> {
+    let bytes = reader.read_bytes(PATH_LEN)?;
+    let len = bytes.iter().position(|byte| *byte == 0).unwrap_or(PATH_LEN);
+
+    Ok(String::from_utf8_lossy(&bytes[..len]).into_owned())
+}
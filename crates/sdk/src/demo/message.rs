@@ -0,0 +1,326 @@
+//! Net messages found in packets, by protobuf field number.
+
+use super::protobuf::{self, Fields, Value};
+use super::table::{SendProperty, SendTable};
+use super::Error;
+use crate::client::PropertyKind;
+
+/// `net_Tick`
+pub const NET_TICK: u32 = 4;
+/// `svc_ServerInfo`
+pub const SVC_SERVER_INFO: u32 = 8;
+/// `svc_CreateStringTable`
+pub const SVC_CREATE_STRING_TABLE: u32 = 12;
+/// `svc_UpdateStringTable`
+pub const SVC_UPDATE_STRING_TABLE: u32 = 13;
+/// `svc_PacketEntities`
+pub const SVC_PACKET_ENTITIES: u32 = 26;
+
+/// messages of a packet, as `(kind, bytes)`
+#[derive(Clone, Debug)]
+pub struct Messages<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Messages<'a> {
+    #[inline]
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn read_message(&mut self) -> Option<(u32, &'a [u8])> {
+        let kind = protobuf::read_varint(&mut self.bytes)? as u32;
+        let len = protobuf::read_varint(&mut self.bytes)? as usize;
+
+        if len > self.bytes.len() {
+            return None;
+        }
+
+        let (message, rest) = self.bytes.split_at(len);
+
+        self.bytes = rest;
+
+        Some((kind, message))
+    }
+}
+
+impl<'a> Iterator for Messages<'a> {
+    type Item = Result<(u32, &'a [u8]), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let message = self.read_message();
+
+        if message.is_none() {
+            self.bytes = &[];
+        }
+
+        Some(message.ok_or(Error::UnexpectedEnd))
+    }
+}
+
+/// call `field` with each field of `bytes`, it returns `None` for a field of the wrong type
+fn decode<'a, F>(name: &'static str, bytes: &'a [u8], mut field: F) -> Result<(), Error>
+where
+    F: FnMut(u32, Value<'a>) -> Option<()>,
+{
+    for entry in Fields::new(bytes) {
+        let (number, value) = entry.ok_or(Error::InvalidMessage(name))?;
+
+        field(number, value).ok_or(Error::InvalidMessage(name))?;
+    }
+
+    Ok(())
+}
+
+/// `CNETMsg_Tick`
+pub fn tick(bytes: &[u8]) -> Result<i32, Error> {
+    let mut tick = 0;
+
+    decode("CNETMsg_Tick", bytes, |number, value| {
+        if number == 1 {
+            tick = value.as_i32()?;
+        }
+
+        Some(())
+    })?;
+
+    Ok(tick)
+}
+
+/// `CSVCMsg_ServerInfo`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ServerInfo {
+    pub max_classes: i32,
+    pub tick_interval: f32,
+    pub map_name: String,
+}
+
+impl ServerInfo {
+    pub fn decode(bytes: &[u8]) -> Result<ServerInfo, Error> {
+        let mut info = ServerInfo::default();
+
+        decode("CSVCMsg_ServerInfo", bytes, |number, value| {
+            match number {
+                12 => info.max_classes = value.as_i32()?,
+                14 => info.tick_interval = value.as_f32()?,
+                16 => info.map_name = value.as_string()?,
+                _ => {}
+            }
+
+            Some(())
+        })?;
+
+        Ok(info)
+    }
+}
+
+/// `CSVCMsg_SendTable`, returning whether it is the last table
+pub fn send_table(bytes: &[u8]) -> Result<(SendTable, bool), Error> {
+    const NAME: &str = "CSVCMsg_SendTable";
+
+    let mut table = SendTable {
+        name: String::new(),
+        properties: Vec::new(),
+    };
+
+    let mut is_end = false;
+
+    decode(NAME, bytes, |number, value| {
+        match number {
+            1 => is_end = value.as_bool()?,
+            2 => table.name = value.as_string()?,
+            4 => table
+                .properties
+                .push(send_property(value.as_bytes()?).ok()?),
+            _ => {}
+        }
+
+        Some(())
+    })?;
+
+    Ok((table, is_end))
+}
+
+/// `CSVCMsg_SendTable.sendprop_t`
+fn send_property(bytes: &[u8]) -> Result<SendProperty, Error> {
+    const NAME: &str = "CSVCMsg_SendTable.sendprop_t";
+
+    let mut kind = None;
+    let mut property = SendProperty {
+        kind: PropertyKind::Int,
+        name: String::new(),
+        flags: 0,
+        priority: 0,
+        data_table: String::new(),
+        elements: 0,
+        low: 0.0,
+        high: 0.0,
+        bits: 0,
+    };
+
+    decode(NAME, bytes, |number, value| {
+        match number {
+            1 => kind = Some(property_kind(value.as_i32()?)?),
+            2 => property.name = value.as_string()?,
+            3 => property.flags = value.as_i32()?,
+            4 => property.priority = value.as_i32()?,
+            5 => property.data_table = value.as_string()?,
+            6 => property.elements = value.as_i32()?,
+            7 => property.low = value.as_f32()?,
+            8 => property.high = value.as_f32()?,
+            9 => property.bits = value.as_i32()?,
+            _ => {}
+        }
+
+        Some(())
+    })?;
+
+    property.kind = kind.ok_or(Error::InvalidMessage(NAME))?;
+
+    // widths are read straight into shifts, so a malformed demo mustn't get past here
+    let max_bits = match property.kind {
+        PropertyKind::Int64 => 64,
+        _ => 32,
+    };
+
+    if !(0..=max_bits).contains(&property.bits) {
+        return Err(Error::InvalidMessage(NAME));
+    }
+
+    Ok(property)
+}
+
+/// `SendPropType`
+const fn property_kind(kind: i32) -> Option<PropertyKind> {
+    let kind = match kind {
+        0 => PropertyKind::Int,
+        1 => PropertyKind::Float,
+        2 => PropertyKind::Vector,
+        3 => PropertyKind::VectorXY,
+        4 => PropertyKind::String,
+        5 => PropertyKind::Array,
+        6 => PropertyKind::DataTable,
+        7 => PropertyKind::Int64,
+        _ => return None,
+    };
+
+    Some(kind)
+}
+
+/// `CSVCMsg_CreateStringTable`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CreateStringTable<'a> {
+    pub name: String,
+    pub max_entries: i32,
+    pub entries: i32,
+    pub user_data_fixed_size: bool,
+    pub user_data_size: i32,
+    pub user_data_bits: i32,
+    pub data: &'a [u8],
+}
+
+impl<'a> CreateStringTable<'a> {
+    pub fn decode(bytes: &'a [u8]) -> Result<Self, Error> {
+        let mut message = CreateStringTable::default();
+
+        decode("CSVCMsg_CreateStringTable", bytes, |number, value| {
+            match number {
+                1 => message.name = value.as_string()?,
+                2 => message.max_entries = value.as_i32()?,
+                3 => message.entries = value.as_i32()?,
+                4 => message.user_data_fixed_size = value.as_bool()?,
+                5 => message.user_data_size = value.as_i32()?,
+                6 => message.user_data_bits = value.as_i32()?,
+                8 => message.data = value.as_bytes()?,
+                _ => {}
+            }
+
+            Some(())
+        })?;
+
+        Ok(message)
+    }
+}
+
+/// `CSVCMsg_UpdateStringTable`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UpdateStringTable<'a> {
+    pub table_id: i32,
+    pub entries: i32,
+    pub data: &'a [u8],
+}
+
+impl<'a> UpdateStringTable<'a> {
+    pub fn decode(bytes: &'a [u8]) -> Result<Self, Error> {
+        let mut message = UpdateStringTable::default();
+
+        decode("CSVCMsg_UpdateStringTable", bytes, |number, value| {
+            match number {
+                1 => message.table_id = value.as_i32()?,
+                2 => message.entries = value.as_i32()?,
+                3 => message.data = value.as_bytes()?,
+                _ => {}
+            }
+
+            Some(())
+        })?;
+
+        Ok(message)
+    }
+}
+
+/// `CSVCMsg_PacketEntities`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PacketEntities<'a> {
+    pub updated_entries: i32,
+    pub data: &'a [u8],
+}
+
+impl<'a> PacketEntities<'a> {
+    pub fn decode(bytes: &'a [u8]) -> Result<Self, Error> {
+        let mut message = PacketEntities::default();
+
+        decode("CSVCMsg_PacketEntities", bytes, |number, value| {
+            match number {
+                2 => message.updated_entries = value.as_i32()?,
+                7 => message.data = value.as_bytes()?,
+                _ => {}
+            }
+
+            Some(())
+        })?;
+
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo::tests;
+
+    #[test]
+    fn property_bits() {
+        let property =
+            |kind, bits| send_property(&tests::send_property("m_iHealth", kind, 0, bits).0);
+
+        assert_eq!(property(PropertyKind::Int, 32).unwrap().bits, 32);
+        assert_eq!(property(PropertyKind::Int64, 64).unwrap().bits, 64);
+
+        for (kind, bits) in [
+            (PropertyKind::Int, 33),
+            (PropertyKind::Float, 64),
+            (PropertyKind::Int64, 65),
+            (PropertyKind::Int, -1),
+        ] {
+            assert_eq!(
+                property(kind, bits).unwrap_err(),
+                Error::InvalidMessage("CSVCMsg_SendTable.sendprop_t")
+            );
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Just enough protobuf to read net messages.
+
+/// a field value, by wire type
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+impl<'a> Value<'a> {
+    #[inline]
+    pub fn as_u32(&self) -> Option<u32> {
+        match *self {
+            Value::Varint(value) => Some(value as u32),
+            _ => None,
+        }
+    }
+
+    /// negative `int32`s are sign extended to ten bytes
+    #[inline]
+    pub fn as_i32(&self) -> Option<i32> {
+        self.as_u32().map(|value| value as i32)
+    }
+
+    #[inline]
+    pub fn as_bool(&self) -> Option<bool> {
+        self.as_u32().map(|value| value != 0)
+    }
+
+    #[inline]
+    pub fn as_f32(&self) -> Option<f32> {
+        match *self {
+            Value::Fixed32(value) => Some(f32::from_bits(value)),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> Option<&'a [u8]> {
+        match *self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_string(&self) -> Option<String> {
+        self.as_bytes()
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+/// read a varint from the front of `bytes`
+pub fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0;
+
+    for index in 0..10 {
+        let (byte, rest) = bytes.split_first()?;
+
+        *bytes = rest;
+        value |= ((byte & 0x7F) as u64) << (7 * index);
+
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+/// fields of a message, `None` once the message is malformed
+#[derive(Clone, Debug)]
+pub struct Fields<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Fields<'a> {
+    #[inline]
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.bytes.len() {
+            return None;
+        }
+
+        let (bytes, rest) = self.bytes.split_at(len);
+
+        self.bytes = rest;
+
+        Some(bytes)
+    }
+
+    fn read_field(&mut self) -> Option<(u32, Value<'a>)> {
+        let key = read_varint(&mut self.bytes)?;
+        let number = (key >> 3) as u32;
+
+        let value = match key & 7 {
+            0 => Value::Varint(read_varint(&mut self.bytes)?),
+            1 => Value::Fixed64(u64::from_le_bytes(self.take(8)?.try_into().ok()?)),
+            2 => {
+                let len = read_varint(&mut self.bytes)? as usize;
+
+                Value::Bytes(self.take(len)?)
+            }
+            5 => Value::Fixed32(u32::from_le_bytes(self.take(4)?.try_into().ok()?)),
+            _ => return None,
+        };
+
+        Some((number, value))
+    }
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = Option<(u32, Value<'a>)>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let field = self.read_field();
+
+        if field.is_none() {
+            self.bytes = &[];
+        }
+
+        Some(field)
+    }
+}
@@ -0,0 +1,224 @@
+use super::table::bit_len;
+use super::Error;
+use crate::bf::Reader;
+
+/// entries remembered for substring encoding
+const HISTORY_LEN: usize = 32;
+/// bits of an index into the history
+const HISTORY_BITS: u32 = 5;
+/// `SUBSTRING_BITS`
+const SUBSTRING_BITS: u32 = 5;
+/// `MAX_USERDATA_BITS`
+const USER_DATA_BITS: u32 = 14;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StringEntry {
+    pub string: String,
+    pub user_data: Vec<u8>,
+}
+
+/// a networked string table, such as `userinfo` or `instancebaseline`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StringTable {
+    name: String,
+    max_entries: i32,
+    user_data_fixed_size: bool,
+    user_data_bits: i32,
+    entries: Vec<StringEntry>,
+}
+
+impl StringTable {
+    #[inline]
+    pub(super) fn new(
+        name: String,
+        max_entries: i32,
+        user_data_fixed_size: bool,
+        user_data_bits: i32,
+    ) -> Self {
+        Self {
+            name,
+            max_entries,
+            user_data_fixed_size,
+            user_data_bits,
+            entries: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn entries(&self) -> &[StringEntry] {
+        &self.entries
+    }
+
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&StringEntry> {
+        self.entries.get(index)
+    }
+
+    /// user data of the entry named `string`
+    #[inline]
+    pub fn user_data(&self, string: &str) -> Option<&[u8]> {
+        self.entries
+            .iter()
+            .find(|entry| entry.string == string)
+            .map(|entry| entry.user_data.as_slice())
+    }
+
+    fn entry_mut(&mut self, index: usize) -> &mut StringEntry {
+        if index >= self.entries.len() {
+            self.entries.resize_with(index + 1, StringEntry::default);
+        }
+
+        &mut self.entries[index]
+    }
+
+    /// apply the data of `svc_CreateStringTable` or `svc_UpdateStringTable`
+    pub(super) fn update(&mut self, data: &[u8], entries: i32) -> Result<(), Error> {
+        let mut reader = Reader::new(data);
+        let entry_bits = bit_len(self.max_entries.max(1) as u32) - 1;
+
+        if reader.read_bit()? {
+            return Err(Error::Unsupported("dictionary encoded string table"));
+        }
+
+        let mut history: Vec<String> = Vec::with_capacity(HISTORY_LEN);
+        let mut last_index = -1;
+
+        for _ in 0..entries {
+            let index = if reader.read_bit()? {
+                last_index + 1
+            } else {
+                reader.read_ubits(entry_bits)? as i32
+            };
+
+            if index < 0 || index >= self.max_entries {
+                return Err(Error::InvalidMessage("CSVCMsg_UpdateStringTable"));
+            }
+
+            last_index = index;
+
+            let string = if reader.read_bit()? {
+                let string = if reader.read_bit()? {
+                    // a prefix of an earlier entry, then the rest
+                    let index = reader.read_ubits(HISTORY_BITS)? as usize;
+                    let len = reader.read_ubits(SUBSTRING_BITS)? as usize;
+                    let prefix = history
+                        .get(index)
+                        .ok_or(Error::InvalidMessage("CSVCMsg_UpdateStringTable"))?;
+
+                    let prefix = &prefix.as_bytes()[..len.min(prefix.len())];
+                    let mut string = String::from_utf8_lossy(prefix).into_owned();
+
+                    string.push_str(&reader.read_string()?);
+                    string
+                } else {
+                    reader.read_string()?
+                };
+
+                Some(string)
+            } else {
+                None
+            };
+
+            let user_data = if reader.read_bit()? {
+                let user_data = if self.user_data_fixed_size {
+                    read_bits(&mut reader, self.user_data_bits as u32)?
+                } else {
+                    let len = reader.read_ubits(USER_DATA_BITS)? as usize;
+
+                    reader.read_bytes(len)?
+                };
+
+                Some(user_data)
+            } else {
+                None
+            };
+
+            let entry = self.entry_mut(index as usize);
+
+            if let Some(string) = string {
+                entry.string = string;
+            }
+
+            if let Some(user_data) = user_data {
+                entry.user_data = user_data;
+            }
+
+            if history.len() == HISTORY_LEN {
+                history.remove(0);
+            }
+
+            history.push(entry.string.clone());
+        }
+
+        Ok(())
+    }
+}
+
+/// read `bits` into bytes, the last one partially filled (`ReadBits`)
+fn read_bits(reader: &mut Reader<'_>, bits: u32) -> Result<Vec<u8>, Error> {
+    let mut bytes = reader.read_bytes((bits / 8) as usize)?;
+
+    if bits % 8 != 0 {
+        bytes.push(reader.read_ubits(bits % 8)? as u8);
+    }
+
+    Ok(bytes)
+}
+
+/// `dem_stringtables`, every table and its entries
+pub(super) fn parse_snapshot(data: &[u8], tables: &mut Vec<StringTable>) -> Result<(), Error> {
+    let mut reader = Reader::new(data);
+    let len = reader.read_u8()?;
+
+    for _ in 0..len {
+        let name = reader.read_string()?;
+        let index = match tables.iter().position(|table| table.name == name) {
+            Some(index) => index,
+            None => {
+                tables.push(StringTable::new(name, i32::MAX, false, 0));
+                tables.len() - 1
+            }
+        };
+
+        let table = &mut tables[index];
+
+        table.entries.clear();
+        read_snapshot_entries(&mut reader, Some(&mut table.entries))?;
+
+        // client side entries
+        if reader.read_bit()? {
+            read_snapshot_entries(&mut reader, None)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_snapshot_entries(
+    reader: &mut Reader<'_>,
+    mut entries: Option<&mut Vec<StringEntry>>,
+) -> Result<(), Error> {
+    let len = reader.read_u16()?;
+
+    for _ in 0..len {
+        let string = reader.read_string()?;
+        let user_data = if reader.read_bit()? {
+            let len = reader.read_u16()? as usize;
+
+            reader.read_bytes(len)?
+        } else {
+            Vec::new()
+        };
+
+        if let Some(entries) = entries.as_mut() {
+            entries.push(StringEntry { string, user_data });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,410 @@
+use super::{message, Error};
+use crate::bf::Reader;
+use crate::client::PropertyKind;
+
+/// `SPROP_UNSIGNED`
+pub(super) const UNSIGNED: i32 = 1 << 0;
+/// `SPROP_COORD`
+pub(super) const COORD: i32 = 1 << 1;
+/// `SPROP_NOSCALE`
+pub(super) const NO_SCALE: i32 = 1 << 2;
+/// `SPROP_NORMAL`
+pub(super) const NORMAL: i32 = 1 << 5;
+/// `SPROP_EXCLUDE`
+const EXCLUDE: i32 = 1 << 6;
+/// `SPROP_INSIDEARRAY`
+const INSIDE_ARRAY: i32 = 1 << 8;
+/// `SPROP_COLLAPSIBLE`
+const COLLAPSIBLE: i32 = 1 << 11;
+/// `SPROP_COORD_MP`
+pub(super) const COORD_MP: i32 = 1 << 12;
+/// `SPROP_COORD_MP_LOWPRECISION`
+pub(super) const COORD_MP_LOW_PRECISION: i32 = 1 << 13;
+/// `SPROP_COORD_MP_INTEGRAL`
+pub(super) const COORD_MP_INTEGRAL: i32 = 1 << 14;
+/// `SPROP_CELL_COORD`
+pub(super) const CELL_COORD: i32 = 1 << 15;
+/// `SPROP_CELL_COORD_LOWPRECISION`
+pub(super) const CELL_COORD_LOW_PRECISION: i32 = 1 << 16;
+/// `SPROP_CELL_COORD_INTEGRAL`
+pub(super) const CELL_COORD_INTEGRAL: i32 = 1 << 17;
+/// `SPROP_CHANGES_OFTEN`
+const CHANGES_OFTEN: i32 = 1 << 18;
+/// `SPROP_VARINT`
+pub(super) const VARINT: i32 = 1 << 19;
+
+/// priority given to `SPROP_CHANGES_OFTEN` properties
+const CHANGES_OFTEN_PRIORITY: i32 = 64;
+
+/// `CSVCMsg_SendTable.sendprop_t`, the server side of a [`Property`](crate::client::Property)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SendProperty {
+    pub kind: PropertyKind,
+    pub name: String,
+    pub flags: i32,
+    pub priority: i32,
+    /// table name of data table properties, and exclusions
+    pub data_table: String,
+    /// for arrays
+    pub elements: i32,
+    pub low: f32,
+    pub high: f32,
+    pub bits: i32,
+}
+
+/// `CSVCMsg_SendTable`, the server side of a [`Table`](crate::client::Table)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SendTable {
+    pub name: String,
+    pub properties: Vec<SendProperty>,
+}
+
+/// a property of a flattened class, in the order the server sends them
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlattenedProperty {
+    /// the table the property was declared in
+    pub table: String,
+    pub property: SendProperty,
+    /// element of array properties
+    pub element: Option<SendProperty>,
+}
+
+/// a server class and its flattened properties
+#[derive(Clone, Debug, PartialEq)]
+pub struct Class {
+    pub id: u32,
+    pub name: String,
+    pub table: String,
+    pub properties: Vec<FlattenedProperty>,
+}
+
+impl Class {
+    /// index of the property named `name`, optionally qualified by its table as `table.name`
+    #[inline]
+    pub fn property_index(&self, name: &str) -> Option<usize> {
+        self.properties
+            .iter()
+            .position(|flattened| match name.split_once('.') {
+                Some((table, name)) => flattened.table == table && flattened.property.name == name,
+                None => flattened.property.name == name,
+            })
+    }
+}
+
+/// contents of the data tables frame
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tables {
+    tables: Vec<SendTable>,
+    classes: Vec<Class>,
+}
+
+impl Tables {
+    pub(super) fn parse(data: &[u8]) -> Result<Tables, Error> {
+        let mut reader = Reader::new(data);
+        let mut tables = Vec::new();
+
+        loop {
+            let _kind = reader.read_varint32()?;
+            let len = reader.read_varint32()? as usize;
+            let (table, is_end) = message::send_table(&reader.read_bytes(len)?)?;
+
+            if is_end {
+                break;
+            }
+
+            tables.push(table);
+        }
+
+        let len = reader.read_i16()?;
+        let mut classes = Vec::with_capacity(len.max(0) as usize);
+
+        for _ in 0..len {
+            let id = reader.read_i16()? as u32;
+            let name = reader.read_string()?;
+            let table = reader.read_string()?;
+
+            classes.push(Class {
+                id,
+                name,
+                table,
+                properties: Vec::new(),
+            });
+        }
+
+        let mut this = Tables {
+            tables,
+            classes: Vec::new(),
+        };
+
+        for mut class in classes {
+            class.properties = this.flatten(&class.table)?;
+            this.classes.push(class);
+        }
+
+        Ok(this)
+    }
+
+    #[inline]
+    pub fn tables(&self) -> &[SendTable] {
+        &self.tables
+    }
+
+    #[inline]
+    pub fn table(&self, name: &str) -> Option<&SendTable> {
+        self.tables.iter().find(|table| table.name == name)
+    }
+
+    #[inline]
+    pub fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    #[inline]
+    pub fn class(&self, id: u32) -> Option<&Class> {
+        match self.classes.get(id as usize) {
+            Some(class) if class.id == id => Some(class),
+            _ => self.classes.iter().find(|class| class.id == id),
+        }
+    }
+
+    #[inline]
+    pub fn class_by_name(&self, name: &str) -> Option<&Class> {
+        self.classes.iter().find(|class| class.name == name)
+    }
+
+    /// bits used to send a class id
+    #[inline]
+    pub(super) fn class_bits(&self) -> u32 {
+        bit_len(self.classes.len() as u32)
+    }
+
+    fn table_or_err(&self, name: &str) -> Result<&SendTable, Error> {
+        self.table(name)
+            .ok_or_else(|| Error::UnknownTable(name.into()))
+    }
+
+    /// `SendTable_BuildHierarchy` and `SendTable_SortByPriority`
+    fn flatten(&self, name: &str) -> Result<Vec<FlattenedProperty>, Error> {
+        let table = self.table_or_err(name)?;
+        let mut excludes = Vec::new();
+        let mut properties = Vec::new();
+
+        self.gather_excludes(table, &mut excludes)?;
+        self.gather_properties(table, &excludes, &mut properties)?;
+
+        let mut priorities: Vec<i32> = properties
+            .iter()
+            .map(|flattened| flattened.property.priority)
+            .chain([CHANGES_OFTEN_PRIORITY])
+            .collect();
+
+        priorities.sort_unstable();
+        priorities.dedup();
+
+        // the engine sorts with repeated swaps, so the order within a priority is not stable
+        let mut start = 0;
+
+        for priority in priorities {
+            while let Some(offset) = properties[start..].iter().position(|flattened| {
+                let property = &flattened.property;
+
+                property.priority == priority
+                    || (priority == CHANGES_OFTEN_PRIORITY && property.flags & CHANGES_OFTEN != 0)
+            }) {
+                properties.swap(start, start + offset);
+                start += 1;
+            }
+        }
+
+        Ok(properties)
+    }
+
+    fn gather_excludes<'a>(
+        &'a self,
+        table: &'a SendTable,
+        excludes: &mut Vec<(&'a str, &'a str)>,
+    ) -> Result<(), Error> {
+        for property in &table.properties {
+            if property.flags & EXCLUDE != 0 {
+                excludes.push((&property.data_table, &property.name));
+            }
+
+            if property.kind == PropertyKind::DataTable {
+                self.gather_excludes(self.table_or_err(&property.data_table)?, excludes)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// properties of non-collapsible sub tables come first
+    fn gather_properties(
+        &self,
+        table: &SendTable,
+        excludes: &[(&str, &str)],
+        flattened: &mut Vec<FlattenedProperty>,
+    ) -> Result<(), Error> {
+        let mut properties = Vec::new();
+
+        self.iterate_properties(table, excludes, flattened, &mut properties)?;
+        flattened.append(&mut properties);
+
+        Ok(())
+    }
+
+    fn iterate_properties(
+        &self,
+        table: &SendTable,
+        excludes: &[(&str, &str)],
+        flattened: &mut Vec<FlattenedProperty>,
+        properties: &mut Vec<FlattenedProperty>,
+    ) -> Result<(), Error> {
+        for (index, property) in table.properties.iter().enumerate() {
+            let excluded = excludes
+                .iter()
+                .any(|(table_name, name)| *table_name == table.name && *name == property.name);
+
+            if property.flags & (INSIDE_ARRAY | EXCLUDE) != 0 || excluded {
+                continue;
+            }
+
+            if property.kind == PropertyKind::DataTable {
+                let sub_table = self.table_or_err(&property.data_table)?;
+
+                if property.flags & COLLAPSIBLE != 0 {
+                    self.iterate_properties(sub_table, excludes, flattened, properties)?;
+                } else {
+                    self.gather_properties(sub_table, excludes, flattened)?;
+                }
+
+                continue;
+            }
+
+            // the element is declared right before the array
+            let element = if property.kind == PropertyKind::Array {
+                let element = index
+                    .checked_sub(1)
+                    .and_then(|index| table.properties.get(index))
+                    .ok_or(Error::InvalidMessage("CSVCMsg_SendTable"))?;
+
+                Some(element.clone())
+            } else {
+                None
+            };
+
+            properties.push(FlattenedProperty {
+                table: table.name.clone(),
+                property: property.clone(),
+                element,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// bits needed to store `value`, at least one (`Q_log2(value) + 1`)
+#[inline]
+pub(super) const fn bit_len(value: u32) -> u32 {
+    let len = u32::BITS - value.leading_zeros();
+
+    if len == 0 {
+        1
+    } else {
+        len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn property(name: &str, kind: PropertyKind, flags: i32, data_table: &str) -> SendProperty {
+        SendProperty {
+            kind,
+            name: name.into(),
+            flags,
+            priority: 128,
+            data_table: data_table.into(),
+            elements: 0,
+            low: 0.0,
+            high: 0.0,
+            bits: 8,
+        }
+    }
+
+    fn names(properties: &[FlattenedProperty]) -> Vec<&str> {
+        properties
+            .iter()
+            .map(|flattened| flattened.property.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn flatten() {
+        let tables = Tables {
+            tables: vec![
+                SendTable {
+                    name: "DT_Base".into(),
+                    properties: vec![
+                        property("m_fFlags", PropertyKind::Int, 0, ""),
+                        property("m_iHealth", PropertyKind::Int, 0, ""),
+                    ],
+                },
+                SendTable {
+                    name: "DT_Local".into(),
+                    properties: vec![property("m_flFallVelocity", PropertyKind::Float, 0, "")],
+                },
+                SendTable {
+                    name: "DT_Player".into(),
+                    properties: vec![
+                        property("m_fFlags", PropertyKind::Int, EXCLUDE, "DT_Base"),
+                        property("baseclass", PropertyKind::DataTable, 0, "DT_Base"),
+                        property("m_Local", PropertyKind::DataTable, COLLAPSIBLE, "DT_Local"),
+                        property("m_iAmmo_element", PropertyKind::Int, INSIDE_ARRAY, ""),
+                        property("m_iAmmo", PropertyKind::Array, 0, ""),
+                        property("m_flSimulationTime", PropertyKind::Int, CHANGES_OFTEN, ""),
+                    ],
+                },
+            ],
+            classes: Vec::new(),
+        };
+
+        let properties = tables.flatten("DT_Player").unwrap();
+
+        // before sorting, the base class comes first: health, fall velocity, ammo, simulation
+        // time. moving simulation time to the front swaps health to the back.
+        assert_eq!(
+            names(&properties),
+            [
+                "m_flSimulationTime",
+                "m_flFallVelocity",
+                "m_iAmmo",
+                "m_iHealth"
+            ]
+        );
+
+        assert_eq!(properties[1].table, "DT_Local");
+        assert_eq!(
+            properties[2]
+                .element
+                .as_ref()
+                .map(|element| element.name.as_str()),
+            Some("m_iAmmo_element")
+        );
+
+        assert_eq!(
+            tables.flatten("DT_Missing"),
+            Err(Error::UnknownTable("DT_Missing".into()))
+        );
+    }
+
+    #[test]
+    fn bits() {
+        assert_eq!(bit_len(0), 1);
+        assert_eq!(bit_len(1), 1);
+        assert_eq!(bit_len(2), 2);
+        assert_eq!(bit_len(255), 8);
+        assert_eq!(bit_len(256), 9);
+    }
+}
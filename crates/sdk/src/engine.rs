@@ -1,4 +1,4 @@
-use super::{ffi, vtable_export, vtable_validate, NetworkChannel, SteamAPIContext, SteamId};
+use super::{ffi, vtable_export, vtable_validate, NetworkChannel, SteamAPIContext};
 use cake::ffi::vtable;
 use elysium_math::{Matrix3x4, Vec3};
 use std::ffi::OsStr;
@@ -13,12 +13,42 @@ pub struct PlayerInfo {
     pub user_id: i32,
     pub guid: [u8; 33],
     pub friends_id: u32,
+    pub friends_name: [u8; 128],
     pub fake_player: bool,
     pub hltv: bool,
     pub custom_files: [i32; 4],
     pub files_downloaded: u8,
 }
 
+impl PlayerInfo {
+    /// the player's name
+    #[inline]
+    pub fn name(&self) -> &str {
+        str_from_nul_padded(&self.name)
+    }
+
+    /// the player's friends name
+    #[inline]
+    pub fn friends_name(&self) -> &str {
+        str_from_nul_padded(&self.friends_name)
+    }
+}
+
+/// names are nul-padded, and may be cut mid-character
+#[inline]
+fn str_from_nul_padded(bytes: &[u8]) -> &str {
+    let len = bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(bytes.len());
+    let bytes = &bytes[..len];
+
+    match core::str::from_utf8(bytes) {
+        Ok(string) => string,
+        Err(error) => unsafe { core::str::from_utf8_unchecked(&bytes[..error.valid_up_to()]) },
+    }
+}
+
 #[repr(C)]
 struct VTable {
     _unknown0: vtable::Pad<5>,
@@ -30,8 +60,7 @@ struct VTable {
         index: i32,
         player_info: *mut PlayerInfo,
     ) -> bool,
-    get_player_for_user_id:
-        unsafe extern "thiscall" fn(this: *const Engine, user_id: SteamId) -> i32,
+    get_player_for_user_id: unsafe extern "thiscall" fn(this: *const Engine, user_id: i32) -> i32,
     _unknown2: vtable::Pad<2>,
     local_player_index: unsafe extern "thiscall" fn(this: *const Engine) -> i32,
     _unknown3: vtable::Pad<5>,
@@ -104,9 +133,12 @@ impl Engine {
 
         /// returns the bsp tree
         get_bsp_tree_query() -> *const (),
+    }
 
-        /// returns the network channel
-        get_network_channel() -> *const NetworkChannel,
+    /// returns the network channel, `None` when not connected
+    #[inline]
+    pub fn network_channel(&self) -> Option<&NetworkChannel> {
+        unsafe { (self.vtable.get_network_channel)(self).as_ref() }
     }
 
     /// returns the local player's index
@@ -143,14 +175,12 @@ impl Engine {
         }
     }
 
-    /// get player index by `user_id`
+    /// get player index by `user_id`, as found in game events
     #[inline]
-    pub fn get_player_for_user_id(&self, user_id: SteamId) -> Option<i32> {
-        unsafe {
-            let index = (self.vtable.get_player_for_user_id)(self, user_id);
+    pub fn get_player_for_user_id(&self, user_id: i32) -> Option<i32> {
+        let index = unsafe { (self.vtable.get_player_for_user_id)(self, user_id) };
 
-            Some(index)
-        }
+        (index != 0).then_some(index)
     }
 
     /// get the view angle
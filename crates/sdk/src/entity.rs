@@ -1,9 +1,16 @@
+pub use handle::EntityHandle;
 pub use id::EntityId;
+pub use iter::{
+    Bomb, EntityRef, FogController, GrenadeProjectile, Inferno, Iter, Kind, Player,
+    TonemapController,
+};
 pub use list::EntityList;
 pub use networkable::{DataUpdateKind, Networkable};
 pub use renderable::Renderable;
 
+mod handle;
 mod id;
+mod iter;
 mod list;
 mod networkable;
 mod renderable;
@@ -0,0 +1,139 @@
+use crate::vtable_validate;
+use cake::ffi::vtable;
+use core::fmt;
+
+/// bits used for the entity index (`NUM_ENT_ENTRY_BITS`)
+const INDEX_BITS: u32 = 12;
+
+/// mask for the entity index (`ENT_ENTRY_MASK`)
+const INDEX_MASK: u32 = (1 << INDEX_BITS) - 1;
+
+/// where the serial number starts (`NUM_SERIAL_NUM_SHIFT_BITS`)
+const SERIAL_SHIFT: u32 = 16;
+
+/// `CBaseHandle`, an entity index paired with the serial number of the entity occupying it
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct EntityHandle(pub u32);
+
+impl EntityHandle {
+    /// `INVALID_EHANDLE_INDEX`
+    pub const INVALID: EntityHandle = EntityHandle(u32::MAX);
+
+    #[inline]
+    pub const fn new(index: i32, serial: i32) -> Self {
+        Self((index as u32 & INDEX_MASK) | ((serial as u32) << SERIAL_SHIFT))
+    }
+
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    #[inline]
+    pub const fn to_raw(self) -> u32 {
+        self.0
+    }
+
+    /// entity index
+    #[inline]
+    pub const fn index(self) -> i32 {
+        (self.0 & INDEX_MASK) as i32
+    }
+
+    /// serial number of the entity this handle was made for
+    #[inline]
+    pub const fn serial(self) -> i32 {
+        (self.0 >> SERIAL_SHIFT) as i32
+    }
+
+    /// whether this handle refers to anything at all (not whether that entity still exists)
+    #[inline]
+    pub const fn is_valid(self) -> bool {
+        self.0 != Self::INVALID.0
+    }
+}
+
+impl Default for EntityHandle {
+    #[inline]
+    fn default() -> Self {
+        Self::INVALID
+    }
+}
+
+impl fmt::Debug for EntityHandle {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_valid() {
+            fmt.debug_struct("EntityHandle")
+                .field("index", &self.index())
+                .field("serial", &self.serial())
+                .finish()
+        } else {
+            fmt.write_str("EntityHandle::INVALID")
+        }
+    }
+}
+
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<3>,
+    handle: unsafe extern "thiscall" fn(this: *const HandleEntity) -> *const EntityHandle,
+}
+
+vtable_validate! {
+    handle => 3,
+}
+
+/// `IHandleEntity`, the first base of every client entity
+#[repr(C)]
+pub(super) struct HandleEntity {
+    vtable: &'static VTable,
+}
+
+impl HandleEntity {
+    /// `GetRefEHandle`, the handle the entity list assigned to this entity
+    #[inline]
+    pub fn handle(&self) -> EntityHandle {
+        unsafe { *(self.vtable.handle)(self) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let handle = EntityHandle::from_raw(0x0123_0045);
+
+        assert_eq!(handle.index(), 0x45);
+        assert_eq!(handle.serial(), 0x123);
+        assert!(handle.is_valid());
+    }
+
+    #[test]
+    fn encode() {
+        let handle = EntityHandle::new(1, 2);
+
+        assert_eq!(handle.to_raw(), 0x0002_0001);
+        assert_eq!(EntityHandle::new(4095, 0xFFFF).index(), 4095);
+        assert_eq!(EntityHandle::new(4096, 1).index(), 0);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(!EntityHandle::INVALID.is_valid());
+        assert!(!EntityHandle::default().is_valid());
+        assert_eq!(EntityHandle::INVALID.index(), 4095);
+    }
+
+    #[test]
+    fn stale() {
+        // same slot, reused by a different entity
+        let old = EntityHandle::new(70, 1);
+        let new = EntityHandle::new(70, 2);
+
+        assert_eq!(old.index(), new.index());
+        assert_ne!(old, new);
+    }
+}
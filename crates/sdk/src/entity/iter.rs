@@ -0,0 +1,202 @@
+use super::handle::HandleEntity;
+use super::{EntityHandle, EntityId, EntityList, Networkable, Renderable};
+use crate::client::Class;
+use core::fmt;
+use core::ops::RangeInclusive;
+
+/// a kind of entity, for filtering with [`Iter::of`]
+pub trait Kind {
+    /// classes which are this kind of entity
+    const ENTITY_IDS: &'static [EntityId];
+}
+
+macro_rules! kinds {
+    ($($(#[$meta:meta])* $name:ident => [$($id:ident),*],)*) => {$(
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug)]
+        pub struct $name;
+
+        impl Kind for $name {
+            const ENTITY_IDS: &'static [EntityId] = &[$(EntityId::$id),*];
+        }
+    )*};
+}
+
+kinds! {
+    /// `CFogController`
+    FogController => [CFogController],
+    /// `CEnvTonemapController`
+    TonemapController => [CEnvTonemapController],
+    /// `CCSPlayer`
+    Player => [CCSPlayer],
+    /// `CC4` and `CPlantedC4`
+    Bomb => [CC4, CPlantedC4],
+    /// `CInferno`
+    Inferno => [CInferno],
+    /// thrown grenades
+    GrenadeProjectile => [CBaseCSGrenadeProjectile, CSmokeGrenadeProjectile],
+}
+
+/// a non-null entity within the entity list
+#[derive(Clone, Copy)]
+pub struct EntityRef<'a> {
+    index: i32,
+    entity: &'a u8,
+}
+
+impl<'a> EntityRef<'a> {
+    /// wrap an `IClientEntity` pointer, if it isn't null
+    #[inline]
+    pub unsafe fn from_ptr(index: i32, entity: *const u8) -> Option<Self> {
+        let entity = entity.as_ref()?;
+
+        Some(Self { index, entity })
+    }
+
+    #[inline]
+    pub const fn index(&self) -> i32 {
+        self.index
+    }
+
+    #[inline]
+    pub const fn as_ptr(&self) -> *const u8 {
+        self.entity
+    }
+
+    /// reinterpret as some entity type
+    #[inline]
+    pub unsafe fn cast<T>(&self) -> &'a T {
+        &*(self.entity as *const u8).cast()
+    }
+
+    /// the handle the entity list assigned to this entity (`GetRefEHandle`)
+    #[inline]
+    pub fn handle(&self) -> EntityHandle {
+        unsafe { self.cast::<HandleEntity>().handle() }
+    }
+
+    #[inline]
+    pub fn renderable(&self) -> &'a Renderable {
+        unsafe { &*self.as_ptr().byte_add(8).cast() }
+    }
+
+    #[inline]
+    pub fn networkable(&self) -> &'a Networkable {
+        unsafe { &*self.as_ptr().byte_add(16).cast() }
+    }
+
+    #[inline]
+    pub fn client_class(&self) -> Option<&'a Class> {
+        unsafe { self.networkable().client_class().cast::<Class>().as_ref() }
+    }
+
+    #[inline]
+    pub fn entity_id(&self) -> Option<EntityId> {
+        self.client_class().map(|class| class.entity_id)
+    }
+
+    #[inline]
+    pub fn is_dormant(&self) -> bool {
+        self.networkable().is_dormant()
+    }
+
+    /// whether this entity is of kind `K`
+    #[inline]
+    pub fn is<K: Kind>(&self) -> bool {
+        self.entity_id()
+            .map(|entity_id| K::ENTITY_IDS.contains(&entity_id))
+            .unwrap_or(false)
+    }
+}
+
+impl<'a> fmt::Debug for EntityRef<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("EntityRef")
+            .field("index", &self.index)
+            .field("entity", &self.as_ptr())
+            .field("entity_id", &self.entity_id())
+            .finish()
+    }
+}
+
+/// iterator over non-null entities, see [`EntityList::iter`]
+#[derive(Clone)]
+pub struct Iter<'a> {
+    list: &'a EntityList,
+    range: RangeInclusive<i32>,
+    skip_dormant: bool,
+}
+
+impl<'a> Iter<'a> {
+    #[inline]
+    pub(super) fn new(list: &'a EntityList, range: RangeInclusive<i32>) -> Self {
+        Self {
+            list,
+            range,
+            skip_dormant: false,
+        }
+    }
+
+    /// skip dormant entities
+    #[inline]
+    pub fn non_dormant(mut self) -> Self {
+        self.skip_dormant = true;
+        self
+    }
+
+    /// only entities of kind `K`
+    #[inline]
+    pub fn of<K: Kind>(self) -> impl Iterator<Item = EntityRef<'a>> {
+        self.filter(EntityRef::is::<K>)
+    }
+
+    /// only players
+    #[inline]
+    pub fn players(mut self) -> impl Iterator<Item = EntityRef<'a>> {
+        let player_range = self.list.player_range();
+
+        self.range = intersect(&self.range, &player_range);
+        self.of::<Player>()
+    }
+
+    /// only entities outside of the player range
+    #[inline]
+    pub fn non_players(mut self) -> Self {
+        let non_player_range = self.list.non_player_range();
+
+        self.range = intersect(&self.range, &non_player_range);
+        self
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = EntityRef<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<EntityRef<'a>> {
+        loop {
+            let index = self.range.next()?;
+            let entity = unsafe { EntityRef::from_ptr(index, self.list.entity(index)) };
+            let entity = match entity {
+                Some(entity) => entity,
+                None => continue,
+            };
+
+            if self.skip_dormant && entity.is_dormant() {
+                continue;
+            }
+
+            return Some(entity);
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.range.size_hint().1)
+    }
+}
+
+#[inline]
+fn intersect(a: &RangeInclusive<i32>, b: &RangeInclusive<i32>) -> RangeInclusive<i32> {
+    (*a.start()).max(*b.start())..=(*a.end()).min(*b.end())
+}
@@ -1,16 +1,23 @@
+use super::{EntityHandle, EntityRef, Iter};
 use crate::{vtable_validate, Engine};
 use core::ops::RangeInclusive;
 
 #[repr(C)]
 pub struct VTable {
     networkable: unsafe extern "thiscall" fn(this: *const EntityList, index: i32) -> *const u8,
-    networkable_from_handle:
-        unsafe extern "thiscall" fn(this: *const EntityList, handle: *const u8) -> *const u8,
-    unknown_from_handle:
-        unsafe extern "thiscall" fn(this: *const EntityList, handle: *const u8) -> *const u8,
+    networkable_from_handle: unsafe extern "thiscall" fn(
+        this: *const EntityList,
+        handle: *const EntityHandle,
+    ) -> *const u8,
+    unknown_from_handle: unsafe extern "thiscall" fn(
+        this: *const EntityList,
+        handle: *const EntityHandle,
+    ) -> *const u8,
     entity: unsafe extern "thiscall" fn(this: *const EntityList, index: i32) -> *const u8,
-    entity_from_handle:
-        unsafe extern "thiscall" fn(this: *const EntityList, handle: *const u8) -> *const u8,
+    entity_from_handle: unsafe extern "thiscall" fn(
+        this: *const EntityList,
+        handle: *const EntityHandle,
+    ) -> *const u8,
     number_of_entities:
         unsafe extern "thiscall" fn(this: *const EntityList, include_non_networked: bool) -> i32,
     highest_entity_index: unsafe extern "thiscall" fn(this: *const EntityList) -> i32,
@@ -43,13 +50,13 @@ impl EntityList {
     }
 
     #[inline]
-    pub fn networkable_from_handle(&self, handle: *const u8) -> *const u8 {
-        unsafe { (self.vtable.networkable_from_handle)(self, handle) }
+    pub fn networkable_from_handle(&self, handle: EntityHandle) -> *const u8 {
+        unsafe { (self.vtable.networkable_from_handle)(self, &handle) }
     }
 
     #[inline]
-    pub fn unknown_from_handle(&self, handle: *const u8) -> *const u8 {
-        unsafe { (self.vtable.unknown_from_handle)(self, handle) }
+    pub fn unknown_from_handle(&self, handle: EntityHandle) -> *const u8 {
+        unsafe { (self.vtable.unknown_from_handle)(self, &handle) }
     }
 
     #[inline]
@@ -58,8 +65,24 @@ impl EntityList {
     }
 
     #[inline]
-    pub fn entity_from_handle(&self, handle: *const u8) -> *const u8 {
-        unsafe { (self.vtable.entity_from_handle)(self, handle) }
+    pub fn entity_from_handle(&self, handle: EntityHandle) -> *const u8 {
+        unsafe { (self.vtable.entity_from_handle)(self, &handle) }
+    }
+
+    /// resolve `handle`, if its entity still occupies the slot
+    ///
+    /// a stale handle to a reused slot has a different serial than the current occupant, and
+    /// resolves to `None` rather than the new entity
+    #[inline]
+    pub fn resolve(&self, handle: EntityHandle) -> Option<EntityRef<'_>> {
+        if !handle.is_valid() {
+            return None;
+        }
+
+        let index = handle.index();
+        let entity = unsafe { EntityRef::from_ptr(index, self.entity(index))? };
+
+        (entity.handle() == handle).then_some(entity)
     }
 
     #[inline]
@@ -87,6 +110,12 @@ impl EntityList {
         self.entity(engine.local_player_index())
     }
 
+    /// iterate every non-null entity
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::new(self, 1..=self.highest_entity_index())
+    }
+
     #[inline]
     pub fn player_range(&self) -> RangeInclusive<i32> {
         1..=64
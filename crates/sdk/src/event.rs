@@ -0,0 +1,312 @@
+//! Game events (`IGameEventManager2`)
+
+use crate::{ffi, vtable_validate, HitGroup};
+use cake::ffi::vtable;
+use core::fmt;
+use elysium_math::Vec3;
+use std::ffi::OsStr;
+
+/// `EVENT_DEBUG_ID_INIT`, the engine refuses listeners which don't return this
+const DEBUG_ID: i32 = 42;
+
+/// called with every event a [`Listener`] was added for
+pub type FireGameEvent = unsafe extern "C" fn(event: *const Event);
+
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<4>,
+    add_listener: unsafe extern "thiscall" fn(
+        this: *const Events,
+        listener: *const Listener,
+        name: *const u8,
+        server_side: bool,
+    ) -> bool,
+    find_listener: unsafe extern "thiscall" fn(
+        this: *const Events,
+        listener: *const Listener,
+        name: *const u8,
+    ) -> bool,
+    remove_listener: unsafe extern "thiscall" fn(this: *const Events, listener: *const Listener),
+}
+
+vtable_validate! {
+    add_listener => 4,
+    find_listener => 5,
+    remove_listener => 6,
+}
+
+/// `IGameEventManager2`
+#[repr(C)]
+pub struct Events {
+    vtable: &'static VTable,
+}
+
+impl Events {
+    /// have `listener` called for every event named `name`
+    #[inline]
+    pub fn add_listener<S>(&self, listener: &'static Listener, name: S) -> bool
+    where
+        S: AsRef<OsStr>,
+    {
+        let cstr = ffi::osstr_to_cstr_cow(name);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+
+        unsafe { (self.vtable.add_listener)(self, listener, ptr, false) }
+    }
+
+    /// whether `listener` is called for events named `name`
+    #[inline]
+    pub fn has_listener<S>(&self, listener: &Listener, name: S) -> bool
+    where
+        S: AsRef<OsStr>,
+    {
+        let cstr = ffi::osstr_to_cstr_cow(name);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+
+        unsafe { (self.vtable.find_listener)(self, listener, ptr) }
+    }
+
+    /// stop calling `listener` for any event
+    #[inline]
+    pub fn remove_listener(&self, listener: &Listener) {
+        unsafe { (self.vtable.remove_listener)(self, listener) }
+    }
+}
+
+#[repr(C)]
+struct EventVTable {
+    _pad0: vtable::Pad<2>,
+    name: unsafe extern "thiscall" fn(this: *const Event) -> *const u8,
+    _pad1: vtable::Pad<3>,
+    get_bool:
+        unsafe extern "thiscall" fn(this: *const Event, key: *const u8, default: bool) -> bool,
+    get_int: unsafe extern "thiscall" fn(this: *const Event, key: *const u8, default: i32) -> i32,
+    _pad2: vtable::Pad<1>,
+    get_float: unsafe extern "thiscall" fn(this: *const Event, key: *const u8, default: f32) -> f32,
+    get_string: unsafe extern "thiscall" fn(
+        this: *const Event,
+        key: *const u8,
+        default: *const u8,
+    ) -> *const u8,
+}
+
+/// `IGameEvent`
+#[repr(C)]
+pub struct Event {
+    vtable: &'static EventVTable,
+}
+
+impl Event {
+    /// the event's name, such as `player_hurt`
+    #[inline]
+    pub fn name(&self) -> &str {
+        unsafe { ffi::str_from_ptr_nullable((self.vtable.name)(self)) }
+    }
+
+    #[inline]
+    pub fn get_bool<S>(&self, key: S) -> bool
+    where
+        S: AsRef<OsStr>,
+    {
+        let cstr = ffi::osstr_to_cstr_cow(key);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+
+        unsafe { (self.vtable.get_bool)(self, ptr, false) }
+    }
+
+    #[inline]
+    pub fn get_int<S>(&self, key: S) -> i32
+    where
+        S: AsRef<OsStr>,
+    {
+        let cstr = ffi::osstr_to_cstr_cow(key);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+
+        unsafe { (self.vtable.get_int)(self, ptr, 0) }
+    }
+
+    #[inline]
+    pub fn get_float<S>(&self, key: S) -> f32
+    where
+        S: AsRef<OsStr>,
+    {
+        let cstr = ffi::osstr_to_cstr_cow(key);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+
+        unsafe { (self.vtable.get_float)(self, ptr, 0.0) }
+    }
+
+    #[inline]
+    pub fn get_string<S>(&self, key: S) -> &str
+    where
+        S: AsRef<OsStr>,
+    {
+        let cstr = ffi::osstr_to_cstr_cow(key);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+
+        unsafe {
+            let string = (self.vtable.get_string)(self, ptr, b"\0".as_ptr());
+
+            ffi::str_from_ptr_nullable(string)
+        }
+    }
+}
+
+impl fmt::Debug for Event {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("Event").field(&self.name()).finish()
+    }
+}
+
+#[repr(C)]
+struct ListenerVTable {
+    drop: unsafe extern "C" fn(this: *const Listener),
+    delete: unsafe extern "C" fn(this: *const Listener),
+    fire_game_event: unsafe extern "C" fn(this: *const Listener, event: *const Event),
+    debug_id: unsafe extern "C" fn(this: *const Listener) -> i32,
+}
+
+/// `IGameEventListener2`, implemented in Rust
+#[repr(C)]
+pub struct Listener {
+    vtable: &'static ListenerVTable,
+    callback: FireGameEvent,
+}
+
+impl Listener {
+    pub const fn new(callback: FireGameEvent) -> Self {
+        Self {
+            vtable: &ListenerVTable {
+                drop: listener_drop,
+                delete: listener_drop,
+                fire_game_event: listener_fire_game_event,
+                debug_id: listener_debug_id,
+            },
+            callback,
+        }
+    }
+}
+
+// the engine never owns listeners
+unsafe extern "C" fn listener_drop(_this: *const Listener) {}
+
+unsafe extern "C" fn listener_fire_game_event(this: *const Listener, event: *const Event) {
+    if let Some(this) = this.as_ref() {
+        (this.callback)(event);
+    }
+}
+
+unsafe extern "C" fn listener_debug_id(_this: *const Listener) -> i32 {
+    DEBUG_ID
+}
+
+/// events we have typed bindings for
+///
+/// players are identified by user id, see `Engine::get_player_for_user_id`
+#[derive(Clone, Copy, Debug)]
+pub enum GameEvent<'a> {
+    PlayerHurt {
+        user_id: i32,
+        attacker: i32,
+        /// health remaining
+        health: i32,
+        /// armor remaining
+        armor: i32,
+        weapon: &'a str,
+        damage_health: i32,
+        damage_armor: i32,
+        hit_group: Option<HitGroup>,
+    },
+    PlayerDeath {
+        user_id: i32,
+        attacker: i32,
+        assister: i32,
+        weapon: &'a str,
+        headshot: bool,
+        /// number of objects the bullet went through
+        penetrated: i32,
+    },
+    BulletImpact {
+        user_id: i32,
+        position: Vec3,
+    },
+    RoundStart {
+        time_limit: i32,
+        frag_limit: i32,
+        objective: &'a str,
+    },
+    WeaponFire {
+        user_id: i32,
+        weapon: &'a str,
+        silenced: bool,
+    },
+}
+
+impl<'a> GameEvent<'a> {
+    /// names of every typed event, for adding listeners
+    pub const NAMES: &'static [&'static str] = &[
+        "player_hurt",
+        "player_death",
+        "bullet_impact",
+        "round_start",
+        "weapon_fire",
+    ];
+
+    /// read a typed event, if `event` is one we have bindings for
+    #[inline]
+    pub fn from_event(event: &'a Event) -> Option<Self> {
+        let event = match event.name() {
+            "player_hurt" => GameEvent::PlayerHurt {
+                user_id: event.get_int("userid\0"),
+                attacker: event.get_int("attacker\0"),
+                health: event.get_int("health\0"),
+                armor: event.get_int("armor\0"),
+                weapon: event.get_string("weapon\0"),
+                damage_health: event.get_int("dmg_health\0"),
+                damage_armor: event.get_int("dmg_armor\0"),
+                hit_group: HitGroup::from_raw(event.get_int("hitgroup\0")),
+            },
+            "player_death" => GameEvent::PlayerDeath {
+                user_id: event.get_int("userid\0"),
+                attacker: event.get_int("attacker\0"),
+                assister: event.get_int("assister\0"),
+                weapon: event.get_string("weapon\0"),
+                headshot: event.get_bool("headshot\0"),
+                penetrated: event.get_int("penetrated\0"),
+            },
+            "bullet_impact" => GameEvent::BulletImpact {
+                user_id: event.get_int("userid\0"),
+                position: Vec3::from_xyz(
+                    event.get_float("x\0"),
+                    event.get_float("y\0"),
+                    event.get_float("z\0"),
+                ),
+            },
+            "round_start" => GameEvent::RoundStart {
+                time_limit: event.get_int("timelimit\0"),
+                frag_limit: event.get_int("fraglimit\0"),
+                objective: event.get_string("objective\0"),
+            },
+            "weapon_fire" => GameEvent::WeaponFire {
+                user_id: event.get_int("userid\0"),
+                weapon: event.get_string("weapon\0"),
+                silenced: event.get_bool("silenced\0"),
+            },
+            _ => return None,
+        };
+
+        Some(event)
+    }
+
+    /// the event's name
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            GameEvent::PlayerHurt { .. } => "player_hurt",
+            GameEvent::PlayerDeath { .. } => "player_death",
+            GameEvent::BulletImpact { .. } => "bullet_impact",
+            GameEvent::RoundStart { .. } => "round_start",
+            GameEvent::WeaponFire { .. } => "weapon_fire",
+        }
+    }
+}
@@ -14,6 +14,39 @@ pub enum HitGroup {
 }
 
 impl HitGroup {
+    #[inline]
+    pub const fn from_raw(raw: i32) -> Option<HitGroup> {
+        let group = match raw {
+            0 => HitGroup::Generic,
+            1 => HitGroup::Head,
+            2 => HitGroup::Chest,
+            3 => HitGroup::Stomach,
+            4 => HitGroup::LeftArm,
+            5 => HitGroup::RightArm,
+            6 => HitGroup::LeftLeg,
+            7 => HitGroup::RightLeg,
+            8 => HitGroup::Gear,
+            _ => return None,
+        };
+
+        Some(group)
+    }
+
+    #[inline]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            HitGroup::Generic => "body",
+            HitGroup::Head => "head",
+            HitGroup::Chest => "chest",
+            HitGroup::Stomach => "stomach",
+            HitGroup::LeftArm => "left arm",
+            HitGroup::RightArm => "right arm",
+            HitGroup::LeftLeg => "left leg",
+            HitGroup::RightLeg => "right leg",
+            HitGroup::Gear => "gear",
+        }
+    }
+
     /// returns the damage modifier for the given hit group
     #[inline]
     pub const fn damage_modifier(&self) -> f32 {
@@ -30,4 +63,18 @@ impl HitGroup {
     pub const fn is_head(&self) -> bool {
         matches!(self, HitGroup::Head)
     }
+
+    /// if kevlar protects the hit group (the head needs a helmet)
+    #[inline]
+    pub const fn is_armored(&self, has_helmet: bool) -> bool {
+        match self {
+            HitGroup::Head => has_helmet,
+            HitGroup::Generic
+            | HitGroup::Chest
+            | HitGroup::Stomach
+            | HitGroup::LeftArm
+            | HitGroup::RightArm => true,
+            _ => false,
+        }
+    }
 }
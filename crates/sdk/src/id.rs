@@ -1,9 +1,11 @@
 //! Steam ID.
 
+pub use format::{ParseError, Steam2, Steam3};
 pub use instance::Instance;
 pub use kind::Kind;
 pub use universe::Universe;
 
+mod format;
 mod instance;
 mod kind;
 mod universe;
@@ -112,4 +114,75 @@ mod tests {
 
         assert_eq!(id.universe(), Universe::Public);
     }
+
+    #[test]
+    fn steam2() {
+        let id = SteamId::new(STEAMID).unwrap();
+
+        assert_eq!(id.steam2().to_string(), "STEAM_1:1:646918469");
+        assert_eq!(SteamId::parse_steam2("STEAM_1:1:646918469"), Ok(id));
+        assert_eq!(SteamId::parse_steam2("STEAM_0:1:646918469"), Ok(id));
+        assert_eq!("STEAM_1:1:646918469".parse(), Ok(id));
+    }
+
+    #[test]
+    fn steam3() {
+        let id = SteamId::new(STEAMID).unwrap();
+
+        assert_eq!(id.steam3().to_string(), "[U:1:1293836939]");
+        assert_eq!(SteamId::parse_steam3("[U:1:1293836939]"), Ok(id));
+        assert_eq!("[U:1:1293836939]".parse(), Ok(id));
+        assert_eq!(SteamId::from_account_id(STEAMID3), id);
+
+        let web = SteamId::from_parts(Universe::Public, Kind::Individual, Instance::Web, 1);
+
+        assert_eq!(web.instance(), Instance::Web);
+        assert_eq!(web.steam3().to_string(), "[U:1:1:4]");
+        assert_eq!(SteamId::parse_steam3("[U:1:1:4]"), Ok(web));
+
+        let server = SteamId::parse_steam3("[G:1:42]").unwrap();
+
+        assert_eq!(server.kind(), Kind::GameServer);
+        assert_eq!(server.instance(), Instance::All);
+        assert_eq!(server.steam3().to_string(), "[G:1:42]");
+    }
+
+    #[test]
+    fn steam64() {
+        let id = SteamId::new(STEAMID).unwrap();
+
+        assert_eq!(id.to_string(), "76561199254102667");
+        assert_eq!(id.to_u64(), STEAMID);
+        assert_eq!("76561199254102667".parse(), Ok(id));
+        assert_eq!(
+            id.profile_url(),
+            "https://steamcommunity.com/profiles/76561199254102667"
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (string, error) in [
+            ("", ParseError::Format),
+            ("STEAM_1:1", ParseError::Id),
+            ("STEAM_1:2:3", ParseError::Id),
+            ("STEAM_9:1:3", ParseError::Universe),
+            ("STEAM_1:1:3:4", ParseError::Format),
+            ("[U:1:3", ParseError::Format),
+            ("[X:1:3]", ParseError::Kind),
+            ("[?:1:3]", ParseError::Kind),
+            ("[U:9:3]", ParseError::Universe),
+            ("[U:1:-3]", ParseError::Id),
+            ("[U:1:3:3]", ParseError::Instance),
+            ("+76561199254102667", ParseError::Format),
+            ("18446744073709551615", ParseError::Universe),
+        ] {
+            assert_eq!(string.parse::<SteamId>(), Err(error), "{string:?}");
+        }
+
+        assert_eq!(
+            ParseError::Kind.to_string(),
+            "invalid steam id account type"
+        );
+    }
 }
@@ -0,0 +1,241 @@
+use super::{bits, Instance, Kind, SteamId, Universe};
+use core::fmt;
+use core::str::FromStr;
+
+/// SteamID3 account type letters, indexed by [`Kind`]
+const KIND_LETTERS: [char; 11] = ['I', 'U', 'M', 'G', 'A', 'P', 'C', 'g', 'T', '?', 'a'];
+
+/// community profile link prefix
+const PROFILE_URL: &str = "https://steamcommunity.com/profiles/";
+
+/// failed to parse a Steam ID, naming the bad component
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// not `STEAM_X:Y:Z`, `[U:1:N]` or a 64-bit number
+    Format,
+    Universe,
+    Kind,
+    Instance,
+    /// the account number
+    Id,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let component = match self {
+            ParseError::Format => return fmt.write_str("unrecognised steam id format"),
+            ParseError::Universe => "universe",
+            ParseError::Kind => "account type",
+            ParseError::Instance => "instance",
+            ParseError::Id => "account number",
+        };
+
+        write!(fmt, "invalid steam id {component}")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// `STEAM_X:Y:Z` display of a [`SteamId`], see [`SteamId::steam2`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Steam2(SteamId);
+
+/// `[U:1:N]` display of a [`SteamId`], see [`SteamId::steam3`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Steam3(SteamId);
+
+impl SteamId {
+    /// combine the components of a Steam ID
+    #[inline]
+    pub const fn from_parts(universe: Universe, kind: Kind, instance: Instance, id: u32) -> Self {
+        Self(
+            (id as u64) << bits::ID_SHIFT
+                | instance.to_u64() << bits::INSTANCE_SHIFT
+                | kind.to_u64() << bits::KIND_SHIFT
+                | universe.to_u64() << bits::UNIVERSE_SHIFT,
+        )
+    }
+
+    /// an individual on the public universe, such as from `[U:1:N]`
+    #[inline]
+    pub const fn from_account_id(id: u32) -> Self {
+        Self::from_parts(Universe::Public, Kind::Individual, Instance::Desktop, id)
+    }
+
+    #[inline]
+    pub const fn to_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// display as `STEAM_X:Y:Z`
+    #[inline]
+    pub const fn steam2(&self) -> Steam2 {
+        Steam2(*self)
+    }
+
+    /// display as `[U:1:N]`
+    #[inline]
+    pub const fn steam3(&self) -> Steam3 {
+        Steam3(*self)
+    }
+
+    /// link to the community profile
+    #[inline]
+    pub fn profile_url(&self) -> String {
+        format!("{PROFILE_URL}{}", self.0)
+    }
+
+    /// parse `STEAM_X:Y:Z`, where `X` may be zero for the public universe
+    pub fn parse_steam2(string: &str) -> Result<SteamId, ParseError> {
+        let string = string.strip_prefix("STEAM_").ok_or(ParseError::Format)?;
+        let mut parts = string.split(':');
+
+        let universe = parse_part::<u64>(parts.next(), ParseError::Universe)?;
+        let low = parse_part::<u32>(parts.next(), ParseError::Id)?;
+        let high = parse_part::<u32>(parts.next(), ParseError::Id)?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::Format);
+        }
+
+        // older games show the public universe as zero
+        let universe = match universe {
+            0 => Universe::Public,
+            universe => Universe::from_u64(universe).ok_or(ParseError::Universe)?,
+        };
+
+        if low > 1 || high > u32::MAX >> 1 {
+            return Err(ParseError::Id);
+        }
+
+        Ok(Self::from_parts(
+            universe,
+            Kind::Individual,
+            Instance::Desktop,
+            high << 1 | low,
+        ))
+    }
+
+    /// parse `[L:U:N]` or `[L:U:N:I]`, `L` being the account type letter
+    pub fn parse_steam3(string: &str) -> Result<SteamId, ParseError> {
+        let string = string
+            .strip_prefix('[')
+            .and_then(|string| string.strip_suffix(']'))
+            .ok_or(ParseError::Format)?;
+
+        let mut parts = string.split(':');
+        let mut letter = parts.next().ok_or(ParseError::Format)?.chars();
+        let kind = match (letter.next(), letter.next()) {
+            (Some(letter), None) if letter != '?' => KIND_LETTERS
+                .iter()
+                .position(|kind_letter| *kind_letter == letter)
+                .and_then(|kind| Kind::from_u64(kind as u64))
+                .ok_or(ParseError::Kind)?,
+            _ => return Err(ParseError::Kind),
+        };
+
+        let universe = parse_part::<u64>(parts.next(), ParseError::Universe)?;
+        let universe = Universe::from_u64(universe).ok_or(ParseError::Universe)?;
+        let id = parse_part::<u32>(parts.next(), ParseError::Id)?;
+
+        let instance = match parts.next() {
+            Some(instance) => {
+                let instance = parse_part::<u64>(Some(instance), ParseError::Instance)?;
+
+                Instance::from_u64(instance).ok_or(ParseError::Instance)?
+            }
+            None if kind == Kind::Individual => Instance::Desktop,
+            None => Instance::All,
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::Format);
+        }
+
+        Ok(Self::from_parts(universe, kind, instance, id))
+    }
+
+    /// parse a 64-bit decimal Steam ID
+    pub fn parse_steam64(string: &str) -> Result<SteamId, ParseError> {
+        let id = parse_part::<u64>(Some(string), ParseError::Format)?;
+
+        if Universe::from_u64(super::universe(id)).is_none() {
+            return Err(ParseError::Universe);
+        }
+
+        if Kind::from_u64(super::kind(id)).is_none() {
+            return Err(ParseError::Kind);
+        }
+
+        if Instance::from_u64(super::instance(id)).is_none() {
+            return Err(ParseError::Instance);
+        }
+
+        Ok(Self(id))
+    }
+}
+
+/// parse a decimal component, rejecting signs and empty strings
+fn parse_part<T: FromStr>(part: Option<&str>, error: ParseError) -> Result<T, ParseError> {
+    match part {
+        Some(part) if !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit()) => {
+            part.parse().map_err(|_| error)
+        }
+        _ => Err(error),
+    }
+}
+
+/// the 64-bit decimal form
+impl fmt::Display for SteamId {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+/// any of `STEAM_X:Y:Z`, `[U:1:N]` or a 64-bit decimal
+impl FromStr for SteamId {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        if string.starts_with("STEAM_") {
+            SteamId::parse_steam2(string)
+        } else if string.starts_with('[') {
+            SteamId::parse_steam3(string)
+        } else {
+            SteamId::parse_steam64(string)
+        }
+    }
+}
+
+impl fmt::Display for Steam2 {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = self.0.id();
+
+        write!(
+            fmt,
+            "STEAM_{}:{}:{}",
+            self.0.universe().to_u64(),
+            id & 1,
+            id >> 1
+        )
+    }
+}
+
+impl fmt::Display for Steam3 {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = self.0;
+        let letter = KIND_LETTERS[id.kind().to_u64() as usize];
+
+        write!(fmt, "[{letter}:{}:{}", id.universe().to_u64(), id.id())?;
+
+        if id.kind() == Kind::Individual && id.instance() != Instance::Desktop {
+            write!(fmt, ":{}", id.instance().to_u64())?;
+        }
+
+        fmt.write_str("]")
+    }
+}
@@ -21,7 +21,7 @@ impl Instance {
             0 => Some(Instance::All),
             1 => Some(Instance::Desktop),
             2 => Some(Instance::Console),
-            3 => Some(Instance::Web),
+            4 => Some(Instance::Web),
             _ => None,
         }
     }
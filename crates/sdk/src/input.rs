@@ -2,26 +2,21 @@
 
 use crate::Pad;
 use cake::ffi::vtable;
+use core::ptr;
 use elysium_math::Vec3;
 
 pub use button::Button;
+pub use buttons::Buttons;
 pub use joystick::Joystick;
 pub use mouse::Mouse;
-pub use state::State;
 
 mod button;
+mod buttons;
+mod delta;
 mod joystick;
 mod mouse;
-mod state;
 
-pub const IN_ATTACK: i32 = 1 << 0;
-pub const IN_JUMP: i32 = 1 << 1;
-pub const IN_DUCK: i32 = 1 << 2;
-pub const IN_BULLRUSH: i32 = 1 << 22;
-pub const IN_LEFT: i32 = 1 << 9;
-pub const IN_RIGHT: i32 = 1 << 10;
-
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[repr(C)]
 pub struct Command {
     pub vtable: *const (),
@@ -43,56 +38,42 @@ pub struct Command {
 }
 
 impl Command {
-    const fn has(&self, flag: i32) -> bool {
-        (self.state & flag) != 0
-    }
-
-    pub const fn set(&mut self, flag: i32, value: bool) {
-        if value {
-            self.state |= flag;
-        } else {
-            self.state &= !flag;
+    /// a zeroed command, like `CUserCmd::Reset`
+    pub const fn new() -> Command {
+        Command {
+            vtable: ptr::null(),
+            command: 0,
+            tick_count: 0,
+            view_angle: Vec3::zero(),
+            aim_direction: Vec3::zero(),
+            movement: Vec3::zero(),
+            state: 0,
+            impulse: 0,
+            weapon_select: 0,
+            weapon_subtype: 0,
+            random_seed: 0,
+            mouse_dx: 0,
+            mouse_dy: 0,
+            has_been_predicted: false,
+            head_angles: Vec3::zero(),
+            head_offset: Vec3::zero(),
         }
     }
 
-    pub const fn in_left(&self) -> bool {
-        self.has(IN_LEFT)
-    }
-
-    pub const fn in_right(&self) -> bool {
-        self.has(IN_RIGHT)
+    /// buttons held, see [`Buttons`]
+    pub const fn buttons(&self) -> Buttons {
+        Buttons(self.state)
     }
 
-    pub const fn in_attack(&self) -> bool {
-        self.has(IN_ATTACK)
-    }
-
-    pub const fn in_jump(&self) -> bool {
-        self.has(IN_JUMP)
-    }
-
-    pub const fn in_duck(&self) -> bool {
-        self.has(IN_DUCK)
-    }
-
-    pub const fn in_fast_duck(&self) -> bool {
-        self.has(IN_BULLRUSH)
-    }
-
-    pub const fn attack(&mut self, value: bool) {
-        self.set(IN_ATTACK, value)
-    }
-
-    pub const fn jump(&mut self, value: bool) {
-        self.set(IN_JUMP, value)
-    }
-
-    pub const fn duck(&mut self, value: bool) {
-        self.set(IN_DUCK, value)
+    pub const fn set_buttons(&mut self, buttons: Buttons) {
+        self.state = buttons.0;
     }
+}
 
-    pub const fn fast_duck(&mut self, value: bool) {
-        self.set(IN_BULLRUSH, value);
+impl Default for Command {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -0,0 +1,309 @@
+use core::ops;
+
+/// buttons held during a command (`IN_*`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(transparent)]
+pub struct Buttons(pub i32);
+
+impl Buttons {
+    pub const NONE: Self = Self::new(0);
+
+    pub const ATTACK: Self = Self::new(1 << 0);
+    pub const JUMP: Self = Self::new(1 << 1);
+    pub const CROUCH: Self = Self::new(1 << 2);
+    pub const FORWARD: Self = Self::new(1 << 3);
+    pub const BACKWARD: Self = Self::new(1 << 4);
+    pub const USE: Self = Self::new(1 << 5);
+    pub const CANCEL: Self = Self::new(1 << 6);
+    /// turn left (`+left`), not strafe
+    pub const LEFT: Self = Self::new(1 << 7);
+    /// turn right (`+right`), not strafe
+    pub const RIGHT: Self = Self::new(1 << 8);
+    pub const MOVE_LEFT: Self = Self::new(1 << 9);
+    pub const MOVE_RIGHT: Self = Self::new(1 << 10);
+    pub const ATTACK2: Self = Self::new(1 << 11);
+    pub const RUN: Self = Self::new(1 << 12);
+    pub const RELOAD: Self = Self::new(1 << 13);
+    pub const ALT1: Self = Self::new(1 << 14);
+    pub const ALT2: Self = Self::new(1 << 15);
+    pub const SCORE: Self = Self::new(1 << 16);
+    pub const SPEED: Self = Self::new(1 << 17);
+    pub const WALK: Self = Self::new(1 << 18);
+    pub const ZOOM: Self = Self::new(1 << 19);
+    pub const WEAPON1: Self = Self::new(1 << 20);
+    pub const WEAPON2: Self = Self::new(1 << 21);
+    /// fast duck
+    pub const BULLRUSH: Self = Self::new(1 << 22);
+    pub const GRENADE1: Self = Self::new(1 << 23);
+    pub const GRENADE2: Self = Self::new(1 << 24);
+    /// inspect, csgo reuses `IN_ATTACK3` for this
+    pub const LOOKSPIN: Self = Self::new(1 << 25);
+
+    pub const ANY_ATTACK: Self = Self::ATTACK | Self::ATTACK2;
+    pub const ANY_MOVE: Self = Self::FORWARD | Self::BACKWARD | Self::MOVE_LEFT | Self::MOVE_RIGHT;
+
+    const fn new(buttons: i32) -> Self {
+        Self(buttons)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// if every button of `other` is held
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// if any button of `other` is held
+    pub const fn intersects(&self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub const fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub const fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    pub const fn toggle(&mut self, other: Self) {
+        self.0 ^= other.0;
+    }
+
+    /// insert or remove `other`
+    pub const fn set(&mut self, other: Self, value: bool) {
+        if value {
+            self.insert(other);
+        } else {
+            self.remove(other);
+        }
+    }
+
+    pub const fn in_attack(&self) -> bool {
+        !(*self & Self::ATTACK).is_empty()
+    }
+
+    pub const fn in_attack2(&self) -> bool {
+        !(*self & Self::ATTACK2).is_empty()
+    }
+
+    pub const fn in_any_attack(&self) -> bool {
+        !(*self & Self::ANY_ATTACK).is_empty()
+    }
+
+    pub const fn in_jump(&self) -> bool {
+        self.0 & Self::JUMP.0 != 0
+    }
+
+    pub const fn in_crouch(&self) -> bool {
+        self.0 & Self::CROUCH.0 != 0
+    }
+
+    pub const fn in_forward(&self) -> bool {
+        self.0 & Self::FORWARD.0 != 0
+    }
+
+    pub const fn in_backward(&self) -> bool {
+        self.0 & Self::BACKWARD.0 != 0
+    }
+
+    pub const fn in_use(&self) -> bool {
+        self.0 & Self::USE.0 != 0
+    }
+
+    pub const fn in_cancel(&self) -> bool {
+        self.0 & Self::CANCEL.0 != 0
+    }
+
+    pub const fn in_left(&self) -> bool {
+        self.0 & Self::LEFT.0 != 0
+    }
+
+    pub const fn in_right(&self) -> bool {
+        self.0 & Self::RIGHT.0 != 0
+    }
+
+    pub const fn in_move_right(&self) -> bool {
+        self.0 & Self::MOVE_RIGHT.0 != 0
+    }
+
+    pub const fn in_move_left(&self) -> bool {
+        self.0 & Self::MOVE_LEFT.0 != 0
+    }
+
+    pub const fn in_run(&self) -> bool {
+        self.0 & Self::RUN.0 != 0
+    }
+
+    pub const fn in_reload(&self) -> bool {
+        self.0 & Self::RELOAD.0 != 0
+    }
+
+    pub const fn in_alt1(&self) -> bool {
+        self.0 & Self::ALT1.0 != 0
+    }
+
+    pub const fn in_alt2(&self) -> bool {
+        self.0 & Self::ALT2.0 != 0
+    }
+
+    pub const fn in_score(&self) -> bool {
+        self.0 & Self::SCORE.0 != 0
+    }
+
+    pub const fn in_speed(&self) -> bool {
+        self.0 & Self::SPEED.0 != 0
+    }
+
+    pub const fn in_walk(&self) -> bool {
+        self.0 & Self::WALK.0 != 0
+    }
+
+    pub const fn in_zoom(&self) -> bool {
+        self.0 & Self::ZOOM.0 != 0
+    }
+
+    pub const fn in_weapon1(&self) -> bool {
+        self.0 & Self::WEAPON1.0 != 0
+    }
+
+    pub const fn in_weapon2(&self) -> bool {
+        self.0 & Self::WEAPON2.0 != 0
+    }
+
+    pub const fn in_bullrush(&self) -> bool {
+        self.0 & Self::BULLRUSH.0 != 0
+    }
+
+    pub const fn in_grenade1(&self) -> bool {
+        (*self & Self::GRENADE1).0 != 0
+    }
+
+    pub const fn in_grenade2(&self) -> bool {
+        (*self & Self::GRENADE2).0 != 0
+    }
+
+    pub const fn in_lookspin(&self) -> bool {
+        self.0 & Self::LOOKSPIN.0 != 0
+    }
+}
+
+impl const ops::BitAnd for Buttons {
+    type Output = Buttons;
+
+    fn bitand(self, rhs: Buttons) -> Buttons {
+        Buttons(self.0 & rhs.0)
+    }
+}
+
+impl const ops::BitAndAssign for Buttons {
+    fn bitand_assign(&mut self, rhs: Buttons) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl const ops::BitOr for Buttons {
+    type Output = Buttons;
+
+    fn bitor(self, rhs: Buttons) -> Buttons {
+        Buttons(self.0 | rhs.0)
+    }
+}
+
+impl const ops::BitOrAssign for Buttons {
+    fn bitor_assign(&mut self, rhs: Buttons) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl const ops::BitXor for Buttons {
+    type Output = Buttons;
+
+    fn bitxor(self, rhs: Buttons) -> Buttons {
+        Buttons(self.0 ^ rhs.0)
+    }
+}
+
+impl const ops::BitXorAssign for Buttons {
+    fn bitxor_assign(&mut self, rhs: Buttons) {
+        self.0 ^= rhs.0;
+    }
+}
+
+impl const ops::Not for Buttons {
+    type Output = Buttons;
+
+    fn not(self) -> Buttons {
+        Self(!self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits() {
+        let buttons = [
+            (Buttons::ATTACK, 1 << 0),
+            (Buttons::JUMP, 1 << 1),
+            (Buttons::CROUCH, 1 << 2),
+            (Buttons::FORWARD, 1 << 3),
+            (Buttons::BACKWARD, 1 << 4),
+            (Buttons::USE, 1 << 5),
+            (Buttons::CANCEL, 1 << 6),
+            (Buttons::LEFT, 1 << 7),
+            (Buttons::RIGHT, 1 << 8),
+            (Buttons::MOVE_LEFT, 1 << 9),
+            (Buttons::MOVE_RIGHT, 1 << 10),
+            (Buttons::ATTACK2, 1 << 11),
+            (Buttons::RUN, 1 << 12),
+            (Buttons::RELOAD, 1 << 13),
+            (Buttons::ALT1, 1 << 14),
+            (Buttons::ALT2, 1 << 15),
+            (Buttons::SCORE, 1 << 16),
+            (Buttons::SPEED, 1 << 17),
+            (Buttons::WALK, 1 << 18),
+            (Buttons::ZOOM, 1 << 19),
+            (Buttons::WEAPON1, 1 << 20),
+            (Buttons::WEAPON2, 1 << 21),
+            (Buttons::BULLRUSH, 1 << 22),
+            (Buttons::GRENADE1, 1 << 23),
+            (Buttons::GRENADE2, 1 << 24),
+            (Buttons::LOOKSPIN, 1 << 25),
+        ];
+
+        for (button, bit) in buttons {
+            assert_eq!(button.0, bit, "{button:?}");
+        }
+
+        let all = buttons
+            .iter()
+            .fold(Buttons::NONE, |all, (button, _bit)| all | *button);
+
+        assert_eq!(all.0, (1 << 26) - 1);
+    }
+
+    #[test]
+    fn set() {
+        let mut buttons = Buttons::NONE;
+
+        buttons.insert(Buttons::ATTACK | Buttons::JUMP);
+        assert!(buttons.in_attack() && buttons.in_jump());
+        assert!(buttons.contains(Buttons::ATTACK));
+        assert!(!buttons.contains(Buttons::ANY_ATTACK));
+        assert!(buttons.intersects(Buttons::ANY_ATTACK));
+
+        buttons.set(Buttons::JUMP, false);
+        assert_eq!(buttons, Buttons::ATTACK);
+
+        buttons.toggle(Buttons::ATTACK | Buttons::LOOKSPIN);
+        assert_eq!(buttons, Buttons::LOOKSPIN);
+        assert!(buttons.in_lookspin());
+
+        buttons.remove(Buttons::LOOKSPIN);
+        assert!(buttons.is_empty());
+    }
+}
@@ -0,0 +1,308 @@
+//! Usercmd delta encoding, like `ReadUsercmd` and `WriteUsercmd`.
+
+use super::Command;
+use crate::bf::{Overflow, Reader, Writer};
+use crate::crc32::Crc32;
+use crate::md5;
+
+/// `MAX_EDICT_BITS`
+const WEAPON_SELECT_BITS: u32 = 11;
+/// `WEAPON_SUBTYPE_BITS`
+const WEAPON_SUBTYPE_BITS: u32 = 6;
+
+impl Command {
+    /// read a command delta encoded against `from` (`ReadUsercmd`)
+    ///
+    /// fields without a change bit are copied from `from`, the command number and tick
+    /// count are incremented instead.
+    pub fn read_delta(reader: &mut Reader<'_>, from: &Command) -> Result<Command, Overflow> {
+        let mut command = *from;
+
+        command.command = match read_changed(reader, Reader::read_i32)? {
+            Some(command) => command,
+            None => from.command.wrapping_add(1),
+        };
+
+        command.tick_count = match read_changed(reader, Reader::read_i32)? {
+            Some(tick_count) => tick_count,
+            None => from.tick_count.wrapping_add(1),
+        };
+
+        for value in [
+            &mut command.view_angle.x,
+            &mut command.view_angle.y,
+            &mut command.view_angle.z,
+            &mut command.aim_direction.x,
+            &mut command.aim_direction.y,
+            &mut command.aim_direction.z,
+            &mut command.movement.x,
+            &mut command.movement.y,
+            &mut command.movement.z,
+        ] {
+            if let Some(new) = read_changed(reader, Reader::read_f32)? {
+                *value = new;
+            }
+        }
+
+        if let Some(state) = read_changed(reader, Reader::read_i32)? {
+            command.state = state;
+        }
+
+        if let Some(impulse) = read_changed(reader, Reader::read_u8)? {
+            command.impulse = impulse;
+        }
+
+        if reader.read_bit()? {
+            command.weapon_select = reader.read_ubits(WEAPON_SELECT_BITS)? as i32;
+
+            if reader.read_bit()? {
+                command.weapon_subtype = reader.read_ubits(WEAPON_SUBTYPE_BITS)? as i32;
+            }
+        }
+
+        command.random_seed = (md5::pseudo_random(command.command as u32) & 0x7FFF_FFFF) as i32;
+
+        if let Some(mouse_dx) = read_changed(reader, Reader::read_i16)? {
+            command.mouse_dx = mouse_dx;
+        }
+
+        if let Some(mouse_dy) = read_changed(reader, Reader::read_i16)? {
+            command.mouse_dy = mouse_dy;
+        }
+
+        for value in [
+            &mut command.head_angles.x,
+            &mut command.head_angles.y,
+            &mut command.head_angles.z,
+            &mut command.head_offset.x,
+            &mut command.head_offset.y,
+            &mut command.head_offset.z,
+        ] {
+            if let Some(new) = read_changed(reader, Reader::read_f32)? {
+                *value = new;
+            }
+        }
+
+        Ok(command)
+    }
+
+    /// write this command delta encoded against `from` (`WriteUsercmd`)
+    ///
+    /// the weapon is written whenever one is selected, regardless of `from`.
+    pub fn write_delta(&self, writer: &mut Writer, from: &Command) {
+        let command = (self.command != from.command.wrapping_add(1)).then_some(self.command);
+        let tick_count =
+            (self.tick_count != from.tick_count.wrapping_add(1)).then_some(self.tick_count);
+
+        write_changed(writer, command, Writer::write_i32);
+        write_changed(writer, tick_count, Writer::write_i32);
+
+        for (to, from) in [
+            (self.view_angle.x, from.view_angle.x),
+            (self.view_angle.y, from.view_angle.y),
+            (self.view_angle.z, from.view_angle.z),
+            (self.aim_direction.x, from.aim_direction.x),
+            (self.aim_direction.y, from.aim_direction.y),
+            (self.aim_direction.z, from.aim_direction.z),
+            (self.movement.x, from.movement.x),
+            (self.movement.y, from.movement.y),
+            (self.movement.z, from.movement.z),
+        ] {
+            write_changed(writer, (to != from).then_some(to), Writer::write_f32);
+        }
+
+        write_changed(
+            writer,
+            (self.state != from.state).then_some(self.state),
+            Writer::write_i32,
+        );
+
+        write_changed(
+            writer,
+            (self.impulse != from.impulse).then_some(self.impulse),
+            Writer::write_u8,
+        );
+
+        writer.write_bit(self.weapon_select != 0);
+
+        if self.weapon_select != 0 {
+            writer.write_ubits(self.weapon_select as u32, WEAPON_SELECT_BITS);
+            writer.write_bit(self.weapon_subtype != 0);
+
+            if self.weapon_subtype != 0 {
+                writer.write_ubits(self.weapon_subtype as u32, WEAPON_SUBTYPE_BITS);
+            }
+        }
+
+        write_changed(
+            writer,
+            (self.mouse_dx != from.mouse_dx).then_some(self.mouse_dx),
+            Writer::write_i16,
+        );
+
+        write_changed(
+            writer,
+            (self.mouse_dy != from.mouse_dy).then_some(self.mouse_dy),
+            Writer::write_i16,
+        );
+
+        for (to, from) in [
+            (self.head_angles.x, from.head_angles.x),
+            (self.head_angles.y, from.head_angles.y),
+            (self.head_angles.z, from.head_angles.z),
+            (self.head_offset.x, from.head_offset.x),
+            (self.head_offset.y, from.head_offset.y),
+            (self.head_offset.z, from.head_offset.z),
+        ] {
+            write_changed(writer, (to != from).then_some(to), Writer::write_f32);
+        }
+    }
+
+    /// `CUserCmd::GetChecksum`, a CRC-32 of every networked field
+    pub fn checksum(&self) -> u32 {
+        let mut crc = Crc32::new();
+
+        crc.update(&self.command.to_le_bytes());
+        crc.update(&self.tick_count.to_le_bytes());
+
+        for vector in [self.view_angle, self.aim_direction, self.movement] {
+            for value in [vector.x, vector.y, vector.z] {
+                crc.update(&value.to_le_bytes());
+            }
+        }
+
+        crc.update(&self.state.to_le_bytes());
+        crc.update(&[self.impulse]);
+        crc.update(&self.weapon_select.to_le_bytes());
+        crc.update(&self.weapon_subtype.to_le_bytes());
+        crc.update(&self.random_seed.to_le_bytes());
+        crc.update(&self.mouse_dx.to_le_bytes());
+        crc.update(&self.mouse_dy.to_le_bytes());
+
+        for vector in [self.head_angles, self.head_offset] {
+            for value in [vector.x, vector.y, vector.z] {
+                crc.update(&value.to_le_bytes());
+            }
+        }
+
+        crc.finish()
+    }
+}
+
+/// read a change bit, then the value if it is set
+#[inline]
+fn read_changed<'a, T>(
+    reader: &mut Reader<'a>,
+    read: fn(&mut Reader<'a>) -> Result<T, Overflow>,
+) -> Result<Option<T>, Overflow> {
+    if reader.read_bit()? {
+        read(reader).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// write a change bit, then the value if there is one
+#[inline]
+fn write_changed<T>(writer: &mut Writer, value: Option<T>, write: fn(&mut Writer, T)) {
+    writer.write_bit(value.is_some());
+
+    if let Some(value) = value {
+        write(writer, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged() {
+        let mut from = Command::new();
+
+        from.command = 5;
+        from.tick_count = 64;
+        from.state = 1;
+
+        // 22 change bits, all unset
+        let command = Command::read_delta(&mut Reader::new(&[0, 0, 0]), &from).unwrap();
+
+        assert_eq!(command.command, 6);
+        assert_eq!(command.tick_count, 65);
+        assert_eq!(command.state, 1);
+        assert_eq!(
+            command.random_seed,
+            (md5::pseudo_random(6) & 0x7FFF_FFFF) as i32
+        );
+
+        assert_eq!(
+            Command::read_delta(&mut Reader::new(&[0, 0]), &from).unwrap_err(),
+            Overflow
+        );
+    }
+
+    #[test]
+    fn write_from_zero() {
+        let mut command = Command::new();
+        let mut writer = Writer::new();
+
+        command.command = 5;
+        command.write_delta(&mut writer, &Command::new());
+
+        // both numbers are written, as neither follows on from zero
+        assert_eq!(writer.position(), 86);
+        assert_eq!(
+            writer.as_bytes(),
+            [0x0B, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+        );
+    }
+
+    fn changed() -> (Command, Command) {
+        let mut from = Command::new();
+
+        from.command = 10;
+        from.tick_count = 100;
+
+        let mut to = from;
+
+        to.command = 11;
+        to.tick_count = 101;
+        to.view_angle.y = 90.0;
+        to.movement.x = 450.0;
+        to.state = 1 | 2;
+        to.weapon_select = 7;
+        to.mouse_dx = -3;
+        to.random_seed = (md5::pseudo_random(11) & 0x7FFF_FFFF) as i32;
+
+        (from, to)
+    }
+
+    #[test]
+    fn write_changes() {
+        let (from, to) = changed();
+        let mut writer = Writer::new();
+
+        to.write_delta(&mut writer, &from);
+
+        assert_eq!(writer.position(), 146);
+        assert_eq!(
+            writer.as_bytes(),
+            [
+                0x08, 0x00, 0x40, 0x2B, 0x04, 0x01, 0x00, 0xC2, 0x87, 0x38, 0x00, 0x00, 0x00, 0xE0,
+                0x01, 0xEC, 0xFF, 0x07, 0x00
+            ]
+        );
+
+        let read = Command::read_delta(&mut Reader::new(writer.as_bytes()), &from).unwrap();
+
+        assert_eq!(read, to);
+    }
+
+    #[test]
+    fn checksum() {
+        let (_from, to) = changed();
+
+        assert_eq!(to.checksum(), 0x30E7_94D0);
+        assert_eq!(Command::new().checksum(), Command::default().checksum());
+    }
+}
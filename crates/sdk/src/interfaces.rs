@@ -129,6 +129,7 @@ libraries! {
     Server => "./bin/linux64/serverbrowser_client.so",
     Surface => "./bin/linux64/vguimatsurface_client.so",
     Tier0 => "./bin/linux64/libtier0_client.so",
+    VStdLib => "./bin/linux64/libvstdlib_client.so",
     VGui => "./bin/linux64/vgui2_client.so"
 }
 
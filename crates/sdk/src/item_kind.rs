@@ -1,7 +1,26 @@
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-#[non_exhaustive]
-#[repr(i32)]
-pub enum ItemKind {
+macro_rules! item_kinds {
+    ($($kind:ident $(= $raw:literal)?,)*) => {
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[non_exhaustive]
+        #[repr(i32)]
+        pub enum ItemKind {
+            $($kind $(= $raw)?,)*
+        }
+
+        impl ItemKind {
+            /// every item kind, for mapping from a raw item definition index
+            const ALL: &'static [ItemKind] = &[$(ItemKind::$kind,)*];
+
+            /// map an item definition index (`m_iItemDefinitionIndex`) to a known item kind
+            #[inline]
+            pub fn from_raw(raw: i32) -> Option<ItemKind> {
+                Self::ALL.iter().copied().find(|kind| *kind as i32 == raw)
+            }
+        }
+    };
+}
+
+item_kinds! {
     Invalid = -1,
     None = 0,
     Deagle,
@@ -96,6 +115,103 @@ pub enum ItemKind {
 }
 
 impl ItemKind {
+    /// map a weapon's class name, with or without the `weapon_` prefix, to an item kind
+    ///
+    /// game events name the weapon this way, and fire is `inferno`
+    pub fn from_name(name: &str) -> Option<ItemKind> {
+        let name = name.strip_prefix("weapon_").unwrap_or(name);
+        let kind = match name {
+            "deagle" => ItemKind::Deagle,
+            "elite" => ItemKind::DualBerettas,
+            "fiveseven" => ItemKind::FiveSeven,
+            "glock" => ItemKind::Glock,
+            "ak47" => ItemKind::AK47,
+            "aug" => ItemKind::AUG,
+            "awp" => ItemKind::Awp,
+            "famas" => ItemKind::Famas,
+            "g3sg1" => ItemKind::G3SG1,
+            "galilar" => ItemKind::GalilAr,
+            "m249" => ItemKind::M249,
+            "m4a1" => ItemKind::M4A4,
+            "mac10" => ItemKind::MAC10,
+            "p90" => ItemKind::P90,
+            "mp5sd" => ItemKind::MP5,
+            "ump45" => ItemKind::UMP45,
+            "xm1014" => ItemKind::XM1014,
+            "bizon" => ItemKind::PPBizon,
+            "mag7" => ItemKind::MAG7,
+            "negev" => ItemKind::Negev,
+            "sawedoff" => ItemKind::SawedOff,
+            "tec9" => ItemKind::Tec9,
+            "taser" => ItemKind::ZeusX27,
+            "hkp2000" => ItemKind::P2000,
+            "mp7" => ItemKind::MP7,
+            "mp9" => ItemKind::MP9,
+            "nova" => ItemKind::Nova,
+            "p250" => ItemKind::P250,
+            "shield" => ItemKind::Shield,
+            "scar20" => ItemKind::SCAR20,
+            "sg556" => ItemKind::SG556,
+            "ssg08" => ItemKind::SSG08,
+            "knifegg" => ItemKind::KnifeGG,
+            "knife" => ItemKind::Knife,
+            "flashbang" => ItemKind::Flashbang,
+            "hegrenade" => ItemKind::Grenade,
+            "smokegrenade" => ItemKind::Smoke,
+            "molotov" | "inferno" => ItemKind::Molotov,
+            "decoy" => ItemKind::Decoy,
+            "incgrenade" => ItemKind::Incendiary,
+            "c4" | "planted_c4" => ItemKind::C4,
+            "healthshot" => ItemKind::Healthshoot,
+            "knife_t" => ItemKind::KnifeT,
+            "m4a1_silencer" => ItemKind::M4A1S,
+            "usp_silencer" => ItemKind::USP,
+            "cz75a" => ItemKind::CZ75Auto,
+            "revolver" => ItemKind::R8Revolver,
+            "tagrenade" => ItemKind::TaGrenade,
+            "fists" => ItemKind::Fists,
+            "breachcharge" => ItemKind::BreachCharge,
+            "tablet" => ItemKind::Tablet,
+            "melee" => ItemKind::Melee,
+            "axe" => ItemKind::Axe,
+            "hammer" => ItemKind::Hammer,
+            "spanner" => ItemKind::Spanner,
+            "knife_ghost" => ItemKind::KnifeGhost,
+            "firebomb" => ItemKind::FireBomb,
+            "diversion" => ItemKind::Diversion,
+            "frag_grenade" => ItemKind::FragGrenade,
+            "snowball" => ItemKind::Snowball,
+            "bumpmine" => ItemKind::BumpMine,
+            "bayonet" => ItemKind::Bayonet,
+            "knife_css" => ItemKind::ClassicKnife,
+            "knife_flip" => ItemKind::FlipKnife,
+            "knife_gut" => ItemKind::GutKnife,
+            "knife_karambit" => ItemKind::Karambit,
+            "knife_m9_bayonet" => ItemKind::M9Bayonet,
+            "knife_tactical" => ItemKind::TacticalKnife,
+            "knife_falchion" => ItemKind::FalchionKnife,
+            "knife_survival_bowie" => ItemKind::BowieKnife,
+            "knife_butterfly" => ItemKind::ButterflyKnife,
+            "knife_push" => ItemKind::ShadowDaggers,
+            "knife_cord" => ItemKind::CordKnife,
+            "knife_canis" => ItemKind::CanisKnife,
+            "knife_ursus" => ItemKind::UrsusKnife,
+            "knife_gypsy_jackknife" => ItemKind::NavjaKnife,
+            "knife_outdoor" => ItemKind::KnifeOutdoor,
+            "knife_stiletto" => ItemKind::StilettoKnife,
+            "knife_widowmaker" => ItemKind::TalonKnife,
+            "knife_skeleton" => ItemKind::SkeletonKnife,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+
+    #[inline]
+    pub const fn to_raw(self) -> i32 {
+        self as i32
+    }
+
     pub const fn is_pistol(&self) -> bool {
         matches!(
             self,
@@ -206,3 +322,28 @@ impl ItemKind {
         Some(label)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_raw() {
+        for kind in ItemKind::ALL {
+            assert_eq!(ItemKind::from_raw(kind.to_raw()), Some(*kind));
+        }
+
+        assert_eq!(ItemKind::from_raw(7), Some(ItemKind::AK47));
+        assert_eq!(ItemKind::from_raw(6), None);
+    }
+
+    #[test]
+    fn from_name() {
+        assert_eq!(ItemKind::from_name("ak47"), Some(ItemKind::AK47));
+        assert_eq!(ItemKind::from_name("weapon_ak47"), Some(ItemKind::AK47));
+        assert_eq!(ItemKind::from_name("hegrenade"), Some(ItemKind::Grenade));
+        assert_eq!(ItemKind::from_name("inferno"), Some(ItemKind::Molotov));
+        assert_eq!(ItemKind::from_name("world"), None);
+        assert_eq!(ItemKind::from_name(""), None);
+    }
+}
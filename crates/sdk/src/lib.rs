@@ -30,18 +30,21 @@ pub use convar::Vars;
 pub use engine::{Engine, PlayerInfo};
 pub use entity::EntityList;
 pub use entry::Entry;
+pub use event::Events;
 pub use frame::Frame;
 pub use globals::Globals;
 pub use hit_group::HitGroup;
 pub use id::SteamId;
-pub use input::{Command, Input};
+pub use input::{Buttons, Command, Input};
 pub use input_system::InputSystem;
 pub use interfaces::{InterfaceKind, Interfaces, LibraryKind};
 pub use item_kind::ItemKind;
 pub use material::MaterialSystem;
+pub use movement::Movement;
 pub use network::{Flow, NetworkChannel};
 pub use pad::Pad;
 pub use panorama::{PanoramaEventRegistration, PanoramaUIEngine, UIEngine, UIPanel};
+pub use prediction::{MoveData, MoveHelper, Prediction};
 pub use render::{OverrideKind, Render};
 pub use sound::{ActiveChannels, Channel};
 pub use steam::SteamAPIContext;
@@ -51,7 +54,7 @@ pub use utl_mem::UtlMem;
 pub use utl_string::UtlString;
 pub use utl_vec::UtlVec;
 pub use var::{VarEntry, VarMap};
-pub use vdf::{Vdf, VdfValue};
+pub use vdf::{Children, DataKind, KeyValuesSystem, Vdf, VdfFromBytes, VdfValue};
 pub use view::View;
 pub use weapon::{WeaponInfo, WeaponKind};
 
@@ -70,9 +73,11 @@ mod input_system;
 mod interfaces;
 mod item_kind;
 mod macros;
+mod movement;
 mod pad;
 mod panorama;
 mod physics;
+mod prediction;
 mod render;
 mod sound;
 mod steam;
@@ -85,13 +90,18 @@ mod vdf;
 mod view;
 mod weapon;
 
+pub mod bf;
 pub mod client;
 pub mod convar;
+pub mod crc32;
+pub mod demo;
 pub mod entity;
+pub mod event;
 pub mod ffi;
 pub mod id;
 pub mod input;
 pub mod material;
+pub mod md5;
 pub mod model;
 pub mod network;
 pub mod player_model;
@@ -102,8 +112,6 @@ pub struct Debug;
 #[derive(Debug)]
 pub struct Effects;
 #[derive(Debug)]
-pub struct Events;
-#[derive(Debug)]
 pub struct Filesystem;
 #[derive(Debug)]
 pub struct InputInternal;
@@ -112,16 +120,12 @@ pub struct Kinds;
 #[derive(Debug)]
 pub struct Localize;
 #[derive(Debug)]
-pub struct Movement;
-#[derive(Debug)]
 pub struct Panel;
 #[derive(Debug)]
 pub struct Panorama;
 #[derive(Debug)]
 pub struct Physics;
 #[derive(Debug)]
-pub struct Prediction;
-#[derive(Debug)]
 pub struct Sound;
 #[derive(Debug)]
 pub struct Surface;
@@ -9,6 +9,9 @@ macro_rules! materials {
         }
 
         impl MaterialKind {
+            /// Every material.
+            pub const ALL: &'static [MaterialKind] = &[$(MaterialKind::$variant,)*];
+
             #[inline]
             const fn name_nul_str(&self) -> &'static str {
                 match self {
@@ -68,6 +71,12 @@ macro_rules! materials {
                 })
             }
 
+            /// Parse the VDF/KeyValues body, to check it before it reaches the engine.
+            #[inline]
+            pub fn parse_vdf(&self) -> Option<Result<elysium_vdf::Block, elysium_vdf::Error>> {
+                self.vdf().map(elysium_vdf::from_str)
+            }
+
             /// VDF/KeyValues pointer passed to the second argument of `KeyValues::fromString`.
             #[inline]
             pub const fn vdf_ptr(&self) -> *const u8 {
@@ -116,7 +125,7 @@ materials! {
         $basetexture dev/zone_warning
         $envmap editor/cube_vertigo
         $envmapcontrast 1
-        $envmaptint [.7 .7 .7]
+        $envmaptint \"[.7 .7 .7]\"
         proxies {
             texturescroll {
                 texturescollvar $basetexturetransform
@@ -128,12 +137,12 @@ materials! {
     Platinum => ("platinum\0", "VertexLitGeneric\0", Some("
         $basetexture models/player/ct_fbi/ct_fbi_glass
         $envmap env_cubemap
-        $envmaptint [.4 .6 .7]
+        $envmaptint \"[.4 .6 .7]\"
     \0")),
     Glass => ("glass\0", "VertexLitGeneric\0", Some("
         $additive 1
         $basetexture detail/dt_metal1
-        $color [.05 .05 .05]
+        $color \"[.05 .05 .05]\"
         $envmap editor/cube_vertigo
     \0")),
     Crystal => ("crystal\0", "VertexLitGeneric\0", Some("
@@ -144,35 +153,35 @@ materials! {
         $phong 1
         $phongboost 2
         $phongexponent 16
-        $phongtint [.2 .35 .6]
+        $phongtint \"[.2 .35 .6]\"
         $translucent 1
     \0")),
     Silver => ("silver\0", "VertexLitGeneric\0", Some("
         $basetexture white
         $bumpmap effects/flat_normal
-        $color2 [.05 .05 .05]
+        $color2 \"[.05 .05 .05]\"
         $envmap editor/cube_vertigo
         $envmapfresnel .6
-        $envtintmap [.2 .2 .2]
+        $envtintmap \"[.2 .2 .2]\"
         $phong 1
         $phongboost 2
         $phongexponent 8
-        $phongfresnelranges [.7 .8 1]
-        $phongtint [.8 .9 1]
+        $phongfresnelranges \"[.7 .8 1]\"
+        $phongtint \"[.8 .9 1]\"
     \0")),
     Gold => ("gold\0", "VertexLitGeneric\0", Some("
         $basetexture white
         $bumpmap effects/flat_normal
-        $color2 [.18 .15 .06]
+        $color2 \"[.18 .15 .06]\"
         $envmap editor/cube_vertigo
         $envmapfresnel .6
-        $envtintmap [.6 .5 .2]
+        $envtintmap \"[.6 .5 .2]\"
         $phong 1
         $phongboost 6
         $phongdisablehalflambert 1
         $phongexponent 128
-        $phongfresnelranges [.7 .8 1]
-        $phongtint [.6 .5 .2]
+        $phongfresnelranges \"[.7 .8 1]\"
+        $phongtint \"[.6 .5 .2]\"
     \0")),
     Plastic => ("plastic\0", "VertexLitGeneric\0", Some("
         $additive 1
@@ -185,7 +194,37 @@ materials! {
         $phongboost 20
         $phongdisablehalflambert 1
         $phongexponent 3000
-        $phongfesnelranges [.1 .4 1]
-        $phongtint [.8 .9 1]
+        $phongfesnelranges \"[.1 .4 1]\"
+        $phongtint \"[.8 .9 1]\"
     \0"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elysium_vdf::{Block, Value};
+
+    fn check(block: &Block) {
+        for pair in block.iter() {
+            // unquoted vectors are split on whitespace by the engine
+            assert!(pair
+                .key
+                .starts_with(|char: char| char.is_ascii_alphabetic() || char == '$'));
+
+            match &pair.value {
+                Value::String(value) if value.starts_with('[') => assert!(value.ends_with(']')),
+                Value::String(_) => {}
+                Value::Block(block) => check(block),
+            }
+        }
+    }
+
+    #[test]
+    fn vdf() {
+        for material in MaterialKind::ALL {
+            if let Some(vdf) = material.parse_vdf() {
+                check(&vdf.unwrap());
+            }
+        }
+    }
+}
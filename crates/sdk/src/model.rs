@@ -1,7 +1,9 @@
-use crate::Pad;
+use crate::{object_validate, Pad};
 use core::marker::PhantomData;
 use core::ptr;
 use elysium_math::{Matrix3x4, Vec3};
+use providence_model::{HitboxDef, Skeleton};
+use std::ffi::CStr;
 
 pub use info::ModelInfo;
 pub use render::ModelRender;
@@ -9,6 +11,8 @@ pub use render::ModelRender;
 mod info;
 mod render;
 
+pub mod studio;
+
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 #[non_exhaustive]
 #[repr(i32)]
@@ -47,11 +51,12 @@ impl<T> MagicArray<T> {
         self.offset
     }
 
+    /// `offset` is relative to `base_address`, and elements are `size_of::<T>()` apart
     pub unsafe fn get_unchecked(&self, base_address: *const u8, index: i32) -> *const T {
         base_address
             .offset(self.offset as isize)
+            .cast::<T>()
             .offset(index as isize)
-            .cast()
     }
 }
 
@@ -108,12 +113,12 @@ impl Bone {
 pub struct BoundingBox {
     pub bone: i32,
     pub group: i32,
-    pub max: Vec3,
     pub min: Vec3,
+    pub max: Vec3,
     pub hitbox_name_offset: i32,
-    _pad0: Pad<12>,
+    pub orientation: Vec3,
     pub radius: f32,
-    _pad1: Pad<16>,
+    _pad0: Pad<16>,
 }
 
 impl BoundingBox {
@@ -121,6 +126,17 @@ impl BoundingBox {
         self as *const Self as *const u8
     }
 
+    pub const fn to_def(&self) -> HitboxDef {
+        HitboxDef {
+            bone: self.bone as usize,
+            group: self.group,
+            min: self.min,
+            max: self.max,
+            orientation: self.orientation,
+            radius: self.radius,
+        }
+    }
+
     pub unsafe fn name(&self) -> *const i8 {
         match self.hitbox_name_offset {
             0 => ptr::null(),
@@ -151,6 +167,52 @@ impl HitboxSet {
     }
 }
 
+#[derive(Debug)]
+#[non_exhaustive]
+#[repr(C)]
+pub struct Attachment {
+    pub name_offset: i32,
+    pub flags: i32,
+    pub bone: i32,
+    pub local: Matrix3x4,
+    _pad0: Pad<32>,
+}
+
+impl Attachment {
+    pub const fn as_ptr(&self) -> *const u8 {
+        self as *const Self as *const u8
+    }
+
+    pub unsafe fn name(&self) -> *const i8 {
+        self.as_ptr().offset(self.name_offset as isize).cast()
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[repr(C)]
+pub struct PoseParameter {
+    pub name_offset: i32,
+    pub flags: i32,
+    pub start: f32,
+    pub end: f32,
+    pub loop_range: f32,
+}
+
+impl PoseParameter {
+    pub const fn as_ptr(&self) -> *const u8 {
+        self as *const Self as *const u8
+    }
+
+    pub unsafe fn name(&self) -> *const i8 {
+        self.as_ptr().offset(self.name_offset as isize).cast()
+    }
+}
+
+/// `studiohdr_t`, the header of a `.mdl` file
+///
+/// the engine uses the file contents as-is, so pointers are stored elsewhere (`studiohdr2_t`)
+/// on 64-bit, and the slots here are 4 bytes wide
 #[derive(Debug)]
 #[non_exhaustive]
 #[repr(C)]
@@ -172,36 +234,105 @@ pub struct Hdr {
     pub hitbox_sets: MagicArray<HitboxSet>,
     pub local_anims: MagicArray<()>,
     pub local_seqs: MagicArray<()>,
+    pub activity_list_version: i32,
+    pub events_indexed: i32,
     pub textures: MagicArray<()>,
-    pub raw_textures: MagicArray<()>,
-    pub replacable_textures: MagicArray<()>,
+    pub cd_textures: MagicArray<()>,
+    pub skin_references_len: i32,
+    pub skin_families_len: i32,
+    pub skin_offset: i32,
     pub body_parts: MagicArray<()>,
-    pub local_attachments: MagicArray<()>,
+    pub local_attachments: MagicArray<Attachment>,
     pub local_nodes: MagicArray<()>,
+    pub local_node_name_offset: i32,
     pub flex_desc: MagicArray<()>,
     pub flex_controllers: MagicArray<()>,
     pub flex_rules: MagicArray<()>,
     pub ik_chains: MagicArray<()>,
     pub mouths: MagicArray<()>,
-    pub local_pose_parameters: MagicArray<()>,
-    pub surface_pos_offset: i32,
-    pub key_values: MagicArray<()>,
+    pub local_pose_parameters: MagicArray<PoseParameter>,
+    pub surface_prop_offset: i32,
+    pub key_values_offset: i32,
+    pub key_values_len: i32,
     pub local_ik_autoplaylocks: MagicArray<()>,
     pub mass: f32,
     pub contents: i32,
     pub include_models: MagicArray<()>,
-    pub virtual_model: *mut (),
+    _virtual_model: i32,
     pub animation_block_name_offset: i32,
     pub animation_blocks: MagicArray<()>,
-    pub bone_table_by_name_index: i32,
-    pub vertex_base: *const (),
-    pub index_base: *const (),
+    _animation_block_model: i32,
+    pub bone_table_by_name_offset: i32,
+    _vertex_base: i32,
+    _index_base: i32,
     pub constant_directional_light_dot: u8,
     pub root_lod: u8,
     pub allowed_root_lods: u8,
     _pad0: Pad<5>,
     pub flex_controller_ui: MagicArray<()>,
-    _pad1: Pad<16>,
+    pub vertex_anim_fixed_point_scale: f32,
+    _pad1: Pad<4>,
+    pub studio_hdr2_offset: i32,
+    _pad2: Pad<4>,
+}
+
+impl Hdr {
+    pub const fn as_ptr(&self) -> *const u8 {
+        self as *const Self as *const u8
+    }
+
+    pub unsafe fn bone_unchecked(&self, index: i32) -> *const Bone {
+        self.bones.get_unchecked(self.as_ptr(), index)
+    }
+
+    pub unsafe fn hitbox_set_unchecked(&self, index: i32) -> *const HitboxSet {
+        self.hitbox_sets.get_unchecked(self.as_ptr(), index)
+    }
+
+    /// bone names, parents and `UsedBy` flags
+    pub unsafe fn skeleton(&self) -> Skeleton {
+        let len = self.bones.len().max(0);
+        let mut skeleton = Skeleton::with_capacity(len as usize);
+
+        for index in 0..len {
+            let bone = &*self.bone_unchecked(index);
+            let name = CStr::from_ptr(bone.name()).to_string_lossy();
+            let parent = usize::try_from(bone.parent).ok();
+
+            skeleton.push(&name, parent, bone.flags & UsedBy::Anything as i32);
+        }
+
+        skeleton
+    }
+
+    /// hitboxes of the hitbox set at `index`
+    pub unsafe fn hitbox_defs(&self, index: i32) -> Option<Vec<HitboxDef>> {
+        if !(0..self.hitbox_sets.len()).contains(&index) {
+            return None;
+        }
+
+        let set = &*self.hitbox_set_unchecked(index);
+        let defs = (0..set.hitboxes.len())
+            .map(|index| (*set.hitbox_unchecked(index)).to_def())
+            .collect();
+
+        Some(defs)
+    }
+}
+
+object_validate! {
+    Hdr;
+    length => 76,
+    flags => 152,
+    bones => 156,
+    hitbox_sets => 172,
+    textures => 204,
+    local_attachments => 240,
+    local_pose_parameters => 300,
+    mass => 328,
+    constant_directional_light_dot => 376,
+    flex_controller_ui => 384,
+    studio_hdr2_offset => 400,
 }
 
 #[derive(Debug)]
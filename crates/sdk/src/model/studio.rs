@@ -0,0 +1,688 @@
+//! Safe reader for studio model (`.mdl`) files.
+//!
+//! Unlike [`Hdr`](super::Hdr), which is read straight out of engine memory, everything here is
+//! parsed from bytes, with every offset bounds-checked.
+
+use super::UsedBy;
+use core::fmt;
+use core::ops::Range;
+use elysium_math::{Matrix3x4, Vec3};
+use providence_model::{HitboxDef, Skeleton};
+
+/// `IDST`
+pub const ID: i32 = i32::from_le_bytes(*b"IDST");
+
+/// oldest supported `.mdl` version
+pub const MIN_VERSION: i32 = 44;
+
+/// newest supported `.mdl` version (CS:GO)
+pub const MAX_VERSION: i32 = 49;
+
+const HDR_SIZE: usize = 408;
+const BONE_SIZE: usize = 216;
+const HITBOX_SET_SIZE: usize = 12;
+const HITBOX_SIZE: usize = 68;
+const ATTACHMENT_SIZE: usize = 92;
+const POSE_PARAMETER_SIZE: usize = 20;
+
+/// error returned when a model can't be read
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// fewer bytes than the header or its `length` requires
+    TooShort,
+    /// the file doesn't start with `IDST`
+    InvalidId(i32),
+    UnsupportedVersion(i32),
+    /// an array, string or index points outside of the model
+    OutOfBounds(&'static str),
+    /// a name isn't nul-terminated utf-8
+    InvalidString(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::TooShort => fmt.write_str("model is truncated"),
+            Error::InvalidId(id) => write!(fmt, "invalid model id {id:#010x}"),
+            Error::UnsupportedVersion(version) => {
+                write!(fmt, "unsupported model version {version}")
+            }
+            Error::OutOfBounds(what) => write!(fmt, "{what} is out of bounds"),
+            Error::InvalidString(what) => write!(fmt, "{what} is not a valid string"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// a parsed studio model
+#[derive(Clone, Debug)]
+pub struct Studio {
+    pub name: String,
+    pub version: i32,
+    pub checksum: i32,
+    pub eye_position: Vec3,
+    pub hull_min: Vec3,
+    pub hull_max: Vec3,
+    pub view_bounding_box_min: Vec3,
+    pub view_bounding_box_max: Vec3,
+    pub flags: i32,
+    pub mass: f32,
+    pub surface_prop: String,
+    pub bones: Vec<Bone>,
+    pub hitbox_sets: Vec<HitboxSet>,
+    pub attachments: Vec<Attachment>,
+    pub pose_parameters: Vec<PoseParameter>,
+}
+
+/// `mstudiobone_t`
+#[derive(Clone, Debug)]
+pub struct Bone {
+    pub name: String,
+    /// index of the parent bone, `None` for a root bone
+    pub parent: Option<usize>,
+    pub position: Vec3,
+    pub quaternion: [f32; 4],
+    pub rotation: Vec3,
+    pub pose_to_bone: Matrix3x4,
+    pub flags: i32,
+    pub surface_prop: String,
+    pub contents: i32,
+}
+
+/// `mstudiohitboxset_t`
+#[derive(Clone, Debug)]
+pub struct HitboxSet {
+    pub name: String,
+    pub hitboxes: Vec<Hitbox>,
+}
+
+/// `mstudiobbox_t`
+#[derive(Clone, Debug)]
+pub struct Hitbox {
+    pub name: String,
+    /// index of the bone this hitbox is attached to
+    pub bone: usize,
+    pub group: i32,
+    pub min: Vec3,
+    pub max: Vec3,
+    pub orientation: Vec3,
+    /// capsule radius, negative for an oriented box
+    pub radius: f32,
+}
+
+/// `mstudioattachment_t`
+#[derive(Clone, Debug)]
+pub struct Attachment {
+    pub name: String,
+    pub flags: i32,
+    /// index of the bone this attachment is relative to
+    pub bone: usize,
+    pub local: Matrix3x4,
+}
+
+/// `mstudioposeparamdesc_t`
+#[derive(Clone, Debug)]
+pub struct PoseParameter {
+    pub name: String,
+    pub flags: i32,
+    pub start: f32,
+    pub end: f32,
+    pub loop_range: f32,
+}
+
+impl Studio {
+    /// parse a model from the contents of a `.mdl` file
+    pub fn parse(bytes: &[u8]) -> Result<Studio, Error> {
+        let reader = Reader { bytes };
+        let id = reader.i32(0)?;
+
+        if id != ID {
+            return Err(Error::InvalidId(id));
+        }
+
+        let version = reader.i32(4)?;
+
+        if !(MIN_VERSION..=MAX_VERSION).contains(&version) {
+            return Err(Error::UnsupportedVersion(version));
+        }
+
+        let length = usize::try_from(reader.i32(76)?).map_err(|_| Error::TooShort)?;
+
+        if length < HDR_SIZE || length > bytes.len() {
+            return Err(Error::TooShort);
+        }
+
+        let reader = Reader {
+            bytes: &bytes[..length],
+        };
+
+        let name = reader.fixed_string(12..76, "model name")?;
+        let bones = reader.bones()?;
+        let hitbox_sets = reader.hitbox_sets(bones.len())?;
+        let attachments = reader.attachments(bones.len())?;
+        let pose_parameters = reader.pose_parameters()?;
+
+        Ok(Studio {
+            name,
+            version,
+            checksum: reader.i32(8)?,
+            eye_position: reader.vec3(80)?,
+            hull_min: reader.vec3(104)?,
+            hull_max: reader.vec3(116)?,
+            view_bounding_box_min: reader.vec3(128)?,
+            view_bounding_box_max: reader.vec3(140)?,
+            flags: reader.i32(152)?,
+            mass: reader.f32(328)?,
+            surface_prop: reader.string(0, reader.i32(308)?, "surface prop")?,
+            bones,
+            hitbox_sets,
+            attachments,
+            pose_parameters,
+        })
+    }
+
+    /// index of the bone named `name`
+    pub fn bone_index(&self, name: &str) -> Option<usize> {
+        self.bones.iter().position(|bone| bone.name == name)
+    }
+
+    /// bone names, parents and `UsedBy` flags
+    pub fn skeleton(&self) -> Skeleton {
+        let mut skeleton = Skeleton::with_capacity(self.bones.len());
+
+        for bone in &self.bones {
+            skeleton.push(
+                &bone.name,
+                bone.parent,
+                bone.flags & UsedBy::Anything as i32,
+            );
+        }
+
+        skeleton
+    }
+
+    /// hitboxes of the hitbox set at `index`
+    pub fn hitbox_defs(&self, index: usize) -> Option<Vec<HitboxDef>> {
+        let set = self.hitbox_sets.get(index)?;
+        let defs = set.hitboxes.iter().map(Hitbox::to_def).collect();
+
+        Some(defs)
+    }
+
+    /// iterate the parents of `index`, nearest first
+    ///
+    /// parents always precede their children, anything else ends the chain
+    pub fn bone_parents(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let parent = |index: usize| {
+            self.bones
+                .get(index)?
+                .parent
+                .filter(|parent| *parent < index)
+        };
+
+        let mut next = parent(index);
+
+        core::iter::from_fn(move || {
+            let index = next?;
+
+            next = parent(index);
+
+            Some(index)
+        })
+    }
+}
+
+impl Hitbox {
+    pub const fn to_def(&self) -> HitboxDef {
+        HitboxDef {
+            bone: self.bone,
+            group: self.group,
+            min: self.min,
+            max: self.max,
+            orientation: self.orientation,
+            radius: self.radius,
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn array<const N: usize>(&self, offset: usize) -> Result<[u8; N], Error> {
+        offset
+            .checked_add(N)
+            .and_then(|end| self.bytes.get(offset..end))
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(Error::TooShort)
+    }
+
+    fn i32(&self, offset: usize) -> Result<i32, Error> {
+        self.array(offset).map(i32::from_le_bytes)
+    }
+
+    fn f32(&self, offset: usize) -> Result<f32, Error> {
+        self.array(offset).map(f32::from_le_bytes)
+    }
+
+    fn vec3(&self, offset: usize) -> Result<Vec3, Error> {
+        Ok(Vec3::from_xyz(
+            self.f32(offset)?,
+            self.f32(offset + 4)?,
+            self.f32(offset + 8)?,
+        ))
+    }
+
+    fn quaternion(&self, offset: usize) -> Result<[f32; 4], Error> {
+        Ok([
+            self.f32(offset)?,
+            self.f32(offset + 4)?,
+            self.f32(offset + 8)?,
+            self.f32(offset + 12)?,
+        ])
+    }
+
+    fn matrix3x4(&self, offset: usize) -> Result<Matrix3x4, Error> {
+        let mut matrix = Matrix3x4::zero();
+
+        for (row, values) in matrix.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().enumerate() {
+                *value = self.f32(offset + (row * 4 + column) * 4)?;
+            }
+        }
+
+        Ok(matrix)
+    }
+
+    /// resolve a `(len, offset)` pair stored at `at`, relative to `base`, into element offsets
+    fn magic_array(
+        &self,
+        base: usize,
+        at: usize,
+        stride: usize,
+        what: &'static str,
+    ) -> Result<impl Iterator<Item = usize>, Error> {
+        let len = usize::try_from(self.i32(at)?).map_err(|_| Error::OutOfBounds(what))?;
+        let start = offset_from(base, self.i32(at + 4)?).ok_or(Error::OutOfBounds(what))?;
+        let end = len
+            .checked_mul(stride)
+            .and_then(|size| start.checked_add(size))
+            .ok_or(Error::OutOfBounds(what))?;
+
+        if len != 0 && end > self.bytes.len() {
+            return Err(Error::OutOfBounds(what));
+        }
+
+        Ok((0..len).map(move |index| start + index * stride))
+    }
+
+    /// read a nul-terminated string at `offset`, relative to `base` (`0` is an empty string)
+    fn string(&self, base: usize, offset: i32, what: &'static str) -> Result<String, Error> {
+        if offset == 0 {
+            return Ok(String::new());
+        }
+
+        let start = offset_from(base, offset)
+            .filter(|start| *start < self.bytes.len())
+            .ok_or(Error::OutOfBounds(what))?;
+
+        self.fixed_string(start..self.bytes.len(), what)
+    }
+
+    /// read a nul-terminated string within `range`
+    fn fixed_string(&self, range: Range<usize>, what: &'static str) -> Result<String, Error> {
+        let bytes = self.bytes.get(range).ok_or(Error::TooShort)?;
+        let len = bytes
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or(Error::InvalidString(what))?;
+
+        core::str::from_utf8(&bytes[..len])
+            .map(String::from)
+            .map_err(|_| Error::InvalidString(what))
+    }
+
+    fn bone_index(&self, index: i32, len: usize, what: &'static str) -> Result<usize, Error> {
+        usize::try_from(index)
+            .ok()
+            .filter(|index| *index < len)
+            .ok_or(Error::OutOfBounds(what))
+    }
+
+    fn bones(&self) -> Result<Vec<Bone>, Error> {
+        let offsets = self.magic_array(0, 156, BONE_SIZE, "bones")?;
+        let mut bones = Vec::with_capacity(offsets.size_hint().0);
+
+        for (index, offset) in offsets.enumerate() {
+            // parents precede their children, which also rules out cycles
+            let parent = match self.i32(offset + 4)? {
+                -1 => None,
+                parent => Some(
+                    usize::try_from(parent)
+                        .ok()
+                        .filter(|parent| *parent < index)
+                        .ok_or(Error::OutOfBounds("bone parent"))?,
+                ),
+            };
+
+            bones.push(Bone {
+                name: self.string(offset, self.i32(offset)?, "bone name")?,
+                parent,
+                position: self.vec3(offset + 32)?,
+                quaternion: self.quaternion(offset + 44)?,
+                rotation: self.vec3(offset + 60)?,
+                pose_to_bone: self.matrix3x4(offset + 96)?,
+                flags: self.i32(offset + 160)?,
+                surface_prop: self.string(offset, self.i32(offset + 176)?, "bone surface prop")?,
+                contents: self.i32(offset + 180)?,
+            });
+        }
+
+        Ok(bones)
+    }
+
+    fn hitbox_sets(&self, bones: usize) -> Result<Vec<HitboxSet>, Error> {
+        let offsets = self.magic_array(0, 172, HITBOX_SET_SIZE, "hitbox sets")?;
+        let mut hitbox_sets = Vec::with_capacity(offsets.size_hint().0);
+
+        for set_offset in offsets {
+            let name = self.string(set_offset, self.i32(set_offset)?, "hitbox set name")?;
+            let offsets = self.magic_array(set_offset, set_offset + 4, HITBOX_SIZE, "hitboxes")?;
+            let mut hitboxes = Vec::with_capacity(offsets.size_hint().0);
+
+            for offset in offsets {
+                hitboxes.push(Hitbox {
+                    bone: self.bone_index(self.i32(offset)?, bones, "hitbox bone")?,
+                    group: self.i32(offset + 4)?,
+                    min: self.vec3(offset + 8)?,
+                    max: self.vec3(offset + 20)?,
+                    name: self.string(offset, self.i32(offset + 32)?, "hitbox name")?,
+                    orientation: self.vec3(offset + 36)?,
+                    radius: self.f32(offset + 48)?,
+                });
+            }
+
+            hitbox_sets.push(HitboxSet { name, hitboxes });
+        }
+
+        Ok(hitbox_sets)
+    }
+
+    fn attachments(&self, bones: usize) -> Result<Vec<Attachment>, Error> {
+        self.magic_array(0, 240, ATTACHMENT_SIZE, "attachments")?
+            .map(|offset| {
+                Ok(Attachment {
+                    name: self.string(offset, self.i32(offset)?, "attachment name")?,
+                    flags: self.i32(offset + 4)?,
+                    bone: self.bone_index(self.i32(offset + 8)?, bones, "attachment bone")?,
+                    local: self.matrix3x4(offset + 12)?,
+                })
+            })
+            .collect()
+    }
+
+    fn pose_parameters(&self) -> Result<Vec<PoseParameter>, Error> {
+        self.magic_array(0, 300, POSE_PARAMETER_SIZE, "pose parameters")?
+            .map(|offset| {
+                Ok(PoseParameter {
+                    name: self.string(offset, self.i32(offset)?, "pose parameter name")?,
+                    flags: self.i32(offset + 4)?,
+                    start: self.f32(offset + 8)?,
+                    end: self.f32(offset + 12)?,
+                    loop_range: self.f32(offset + 16)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// `base + offset`, if it doesn't go below zero
+fn offset_from(base: usize, offset: i32) -> Option<usize> {
+    if offset < 0 {
+        base.checked_sub(offset.unsigned_abs() as usize)
+    } else {
+        base.checked_add(offset as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// builds a model with two bones, one hitbox, one attachment and one pose parameter
+    struct Builder {
+        bytes: Vec<u8>,
+    }
+
+    impl Builder {
+        fn put_i32(&mut self, offset: usize, value: i32) {
+            self.bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        }
+
+        fn put_f32(&mut self, offset: usize, value: f32) {
+            self.bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        }
+
+        fn put_vec3(&mut self, offset: usize, [x, y, z]: [f32; 3]) {
+            self.put_f32(offset, x);
+            self.put_f32(offset + 4, y);
+            self.put_f32(offset + 8, z);
+        }
+
+        /// append a string and point the name offset at `at` (relative to `base`) to it
+        fn put_name(&mut self, base: usize, at: usize, name: &str) {
+            let offset = self.bytes.len() - base;
+
+            self.bytes.extend_from_slice(name.as_bytes());
+            self.bytes.push(0);
+            self.put_i32(at, offset as i32);
+        }
+
+        fn model() -> Vec<u8> {
+            let bones = HDR_SIZE;
+            let sets = bones + 2 * BONE_SIZE;
+            let hitboxes = sets + HITBOX_SET_SIZE;
+            let attachments = hitboxes + HITBOX_SIZE;
+            let poses = attachments + ATTACHMENT_SIZE;
+            let strings = poses + POSE_PARAMETER_SIZE;
+
+            let mut builder = Builder {
+                bytes: vec![0; strings],
+            };
+
+            builder.bytes[..4].copy_from_slice(b"IDST");
+            builder.put_i32(4, 49);
+            builder.put_i32(8, 0x1234);
+            builder.bytes[12..22].copy_from_slice(b"test.mdl\0\0");
+            builder.put_vec3(104, [-16.0, -16.0, 0.0]);
+            builder.put_vec3(116, [16.0, 16.0, 72.0]);
+
+            builder.put_i32(156, 2);
+            builder.put_i32(160, bones as i32);
+            builder.put_i32(172, 1);
+            builder.put_i32(176, sets as i32);
+            builder.put_i32(240, 1);
+            builder.put_i32(244, attachments as i32);
+            builder.put_i32(300, 1);
+            builder.put_i32(304, poses as i32);
+
+            builder.put_i32(bones + 4, -1);
+            builder.put_vec3(bones + 32, [0.0, 0.0, 40.0]);
+            builder.put_i32(bones + BONE_SIZE + 4, 0);
+            builder.put_vec3(bones + BONE_SIZE + 32, [0.0, 0.0, 24.0]);
+
+            builder.put_i32(sets + 4, 1);
+            builder.put_i32(sets + 8, (hitboxes - sets) as i32);
+
+            builder.put_i32(hitboxes, 1);
+            builder.put_vec3(hitboxes + 8, [-1.0, -2.0, -3.0]);
+            builder.put_vec3(hitboxes + 20, [1.0, 2.0, 3.0]);
+            builder.put_f32(hitboxes + 48, 4.5);
+
+            builder.put_i32(attachments + 8, 1);
+            builder.put_f32(attachments + 12, 1.0);
+
+            builder.put_f32(poses + 8, -90.0);
+            builder.put_f32(poses + 12, 90.0);
+            builder.put_f32(poses + 16, 360.0);
+
+            builder.put_name(0, 308, "flesh");
+            builder.put_name(bones, bones, "pelvis");
+            builder.put_name(bones + BONE_SIZE, bones + BONE_SIZE, "head_0");
+            builder.put_name(sets, sets, "default");
+            builder.put_name(hitboxes, hitboxes + 32, "head");
+            builder.put_name(attachments, attachments, "eyes");
+            builder.put_name(poses, poses, "body_yaw");
+
+            let len = builder.bytes.len() as i32;
+
+            builder.put_i32(76, len);
+            builder.bytes
+        }
+    }
+
+    #[test]
+    fn header() {
+        let studio = Studio::parse(&Builder::model()).unwrap();
+
+        assert_eq!(studio.name, "test.mdl");
+        assert_eq!(studio.version, 49);
+        assert_eq!(studio.checksum, 0x1234);
+        assert_eq!(studio.hull_max, Vec3::from_xyz(16.0, 16.0, 72.0));
+        assert_eq!(studio.surface_prop, "flesh");
+    }
+
+    #[test]
+    fn bones() {
+        let mut studio = Studio::parse(&Builder::model()).unwrap();
+
+        assert_eq!(studio.bones.len(), 2);
+        assert_eq!(studio.bones[0].name, "pelvis");
+        assert_eq!(studio.bones[0].parent, None);
+        assert_eq!(studio.bones[1].name, "head_0");
+        assert_eq!(studio.bones[1].parent, Some(0));
+        assert_eq!(studio.bones[1].position, Vec3::from_xyz(0.0, 0.0, 24.0));
+        assert_eq!(studio.bone_index("head_0"), Some(1));
+        assert_eq!(studio.bone_parents(1).collect::<Vec<_>>(), [0]);
+        assert_eq!(studio.bone_parents(2).count(), 0);
+
+        // hand-built cycles end rather than loop forever
+        studio.bones[0].parent = Some(1);
+
+        assert_eq!(studio.bone_parents(1).collect::<Vec<_>>(), [0]);
+    }
+
+    #[test]
+    fn hitboxes() {
+        let studio = Studio::parse(&Builder::model()).unwrap();
+        let set = &studio.hitbox_sets[0];
+        let hitbox = &set.hitboxes[0];
+
+        assert_eq!(set.name, "default");
+        assert_eq!(hitbox.name, "head");
+        assert_eq!(hitbox.bone, 1);
+        assert_eq!(hitbox.min, Vec3::from_xyz(-1.0, -2.0, -3.0));
+        assert_eq!(hitbox.max, Vec3::from_xyz(1.0, 2.0, 3.0));
+        assert_eq!(hitbox.radius, 4.5);
+    }
+
+    #[test]
+    fn attachments_and_pose_parameters() {
+        let studio = Studio::parse(&Builder::model()).unwrap();
+        let attachment = &studio.attachments[0];
+        let pose = &studio.pose_parameters[0];
+
+        assert_eq!(attachment.name, "eyes");
+        assert_eq!(attachment.bone, 1);
+        assert_eq!(attachment.local[0][0], 1.0);
+        assert_eq!(pose.name, "body_yaw");
+        assert_eq!(
+            (pose.start, pose.end, pose.loop_range),
+            (-90.0, 90.0, 360.0)
+        );
+    }
+
+    #[test]
+    fn skeleton_and_hitbox_defs() {
+        let studio = Studio::parse(&Builder::model()).unwrap();
+        let skeleton = studio.skeleton();
+        let defs = studio.hitbox_defs(0).unwrap();
+
+        assert_eq!(skeleton.index("head_0"), Some(1));
+        assert_eq!(skeleton.segments().collect::<Vec<_>>(), [(0, 1)]);
+        assert_eq!(defs.len(), 1);
+        assert_eq!(defs[0].bone, 1);
+        assert_eq!(defs[0].radius, 4.5);
+        assert!(studio.hitbox_defs(1).is_none());
+    }
+
+    #[test]
+    fn invalid_header() {
+        let mut model = Builder::model();
+
+        assert_eq!(Studio::parse(&model[..100]).unwrap_err(), Error::TooShort);
+
+        model[4] = 10;
+
+        assert_eq!(
+            Studio::parse(&model).unwrap_err(),
+            Error::UnsupportedVersion(10)
+        );
+
+        model[0] = b'X';
+
+        assert!(matches!(
+            Studio::parse(&model).unwrap_err(),
+            Error::InvalidId(_)
+        ));
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let mut builder = Builder {
+            bytes: Builder::model(),
+        };
+
+        builder.put_i32(156, 1_000);
+
+        assert_eq!(
+            Studio::parse(&builder.bytes).unwrap_err(),
+            Error::OutOfBounds("bones")
+        );
+
+        builder.put_i32(156, 2);
+        builder.put_i32(HDR_SIZE + BONE_SIZE + 4, 5);
+
+        assert_eq!(
+            Studio::parse(&builder.bytes).unwrap_err(),
+            Error::OutOfBounds("bone parent")
+        );
+
+        // a bone can't be its own parent, or the parent of an earlier bone
+        builder.put_i32(HDR_SIZE + BONE_SIZE + 4, 1);
+
+        assert_eq!(
+            Studio::parse(&builder.bytes).unwrap_err(),
+            Error::OutOfBounds("bone parent")
+        );
+
+        builder.put_i32(HDR_SIZE + BONE_SIZE + 4, -1);
+        builder.put_i32(HDR_SIZE + 4, 1);
+
+        assert_eq!(
+            Studio::parse(&builder.bytes).unwrap_err(),
+            Error::OutOfBounds("bone parent")
+        );
+
+        builder.put_i32(HDR_SIZE + 4, -1);
+        builder.put_i32(HDR_SIZE + BONE_SIZE + 4, 0);
+        builder.put_i32(HDR_SIZE, -1_000);
+
+        assert_eq!(
+            Studio::parse(&builder.bytes).unwrap_err(),
+            Error::OutOfBounds("bone name")
+        );
+    }
+}
@@ -0,0 +1,50 @@
+// https://github.com/VSES/SourceEngine2007/blob/master/se2007/game/shared/igamemovement.h
+
+use crate::{vtable_validate, MoveData};
+use cake::ffi::vtable;
+
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<2>,
+    process_movement: unsafe extern "thiscall" fn(
+        this: *const Movement,
+        player: *const (),
+        move_data: *mut MoveData,
+    ),
+    _pad1: vtable::Pad<1>,
+    start_track_prediction_errors:
+        unsafe extern "thiscall" fn(this: *const Movement, player: *const ()),
+    finish_track_prediction_errors:
+        unsafe extern "thiscall" fn(this: *const Movement, player: *const ()),
+}
+
+vtable_validate! {
+    process_movement => 2,
+    start_track_prediction_errors => 4,
+    finish_track_prediction_errors => 5,
+}
+
+/// game movement (`CGameMovement`)
+#[repr(C)]
+pub struct Movement {
+    vtable: &'static VTable,
+}
+
+impl Movement {
+    /// move `player` according to `move_data`, leaving the result in `move_data`
+    #[inline]
+    pub fn process_movement(&self, player: *const (), move_data: &mut MoveData) {
+        unsafe { (self.vtable.process_movement)(self, player, move_data) }
+    }
+
+    /// start recording prediction errors of `player`, call before processing movement
+    #[inline]
+    pub fn start_track_prediction_errors(&self, player: *const ()) {
+        unsafe { (self.vtable.start_track_prediction_errors)(self, player) }
+    }
+
+    #[inline]
+    pub fn finish_track_prediction_errors(&self, player: *const ()) {
+        unsafe { (self.vtable.finish_track_prediction_errors)(self, player) }
+    }
+}
@@ -2,6 +2,7 @@ use core::fmt;
 use core::mem::MaybeUninit;
 
 /// Byte-padding for unknown data.
+#[derive(Clone, Copy)]
 #[repr(C)]
 pub struct Pad<const N: usize>([MaybeUninit<u8>; N]);
 
@@ -0,0 +1,206 @@
+// https://github.com/VSES/SourceEngine2007/blob/master/se2007/game/client/prediction.cpp
+// https://github.com/VSES/SourceEngine2007/blob/master/se2007/game/shared/igamemovement.h
+
+use crate::{object_validate, vtable_validate, Buttons, Command, Pad};
+use cake::ffi::vtable;
+use core::cell::Cell;
+use elysium_math::Vec3;
+
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<20>,
+    run_command: unsafe extern "thiscall" fn(
+        this: *const Prediction,
+        player: *const (),
+        command: *const Command,
+        move_helper: *const MoveHelper,
+    ),
+    setup_move: unsafe extern "thiscall" fn(
+        this: *const Prediction,
+        player: *const (),
+        command: *const Command,
+        move_helper: *const MoveHelper,
+        move_data: *mut MoveData,
+    ),
+    finish_move: unsafe extern "thiscall" fn(
+        this: *const Prediction,
+        player: *const (),
+        command: *const Command,
+        move_data: *mut MoveData,
+    ),
+}
+
+vtable_validate! {
+    run_command => 20,
+    setup_move => 21,
+    finish_move => 22,
+}
+
+/// client prediction (`CPrediction`)
+#[repr(C)]
+pub struct Prediction {
+    vtable: &'static VTable,
+    // m_hLastGround
+    _pad0: Pad<4>,
+    in_prediction: Cell<bool>,
+    // m_bOldCLPredictValue through m_flLastServerWorldTimeStamp
+    _pad1: Pad<15>,
+    // m_Split[0].m_bFirstTimePredicted
+    is_first_time_predicted: Cell<bool>,
+}
+
+object_validate! {
+    Prediction;
+    in_prediction => 12,
+    is_first_time_predicted => 28,
+}
+
+impl Prediction {
+    /// whether a command is being predicted
+    #[inline]
+    pub fn in_prediction(&self) -> bool {
+        self.in_prediction.get()
+    }
+
+    #[inline]
+    pub fn set_in_prediction(&self, in_prediction: bool) {
+        self.in_prediction.set(in_prediction)
+    }
+
+    /// whether the command being predicted is run for the first time, effects and sounds are
+    /// only played then
+    #[inline]
+    pub fn is_first_time_predicted(&self) -> bool {
+        self.is_first_time_predicted.get()
+    }
+
+    #[inline]
+    pub fn set_first_time_predicted(&self, is_first_time_predicted: bool) {
+        self.is_first_time_predicted.set(is_first_time_predicted)
+    }
+
+    /// run `command` for `player` as the game does, setting up, processing and finishing the move
+    #[inline]
+    pub fn run_command(&self, player: *const (), command: &Command, move_helper: &MoveHelper) {
+        unsafe { (self.vtable.run_command)(self, player, command, move_helper) }
+    }
+
+    /// copy `player`'s state and `command` into `move_data`, ready for
+    /// [`Movement::process_movement`](crate::Movement::process_movement)
+    #[inline]
+    pub fn setup_move(
+        &self,
+        player: *const (),
+        command: &Command,
+        move_helper: &MoveHelper,
+        move_data: &mut MoveData,
+    ) {
+        unsafe { (self.vtable.setup_move)(self, player, command, move_helper, move_data) }
+    }
+
+    /// copy the processed `move_data` back onto `player`
+    #[inline]
+    pub fn finish_move(&self, player: *const (), command: &Command, move_data: &mut MoveData) {
+        unsafe { (self.vtable.finish_move)(self, player, command, move_data) }
+    }
+}
+
+#[repr(C)]
+struct MoveHelperVTable {
+    _pad0: vtable::Pad<1>,
+    set_host: unsafe extern "thiscall" fn(this: *const MoveHelper, host: *const ()),
+}
+
+/// interface between game movement and the entity being moved (`IMoveHelper`)
+///
+/// not exported, found through `IMoveHelper::sm_pSingleton` instead
+#[repr(C)]
+pub struct MoveHelper {
+    vtable: &'static MoveHelperVTable,
+}
+
+impl MoveHelper {
+    /// set the entity being moved, null once done
+    #[inline]
+    pub fn set_host(&self, host: *const ()) {
+        unsafe { (self.vtable.set_host)(self, host) }
+    }
+}
+
+/// input and output of a single move (`CMoveData`)
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct MoveData {
+    /// see [`MoveData::FIRST_RUN_OF_FUNCTIONS`] and friends
+    pub flags: u8,
+    pub player_handle: i32,
+    pub impulse_command: i32,
+    pub view_angle: Vec3,
+    pub abs_view_angle: Vec3,
+    pub buttons: Buttons,
+    pub old_buttons: Buttons,
+    pub forward_move: f32,
+    pub side_move: f32,
+    pub up_move: f32,
+    pub max_speed: f32,
+    pub client_max_speed: f32,
+    pub velocity: Vec3,
+    pub trailing_velocity: Vec3,
+    pub trailing_velocity_time: f32,
+    pub angles: Vec3,
+    pub old_angles: Vec3,
+    pub step_height: f32,
+    pub wish_velocity: Vec3,
+    pub jump_velocity: Vec3,
+    pub constraint_center: Vec3,
+    pub constraint_radius: f32,
+    pub constraint_width: f32,
+    pub constraint_speed_factor: f32,
+    pub constraint_past_radius: bool,
+    pub abs_origin: Vec3,
+    // room for anything the game has added since
+    _pad0: Pad<72>,
+}
+
+object_validate! {
+    MoveData;
+    player_handle => 4,
+    impulse_command => 8,
+    view_angle => 12,
+    abs_view_angle => 24,
+    buttons => 36,
+    old_buttons => 40,
+    forward_move => 44,
+    side_move => 48,
+    up_move => 52,
+    max_speed => 56,
+    client_max_speed => 60,
+    velocity => 64,
+    trailing_velocity => 76,
+    trailing_velocity_time => 88,
+    angles => 92,
+    old_angles => 104,
+    step_height => 116,
+    wish_velocity => 120,
+    jump_velocity => 132,
+    constraint_center => 144,
+    constraint_radius => 156,
+    constraint_width => 160,
+    constraint_speed_factor => 164,
+    constraint_past_radius => 168,
+    abs_origin => 172,
+}
+
+impl MoveData {
+    /// whether this is the first time the command is being run
+    pub const FIRST_RUN_OF_FUNCTIONS: u8 = 1 << 0;
+    pub const GAME_CODE_MOVED_PLAYER: u8 = 1 << 1;
+    pub const NO_AIR_CONTROL: u8 = 1 << 2;
+
+    /// zeroed move data, for [`Prediction::setup_move`] to fill in
+    #[inline]
+    pub fn zeroed() -> Self {
+        // SAFETY: every field is plain old data
+        unsafe { core::mem::zeroed() }
+    }
+}
@@ -1,4 +1,91 @@
-use core::fmt;
+use crate::{ffi, vtable_validate};
+use cake::ffi::vtable;
+use core::{fmt, ptr};
+use elysium_vdf::{Block, Pair, Value};
+use std::ffi::{CString, OsStr};
+
+/// `KeyValues::FromString`-like function found through `VDF_FROM_BYTES`
+pub type VdfFromBytes =
+    unsafe extern "C" fn(name: *const u8, value: *const u8, _unk1: *const u8) -> *const Vdf;
+
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<3>,
+    symbol_for_string: unsafe extern "thiscall" fn(
+        this: *const KeyValuesSystem,
+        name: *const u8,
+        create: bool,
+    ) -> i32,
+    string_for_symbol:
+        unsafe extern "thiscall" fn(this: *const KeyValuesSystem, symbol: i32) -> *const u8,
+}
+
+vtable_validate! {
+    symbol_for_string => 3,
+    string_for_symbol => 4,
+}
+
+/// `IKeyValuesSystem`, owner of the key name symbol table
+#[repr(C)]
+pub struct KeyValuesSystem {
+    vtable: &'static VTable,
+}
+
+impl KeyValuesSystem {
+    /// the symbol for `name`, if it has been used as a key before
+    #[inline]
+    pub fn symbol_for_string<S>(&self, name: S) -> Option<i32>
+    where
+        S: AsRef<OsStr>,
+    {
+        let cstr = ffi::osstr_to_cstr_cow(name);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+        let symbol = unsafe { (self.vtable.symbol_for_string)(self, ptr, false) };
+
+        (symbol != -1).then_some(symbol)
+    }
+
+    /// the string a key symbol refers to
+    #[inline]
+    pub fn string_for_symbol(&self, symbol: i32) -> &str {
+        unsafe { ffi::str_from_ptr_nullable((self.vtable.string_for_symbol)(self, symbol)) }
+    }
+}
+
+/// the kind of value a node holds
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum DataKind {
+    /// the node has children instead of a value
+    None = 0,
+    String = 1,
+    Int = 2,
+    Float = 3,
+    Ptr = 4,
+    WString = 5,
+    Color = 6,
+    U64 = 7,
+}
+
+impl DataKind {
+    #[inline]
+    pub const fn from_u8(kind: u8) -> Option<Self> {
+        let kind = match kind {
+            0 => DataKind::None,
+            1 => DataKind::String,
+            2 => DataKind::Int,
+            3 => DataKind::Float,
+            4 => DataKind::Ptr,
+            5 => DataKind::WString,
+            6 => DataKind::Color,
+            7 => DataKind::U64,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+}
 
 #[repr(C)]
 pub union VdfValue {
@@ -8,6 +95,7 @@ pub union VdfValue {
     pub color: [u8; 4],
 }
 
+/// the engine's `KeyValues` node
 #[repr(C)]
 pub struct Vdf {
     pub key_name: i32,
@@ -39,3 +127,168 @@ impl fmt::Debug for Vdf {
             .finish()
     }
 }
+
+/// iterator over the children of a node
+pub struct Children<'a> {
+    next: Option<&'a Vdf>,
+}
+
+impl<'a> Iterator for Children<'a> {
+    type Item = &'a Vdf;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a Vdf> {
+        let node = self.next?;
+
+        self.next = unsafe { node.peer.as_ref() };
+
+        Some(node)
+    }
+}
+
+impl Vdf {
+    /// build a node tree from a pure-rust block, through the engine's parser
+    ///
+    /// the returned tree is owned by whatever it is handed to, such as `MaterialSystem::create`
+    #[inline]
+    pub unsafe fn from_block<'a>(
+        from_bytes: VdfFromBytes,
+        name: &str,
+        block: &Block,
+    ) -> Option<&'a Vdf> {
+        let name = CString::new(name).ok()?;
+        let text = CString::new(block.to_string()).ok()?;
+
+        from_bytes(name.as_ptr().cast(), text.as_ptr().cast(), ptr::null()).as_ref()
+    }
+
+    /// symbol id of this node's key
+    #[inline]
+    pub const fn key_symbol(&self) -> i32 {
+        self.key_name & 0x00FF_FFFF
+    }
+
+    /// this node's key, resolved through the key values system
+    #[inline]
+    pub fn key<'a>(&self, system: &'a KeyValuesSystem) -> &'a str {
+        system.string_for_symbol(self.key_symbol())
+    }
+
+    #[inline]
+    pub const fn data_kind(&self) -> Option<DataKind> {
+        DataKind::from_u8(self.data_kind)
+    }
+
+    /// whether this node has children rather than a value
+    #[inline]
+    pub fn is_block(&self) -> bool {
+        self.data_kind() == Some(DataKind::None)
+    }
+
+    #[inline]
+    pub fn children(&self) -> Children<'_> {
+        let next = unsafe { self.sub.as_ref() };
+
+        Children { next }
+    }
+
+    /// the first child named `key` (case-insensitive, like the engine)
+    #[inline]
+    pub fn find(&self, system: &KeyValuesSystem, key: &str) -> Option<&Vdf> {
+        self.children()
+            .find(|child| child.key(system).eq_ignore_ascii_case(key))
+    }
+
+    /// follow a `/` separated path of keys
+    #[inline]
+    pub fn find_path(&self, system: &KeyValuesSystem, path: &str) -> Option<&Vdf> {
+        path.split('/')
+            .try_fold(self, |node, key| node.find(system, key))
+    }
+
+    #[inline]
+    pub fn value_str(&self) -> Option<&str> {
+        match self.data_kind()? {
+            DataKind::String => Some(unsafe { ffi::str_from_ptr_nullable(self.value) }),
+            _ => None,
+        }
+    }
+
+    /// the value as an integer, converting like `KeyValues::GetInt`
+    #[inline]
+    pub fn value_int(&self) -> Option<i32> {
+        unsafe {
+            match self.data_kind()? {
+                DataKind::Int => Some(self.vdf_value.int),
+                DataKind::Float => Some(self.vdf_value.float as i32),
+                DataKind::String => self.value_str()?.trim().parse().ok(),
+                DataKind::U64 => Some(self.value.cast::<u64>().read_unaligned() as i32),
+                _ => None,
+            }
+        }
+    }
+
+    /// the value as a float, converting like `KeyValues::GetFloat`
+    #[inline]
+    pub fn value_float(&self) -> Option<f32> {
+        unsafe {
+            match self.data_kind()? {
+                DataKind::Int => Some(self.vdf_value.int as f32),
+                DataKind::Float => Some(self.vdf_value.float),
+                DataKind::String => self.value_str()?.trim().parse().ok(),
+                DataKind::U64 => Some(self.value.cast::<u64>().read_unaligned() as f32),
+                _ => None,
+            }
+        }
+    }
+
+    /// the value as a color, converting like `KeyValues::GetColor`
+    #[inline]
+    pub fn value_color(&self) -> Option<[u8; 4]> {
+        unsafe {
+            match self.data_kind()? {
+                DataKind::Color => Some(self.vdf_value.color),
+                DataKind::Int => Some(self.vdf_value.int.to_le_bytes()),
+                DataKind::String => Value::from(self.value_str()?).as_color(),
+                _ => None,
+            }
+        }
+    }
+
+    /// the value as text, the way the engine writes it out
+    #[inline]
+    pub fn value_string(&self) -> Option<String> {
+        let string = match self.data_kind()? {
+            DataKind::None | DataKind::Ptr | DataKind::WString => return None,
+            DataKind::String => self.value_str()?.into(),
+            DataKind::Int => self.value_int()?.to_string(),
+            DataKind::Float => self.value_float()?.to_string(),
+            DataKind::Color => {
+                let [r, g, b, a] = self.value_color()?;
+
+                format!("{r} {g} {b} {a}")
+            }
+            DataKind::U64 => unsafe { self.value.cast::<u64>().read_unaligned().to_string() },
+        };
+
+        Some(string)
+    }
+
+    /// convert this node and its children into a pure-rust pair
+    #[inline]
+    pub fn to_pair(&self, system: &KeyValuesSystem) -> Pair {
+        let key = self.key(system);
+
+        if self.is_block() {
+            Pair::new(key, self.to_block(system))
+        } else {
+            Pair::new(key, self.value_string().unwrap_or_default())
+        }
+    }
+
+    /// convert this node's children into a pure-rust block
+    #[inline]
+    pub fn to_block(&self, system: &KeyValuesSystem) -> Block {
+        self.children().map(|child| child.to_pair(system)).collect()
+    }
+}
@@ -0,0 +1,63 @@
+use core::fmt;
+
+/// What went wrong while parsing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// The text ended in the middle of a pair or block.
+    UnexpectedEof,
+    /// A `}` without a matching `{`.
+    UnexpectedCloseBrace,
+    /// A key was followed by `}` instead of a value.
+    MissingValue,
+    /// A quoted string was never closed.
+    UnterminatedString,
+    /// A `[...]` conditional couldn't be parsed.
+    InvalidConditional(String),
+    /// An unknown `#` directive.
+    InvalidDirective(String),
+    /// An `#include` or `#base` file couldn't be read.
+    UnresolvedInclude(String),
+    /// Includes nested too deeply, most likely a cycle.
+    IncludeDepth,
+}
+
+/// A parse error and where it happened.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number.
+    pub column: usize,
+}
+
+impl fmt::Display for ErrorKind {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedEof => fmt.write_str("unexpected end of file"),
+            ErrorKind::UnexpectedCloseBrace => fmt.write_str("unexpected `}`"),
+            ErrorKind::MissingValue => fmt.write_str("key has no value"),
+            ErrorKind::UnterminatedString => fmt.write_str("unterminated string"),
+            ErrorKind::InvalidConditional(condition) => {
+                write!(fmt, "invalid conditional `{condition}`")
+            }
+            ErrorKind::InvalidDirective(directive) => {
+                write!(fmt, "invalid directive `{directive}`")
+            }
+            ErrorKind::UnresolvedInclude(path) => write!(fmt, "unable to include `{path}`"),
+            ErrorKind::IncludeDepth => fmt.write_str("includes nested too deeply"),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let Error { kind, line, column } = self;
+
+        write!(fmt, "{kind} at {line}:{column}")
+    }
+}
+
+impl std::error::Error for Error {}
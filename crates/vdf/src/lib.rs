@@ -0,0 +1,290 @@
+//! KeyValues (VDF) text parser and serializer.
+//!
+//! Parses the same text format as the engine's `KeyValues::LoadFromBuffer`: quoted and unquoted
+//! tokens, `//` comments, escape sequences, `#include`/`#base` directives and platform
+//! conditionals such as `[$LINUX]` or `[!$X360 && $WIN32]`.
+
+#![deny(warnings)]
+
+pub use error::{Error, ErrorKind};
+pub use parse::{from_str, Conditions, Parser};
+
+mod error;
+mod parse;
+mod write;
+
+use core::fmt;
+
+/// A key and its value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pair {
+    pub key: String,
+    pub value: Value,
+}
+
+/// The value of a key, either a string or a nested block of pairs.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    String(String),
+    Block(Block),
+}
+
+/// An ordered list of pairs, like `{ "key" "value" ... }`.
+///
+/// Keys are compared case-insensitively, as the engine does, and may repeat.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Block {
+    pairs: Vec<Pair>,
+}
+
+impl Pair {
+    #[inline]
+    pub fn new<K, V>(key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        let key = key.into();
+        let value = value.into();
+
+        Self { key, value }
+    }
+}
+
+impl Value {
+    /// The string value, if this isn't a block.
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            Value::Block(_) => None,
+        }
+    }
+
+    /// The nested block, if this is one.
+    #[inline]
+    pub fn as_block(&self) -> Option<&Block> {
+        match self {
+            Value::String(_) => None,
+            Value::Block(block) => Some(block),
+        }
+    }
+
+    /// The nested block, if this is one.
+    #[inline]
+    pub fn as_block_mut(&mut self) -> Option<&mut Block> {
+        match self {
+            Value::String(_) => None,
+            Value::Block(block) => Some(block),
+        }
+    }
+
+    /// The value as an integer.
+    #[inline]
+    pub fn as_int(&self) -> Option<i32> {
+        self.as_str()?.trim().parse().ok()
+    }
+
+    /// The value as a float.
+    #[inline]
+    pub fn as_float(&self) -> Option<f32> {
+        self.as_str()?.trim().parse().ok()
+    }
+
+    /// The value as an `r g b a` color, alpha defaults to 255.
+    #[inline]
+    pub fn as_color(&self) -> Option<[u8; 4]> {
+        let mut components = self.as_str()?.split_whitespace();
+        let mut color = [0, 0, 0, 255];
+
+        for (index, component) in color.iter_mut().enumerate() {
+            match components.next() {
+                Some(value) => *component = value.parse().ok()?,
+                None if index == 3 => {}
+                None => return None,
+            }
+        }
+
+        components.next().is_none().then_some(color)
+    }
+}
+
+impl From<String> for Value {
+    #[inline]
+    fn from(string: String) -> Self {
+        Value::String(string)
+    }
+}
+
+impl From<&str> for Value {
+    #[inline]
+    fn from(string: &str) -> Self {
+        Value::String(string.into())
+    }
+}
+
+impl From<Block> for Value {
+    #[inline]
+    fn from(block: Block) -> Self {
+        Value::Block(block)
+    }
+}
+
+impl Block {
+    #[inline]
+    pub const fn new() -> Self {
+        let pairs = Vec::new();
+
+        Self { pairs }
+    }
+
+    /// Append a pair, returning the block for chaining.
+    #[inline]
+    pub fn with<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        self.push(key, value);
+        self
+    }
+
+    /// Append a pair.
+    #[inline]
+    pub fn push<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        self.pairs.push(Pair::new(key, value));
+    }
+
+    /// Set the value of the first pair named `key`, appending one if there is none.
+    #[inline]
+    pub fn set<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        let key = key.into();
+        let value = value.into();
+
+        match self.get_mut(&key) {
+            Some(existing) => *existing = value,
+            None => self.pairs.push(Pair { key, value }),
+        }
+    }
+
+    /// Remove every pair named `key`.
+    #[inline]
+    pub fn remove(&mut self, key: &str) {
+        self.pairs
+            .retain(|pair| !pair.key.eq_ignore_ascii_case(key));
+    }
+
+    /// The value of the first pair named `key`.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.pairs
+            .iter()
+            .find(|pair| pair.key.eq_ignore_ascii_case(key))
+            .map(|pair| &pair.value)
+    }
+
+    /// The value of the first pair named `key`.
+    #[inline]
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.pairs
+            .iter_mut()
+            .find(|pair| pair.key.eq_ignore_ascii_case(key))
+            .map(|pair| &mut pair.value)
+    }
+
+    /// The string value of the first pair named `key`.
+    #[inline]
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key)?.as_str()
+    }
+
+    /// The nested block of the first pair named `key`.
+    #[inline]
+    pub fn get_block(&self, key: &str) -> Option<&Block> {
+        self.get(key)?.as_block()
+    }
+
+    /// Follow a `/` separated path of keys through nested blocks.
+    #[inline]
+    pub fn find(&self, path: &str) -> Option<&Value> {
+        let mut keys = path.split('/');
+        let mut value = self.get(keys.next()?)?;
+
+        for key in keys {
+            value = value.as_block()?.get(key)?;
+        }
+
+        Some(value)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, Pair> {
+        self.pairs.iter()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    #[inline]
+    pub fn pairs(&self) -> &[Pair] {
+        &self.pairs
+    }
+
+    #[inline]
+    pub fn pairs_mut(&mut self) -> &mut Vec<Pair> {
+        &mut self.pairs
+    }
+}
+
+impl<'a> IntoIterator for &'a Block {
+    type Item = &'a Pair;
+    type IntoIter = core::slice::Iter<'a, Pair>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Pair> for Block {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Pair>,
+    {
+        let pairs = iter.into_iter().collect();
+
+        Self { pairs }
+    }
+}
+
+impl core::str::FromStr for Block {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(text: &str) -> Result<Self, Error> {
+        parse::from_str(text)
+    }
+}
+
+impl fmt::Display for Block {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write::block(fmt, self, 0)
+    }
+}
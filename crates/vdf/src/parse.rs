@@ -0,0 +1,727 @@
+use crate::{Block, Error, ErrorKind, Pair, Value};
+
+/// How deeply `#include` and `#base` may nest before giving up.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+type Include<'a> = Box<dyn FnMut(&str) -> Option<String> + 'a>;
+
+/// Parse `text` with the default options: escape sequences enabled, linux conditionals and no
+/// `#include`/`#base` support.
+#[inline]
+pub fn from_str(text: &str) -> Result<Block, Error> {
+    Parser::new().parse(text)
+}
+
+/// Symbols considered defined when evaluating `[...]` conditionals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conditions {
+    defined: Vec<String>,
+}
+
+impl Conditions {
+    /// No symbols defined.
+    #[inline]
+    pub const fn none() -> Self {
+        let defined = Vec::new();
+
+        Self { defined }
+    }
+
+    /// The symbols the linux client defines.
+    #[inline]
+    pub fn linux() -> Self {
+        Self::none().define("$LINUX").define("$POSIX")
+    }
+
+    /// Define `symbol`, such as `$LINUX`.
+    #[inline]
+    pub fn define<S>(mut self, symbol: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.defined.push(symbol.into());
+        self
+    }
+
+    #[inline]
+    pub fn is_defined(&self, symbol: &str) -> bool {
+        self.defined
+            .iter()
+            .any(|defined| defined.eq_ignore_ascii_case(symbol))
+    }
+
+    /// Evaluate a conditional such as `[!$X360 && ($LINUX || $OSX)]`.
+    #[inline]
+    pub fn evaluate(&self, conditional: &str) -> Option<bool> {
+        let expression = conditional.strip_prefix('[')?.strip_suffix(']')?;
+        let mut evaluator = Evaluator {
+            conditions: self,
+            bytes: expression.as_bytes(),
+            offset: 0,
+        };
+
+        let result = evaluator.or()?;
+
+        evaluator.skip_whitespace();
+
+        (evaluator.offset == evaluator.bytes.len()).then_some(result)
+    }
+}
+
+impl Default for Conditions {
+    #[inline]
+    fn default() -> Self {
+        Self::linux()
+    }
+}
+
+/// KeyValues parser.
+pub struct Parser<'a> {
+    conditions: Conditions,
+    escapes: bool,
+    include: Option<Include<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            conditions: Conditions::default(),
+            escapes: true,
+            include: None,
+        }
+    }
+
+    /// Symbols defined when evaluating conditionals.
+    #[inline]
+    pub fn conditions(mut self, conditions: Conditions) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
+    /// Whether `\` starts an escape sequence in quoted strings.
+    #[inline]
+    pub fn escapes(mut self, escapes: bool) -> Self {
+        self.escapes = escapes;
+        self
+    }
+
+    /// Read the file named by an `#include` or `#base` directive.
+    #[inline]
+    pub fn include<F>(mut self, include: F) -> Self
+    where
+        F: FnMut(&str) -> Option<String> + 'a,
+    {
+        self.include = Some(Box::new(include));
+        self
+    }
+
+    /// Parse `text` into a block of pairs.
+    #[inline]
+    pub fn parse(&mut self, text: &str) -> Result<Block, Error> {
+        self.parse_file(text, 0)
+    }
+
+    fn parse_file(&mut self, text: &str, depth: usize) -> Result<Block, Error> {
+        let mut lexer = Lexer::new(text, self.escapes);
+        let mut includes = Vec::new();
+        let mut block = self.block(&mut lexer, Some(&mut includes))?;
+
+        for (directive, path, offset) in includes {
+            if depth >= MAX_INCLUDE_DEPTH {
+                return Err(lexer.error_at(ErrorKind::IncludeDepth, offset));
+            }
+
+            let included = self
+                .include
+                .as_mut()
+                .and_then(|include| include(&path))
+                .ok_or_else(|| lexer.error_at(ErrorKind::UnresolvedInclude(path), offset))?;
+
+            let included = self.parse_file(&included, depth + 1)?;
+
+            match directive {
+                Directive::Include => block.pairs.extend(included.pairs),
+                Directive::Base => merge(&mut block, included),
+            }
+        }
+
+        Ok(block)
+    }
+
+    /// Parse pairs until `}`, or the end of the text when `includes` is provided (top-level).
+    fn block(
+        &mut self,
+        lexer: &mut Lexer<'_>,
+        mut includes: Option<&mut Vec<(Directive, String, usize)>>,
+    ) -> Result<Block, Error> {
+        let is_top_level = includes.is_some();
+        let mut block = Block::new();
+
+        loop {
+            let key = lexer.next()?;
+            let offset = lexer.start();
+            let key = match key {
+                Token::Eof if is_top_level => return Ok(block),
+                Token::Eof => return Err(lexer.error(ErrorKind::UnexpectedEof)),
+                Token::Close if is_top_level => {
+                    return Err(lexer.error_at(ErrorKind::UnexpectedCloseBrace, offset))
+                }
+                Token::Close => return Ok(block),
+                Token::Open => return Err(lexer.error_at(ErrorKind::MissingValue, offset)),
+                Token::String(key) => key,
+            };
+
+            if let Some(includes) = includes.as_deref_mut() {
+                if !key.quoted && key.text.starts_with('#') {
+                    let directive = match key.text.to_ascii_lowercase().as_str() {
+                        "#include" => Directive::Include,
+                        "#base" => Directive::Base,
+                        _ => {
+                            return Err(
+                                lexer.error_at(ErrorKind::InvalidDirective(key.text), offset)
+                            )
+                        }
+                    };
+
+                    let path = match lexer.next()? {
+                        Token::String(path) => path.text,
+                        _ => return Err(lexer.error(ErrorKind::UnexpectedEof)),
+                    };
+
+                    includes.push((directive, path, offset));
+
+                    continue;
+                }
+            }
+
+            let mut accepted = true;
+            let mut value = lexer.next()?;
+
+            // a conditional may sit between a key and its block
+            if let Token::String(Str {
+                conditional: true,
+                text,
+                ..
+            }) = &value
+            {
+                accepted = self.evaluate(lexer, text)?;
+                value = lexer.next()?;
+            }
+
+            let value = match value {
+                Token::Eof => return Err(lexer.error(ErrorKind::UnexpectedEof)),
+                Token::Close => return Err(lexer.error(ErrorKind::MissingValue)),
+                Token::Open => Value::Block(self.block(lexer, None)?),
+                Token::String(value) => {
+                    // or after a string value
+                    let mut peek = lexer.clone();
+
+                    if let Token::String(Str {
+                        conditional: true,
+                        text,
+                        ..
+                    }) = peek.next()?
+                    {
+                        accepted = self.evaluate(lexer, &text)?;
+                        *lexer = peek;
+                    }
+
+                    Value::String(value.text)
+                }
+            };
+
+            if accepted {
+                block.pairs.push(Pair {
+                    key: key.text,
+                    value,
+                });
+            }
+        }
+    }
+
+    #[inline]
+    fn evaluate(&self, lexer: &Lexer<'_>, conditional: &str) -> Result<bool, Error> {
+        self.conditions
+            .evaluate(conditional)
+            .ok_or_else(|| lexer.error(ErrorKind::InvalidConditional(conditional.into())))
+    }
+}
+
+impl<'a> Default for Parser<'a> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Add every pair of `base` which `block` doesn't already have, recursing into blocks both have.
+fn merge(block: &mut Block, base: Block) {
+    for pair in base.pairs {
+        match block.get_mut(&pair.key) {
+            Some(Value::Block(existing)) => {
+                if let Value::Block(base) = pair.value {
+                    merge(existing, base);
+                }
+            }
+            Some(Value::String(_)) => {}
+            None => block.pairs.push(pair),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Directive {
+    Include,
+    Base,
+}
+
+#[derive(Debug)]
+struct Str {
+    text: String,
+    quoted: bool,
+    conditional: bool,
+}
+
+#[derive(Debug)]
+enum Token {
+    Open,
+    Close,
+    String(Str),
+    Eof,
+}
+
+#[derive(Clone)]
+struct Lexer<'a> {
+    text: &'a str,
+    offset: usize,
+    start: usize,
+    escapes: bool,
+}
+
+impl<'a> Lexer<'a> {
+    #[inline]
+    fn new(text: &'a str, escapes: bool) -> Self {
+        Self {
+            text,
+            offset: 0,
+            start: 0,
+            escapes,
+        }
+    }
+
+    /// Where the last token started.
+    #[inline]
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.text[self.offset..].chars().next()
+    }
+
+    #[inline]
+    fn bump(&mut self) -> Option<char> {
+        let char = self.peek()?;
+
+        self.offset += char.len_utf8();
+
+        Some(char)
+    }
+
+    /// Skip whitespace and `//` comments.
+    fn skip_trivia(&mut self) {
+        loop {
+            let rest = &self.text[self.offset..];
+            let trimmed = rest.trim_start();
+
+            self.offset += rest.len() - trimmed.len();
+
+            if !trimmed.starts_with("//") {
+                break;
+            }
+
+            self.offset += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+    }
+
+    fn next(&mut self) -> Result<Token, Error> {
+        self.skip_trivia();
+
+        let start = self.offset;
+
+        self.start = start;
+
+        let char = match self.bump() {
+            Some(char) => char,
+            None => return Ok(Token::Eof),
+        };
+
+        match char {
+            '{' => return Ok(Token::Open),
+            '}' => return Ok(Token::Close),
+            '"' => return self.quoted(start),
+            _ => {}
+        }
+
+        let mut is_conditional_start = char == '[';
+        let mut conditional = false;
+
+        // same rules as the engine: unquoted tokens end at whitespace or a control character, and
+        // are conditionals if they contain `[` followed later by `]`
+        while let Some(char) = self.peek() {
+            if matches!(char, '"' | '{' | '}') || char.is_whitespace() {
+                break;
+            }
+
+            if char == '[' {
+                is_conditional_start = true;
+            }
+
+            if char == ']' && is_conditional_start {
+                conditional = true;
+            }
+
+            self.bump();
+        }
+
+        Ok(Token::String(Str {
+            text: self.text[start..self.offset].into(),
+            quoted: false,
+            conditional,
+        }))
+    }
+
+    fn quoted(&mut self, start: usize) -> Result<Token, Error> {
+        let mut text = String::new();
+
+        loop {
+            let char = match self.bump() {
+                Some(char) => char,
+                None => return Err(self.error_at(ErrorKind::UnterminatedString, start)),
+            };
+
+            match char {
+                '"' => break,
+                '\\' if self.escapes => {
+                    let escaped = match self.bump() {
+                        Some(char) => char,
+                        None => return Err(self.error_at(ErrorKind::UnterminatedString, start)),
+                    };
+
+                    match unescape(escaped) {
+                        Some(char) => text.push(char),
+                        None => {
+                            text.push('\\');
+                            text.push(escaped);
+                        }
+                    }
+                }
+                char => text.push(char),
+            }
+        }
+
+        Ok(Token::String(Str {
+            text,
+            quoted: true,
+            conditional: false,
+        }))
+    }
+
+    #[inline]
+    fn error(&self, kind: ErrorKind) -> Error {
+        self.error_at(kind, self.offset)
+    }
+
+    fn error_at(&self, kind: ErrorKind, offset: usize) -> Error {
+        let before = &self.text[..offset.min(self.text.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+
+        Error { kind, line, column }
+    }
+}
+
+/// Map the character after `\` to what it represents.
+#[inline]
+fn unescape(char: char) -> Option<char> {
+    let char = match char {
+        'n' => '\n',
+        't' => '\t',
+        'v' => '\x0b',
+        'b' => '\x08',
+        'r' => '\r',
+        'f' => '\x0c',
+        'a' => '\x07',
+        '\\' => '\\',
+        '?' => '?',
+        '\'' => '\'',
+        '"' => '"',
+        _ => return None,
+    };
+
+    Some(char)
+}
+
+/// Recursive descent evaluator for conditional expressions.
+struct Evaluator<'a> {
+    conditions: &'a Conditions,
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Evaluator<'a> {
+    #[inline]
+    fn skip_whitespace(&mut self) {
+        while matches!(self.bytes.get(self.offset), Some(byte) if byte.is_ascii_whitespace()) {
+            self.offset += 1;
+        }
+    }
+
+    #[inline]
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+
+        let is_match = self.bytes[self.offset..].starts_with(token.as_bytes());
+
+        if is_match {
+            self.offset += token.len();
+        }
+
+        is_match
+    }
+
+    fn or(&mut self) -> Option<bool> {
+        let mut result = self.and()?;
+
+        while self.eat("||") {
+            result |= self.and()?;
+        }
+
+        Some(result)
+    }
+
+    fn and(&mut self) -> Option<bool> {
+        let mut result = self.unary()?;
+
+        while self.eat("&&") {
+            result &= self.unary()?;
+        }
+
+        Some(result)
+    }
+
+    fn unary(&mut self) -> Option<bool> {
+        if self.eat("!") {
+            return Some(!self.unary()?);
+        }
+
+        if self.eat("(") {
+            let result = self.or()?;
+
+            return self.eat(")").then_some(result);
+        }
+
+        self.skip_whitespace();
+
+        let start = self.offset;
+
+        if self.bytes.get(start) != Some(&b'$') {
+            return None;
+        }
+
+        self.offset += 1;
+
+        while matches!(
+            self.bytes.get(self.offset),
+            Some(byte) if byte.is_ascii_alphanumeric() || *byte == b'_'
+        ) {
+            self.offset += 1;
+        }
+
+        if self.offset == start + 1 {
+            return None;
+        }
+
+        let symbol = core::str::from_utf8(&self.bytes[start..self.offset]).ok()?;
+
+        Some(self.conditions.is_defined(symbol))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs() {
+        let block = from_str("$basetexture white\n\"$envmap\" \"env_cubemap\"").unwrap();
+
+        assert_eq!(block.len(), 2);
+        assert_eq!(block.get_str("$basetexture"), Some("white"));
+        assert_eq!(block.get_str("$ENVMAP"), Some("env_cubemap"));
+    }
+
+    #[test]
+    fn nested() {
+        let block = from_str(
+            "VertexLitGeneric {
+                proxies {
+                    texturescroll {
+                        texturescrollrate 0.6
+                    }
+                }
+            }",
+        )
+        .unwrap();
+
+        let rate = block.find("VertexLitGeneric/proxies/texturescroll/texturescrollrate");
+
+        assert_eq!(rate.and_then(Value::as_float), Some(0.6));
+    }
+
+    #[test]
+    fn comments() {
+        let block = from_str(
+            "// leading comment
+            key value // trailing comment
+            // key2 value2",
+        )
+        .unwrap();
+
+        assert_eq!(block.len(), 1);
+        assert_eq!(block.get_str("key"), Some("value"));
+    }
+
+    #[test]
+    fn escapes() {
+        let block = from_str(r#""key" "a \"quoted\"\tvalue\\""#).unwrap();
+
+        assert_eq!(block.get_str("key"), Some("a \"quoted\"\tvalue\\"));
+
+        let block = Parser::new()
+            .escapes(false)
+            .parse(r#""key" "C:\path\to""#)
+            .unwrap();
+
+        assert_eq!(block.get_str("key"), Some(r"C:\path\to"));
+    }
+
+    #[test]
+    fn unquoted_vectors_split() {
+        // the engine splits unquoted tokens on whitespace, vectors must be quoted
+        let block = from_str("$color [.1 .2 .3]").unwrap();
+
+        assert_eq!(block.get_str("$color"), Some("[.1"));
+        assert_eq!(block.get_str(".2"), Some(".3]"));
+
+        let block = from_str("$color \"[.1 .2 .3]\"").unwrap();
+
+        assert_eq!(block.get_str("$color"), Some("[.1 .2 .3]"));
+    }
+
+    #[test]
+    fn conditionals() {
+        let block = from_str(
+            "a 1 [$LINUX]
+            b 2 [$WIN32]
+            c 3 [!$WIN32]
+            d [$X360||$POSIX] { e 4 }
+            f [$LINUX&&!($OSX||$X360)] { g 5 }",
+        )
+        .unwrap();
+
+        assert_eq!(block.get_str("a"), Some("1"));
+        assert_eq!(block.get("b"), None);
+        assert_eq!(block.get_str("c"), Some("3"));
+        assert_eq!(block.find("d/e").and_then(Value::as_int), Some(4));
+        assert_eq!(block.find("f/g").and_then(Value::as_int), Some(5));
+
+        let windows = Conditions::none().define("$WIN32");
+        let block = Parser::new()
+            .conditions(windows)
+            .parse("a 1 [$LINUX]\nb 2 [$WIN32]")
+            .unwrap();
+
+        assert_eq!(block.get("a"), None);
+        assert_eq!(block.get_str("b"), Some("2"));
+    }
+
+    #[test]
+    fn invalid_conditional() {
+        let error = from_str("a 1 [$LINUX&&]").unwrap_err();
+
+        assert_eq!(
+            error.kind,
+            ErrorKind::InvalidConditional("[$LINUX&&]".into())
+        );
+    }
+
+    #[test]
+    fn include_and_base() {
+        let files = |path: &str| match path {
+            "included.vdf" => Some("extra { x 1 }".to_string()),
+            "base.vdf" => Some("root { a base\nc base }".to_string()),
+            _ => None,
+        };
+
+        let block = Parser::new()
+            .include(files)
+            .parse(
+                "#include included.vdf
+                #base \"base.vdf\"
+                root { a own\nb own }",
+            )
+            .unwrap();
+
+        let root = block.get_block("root").unwrap();
+
+        assert_eq!(root.get_str("a"), Some("own"));
+        assert_eq!(root.get_str("b"), Some("own"));
+        assert_eq!(root.get_str("c"), Some("base"));
+        assert_eq!(block.find("extra/x").and_then(Value::as_int), Some(1));
+    }
+
+    #[test]
+    fn unresolved_include() {
+        let error = from_str("#include \"missing.vdf\"").unwrap_err();
+
+        assert_eq!(
+            error.kind,
+            ErrorKind::UnresolvedInclude("missing.vdf".into())
+        );
+    }
+
+    #[test]
+    fn include_cycle() {
+        let error = Parser::new()
+            .include(|_path| Some("#include self.vdf".to_string()))
+            .parse("#include self.vdf")
+            .unwrap_err();
+
+        assert_eq!(error.kind, ErrorKind::IncludeDepth);
+    }
+
+    #[test]
+    fn errors() {
+        let error = from_str("a {\n  b c").unwrap_err();
+
+        assert_eq!(error.kind, ErrorKind::UnexpectedEof);
+
+        let error = from_str("a b\n}").unwrap_err();
+
+        assert_eq!(error.kind, ErrorKind::UnexpectedCloseBrace);
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = from_str("a {\n b }\n }").unwrap_err();
+
+        assert_eq!(error.kind, ErrorKind::MissingValue);
+
+        let error = from_str("a \"b").unwrap_err();
+
+        assert_eq!(error.kind, ErrorKind::UnterminatedString);
+        assert_eq!((error.line, error.column), (1, 3));
+    }
+}
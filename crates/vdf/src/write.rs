@@ -0,0 +1,92 @@
+use crate::{Block, Value};
+use core::fmt::{self, Write};
+
+/// Write the pairs of `block`, indented by `depth` tabs.
+pub(crate) fn block(fmt: &mut fmt::Formatter, block: &Block, depth: usize) -> fmt::Result {
+    for pair in block.iter() {
+        indent(fmt, depth)?;
+        quoted(fmt, &pair.key)?;
+
+        match &pair.value {
+            Value::String(value) => {
+                fmt.write_char('\t')?;
+                quoted(fmt, value)?;
+                fmt.write_char('\n')?;
+            }
+            Value::Block(nested) => {
+                fmt.write_char('\n')?;
+                indent(fmt, depth)?;
+                fmt.write_str("{\n")?;
+                self::block(fmt, nested, depth + 1)?;
+                indent(fmt, depth)?;
+                fmt.write_str("}\n")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn indent(fmt: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+    for _ in 0..depth {
+        fmt.write_char('\t')?;
+    }
+
+    Ok(())
+}
+
+/// Write `string` quoted, escaping anything the parser would otherwise interpret.
+fn quoted(fmt: &mut fmt::Formatter, string: &str) -> fmt::Result {
+    fmt.write_char('"')?;
+
+    for char in string.chars() {
+        match char {
+            '"' => fmt.write_str("\\\"")?,
+            '\\' => fmt.write_str("\\\\")?,
+            '\n' => fmt.write_str("\\n")?,
+            '\t' => fmt.write_str("\\t")?,
+            '\r' => fmt.write_str("\\r")?,
+            char => fmt.write_char(char)?,
+        }
+    }
+
+    fmt.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_str, Block};
+
+    #[test]
+    fn write() {
+        let block = Block::new()
+            .with("$basetexture", "white")
+            .with("proxies", Block::new().with("texturescroll", Block::new()));
+
+        assert_eq!(
+            block.to_string(),
+            "\"$basetexture\"\t\"white\"\n\
+             \"proxies\"\n\
+             {\n\
+             \t\"texturescroll\"\n\
+             \t{\n\
+             \t}\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let block = Block::new()
+            .with("$envmaptint", "[.4 .6 .7]")
+            .with("quote", "a \"quoted\"\tvalue\\")
+            .with("empty", "")
+            .with(
+                "proxies",
+                Block::new().with("sine", Block::new().with("sineperiod", "2")),
+            );
+
+        assert_eq!(from_str(&block.to_string()), Ok(block));
+    }
+}
@@ -1,8 +1,8 @@
-use crate::{Networked, State};
+use crate::{Networked, State, Weapon};
 use cake::ffi::vtable;
 use elysium_math::{Matrix3x4, Vec3};
-use elysium_sdk::entity::{Networkable, ObserverMode, Renderable};
-use elysium_sdk::{object_validate, vtable_validate};
+use elysium_sdk::entity::{EntityHandle, Networkable, ObserverMode, Renderable};
+use elysium_sdk::{object_validate, vtable_validate, AnimationLayer, EntityList, UtlVec};
 
 #[repr(C)]
 struct VTable {
@@ -88,6 +88,12 @@ impl Entity {
         unsafe { *(self.vtable.origin)(self) }
     }
 
+    /// overwrite the origin, without any of the engine's bookkeeping
+    #[inline]
+    pub fn set_origin(&self, origin: Vec3) {
+        unsafe { *(self.vtable.origin)(self).as_mut() = origin }
+    }
+
     #[inline]
     pub fn is_player(&self) -> bool {
         unsafe { (self.vtable.is_player)(self) }
@@ -100,7 +106,7 @@ impl Entity {
 
     /// networked variable
     #[inline]
-    fn networked<T, F>(&self, f: F) -> &mut T
+    pub(crate) fn networked<T, F>(&self, f: F) -> &mut T
     where
         F: Fn(&Networked) -> usize,
     {
@@ -124,6 +130,23 @@ impl Entity {
         unsafe { *self.render_mode_address().byte_add(1).cast() }
     }
 
+    #[inline]
+    pub fn simulation_time(&self) -> f32 {
+        *self.networked(|networked| networked.base_entity.simulation_time)
+    }
+
+    /// only for base_animatings, empty if the offset wasn't found
+    #[inline]
+    pub fn animation_layers(&self) -> &[AnimationLayer] {
+        if State::get().networked.base_animating.animation_layers == 0 {
+            return &[];
+        }
+
+        self.networked::<UtlVec<AnimationLayer>, _>(|networked| {
+            networked.base_animating.animation_layers
+        })
+    }
+
     /// only for base_players
     #[inline]
     unsafe fn is_dead_address(&self) -> *const u8 {
@@ -140,12 +163,36 @@ impl Entity {
         }
     }
 
+    /// only for base_players
+    #[inline]
+    pub fn health(&self) -> i32 {
+        *self.networked(|networked| networked.base_player.health)
+    }
+
+    /// only for base_players
+    #[inline]
+    pub fn tick_base(&self) -> i32 {
+        *self.networked(|networked| networked.base_player.tick_base)
+    }
+
     /// only for base_players
     #[inline]
     pub fn velocity(&self) -> Vec3 {
         *self.networked(|networked| networked.base_player.velocity)
     }
 
+    /// only for base_players
+    #[inline]
+    pub fn set_velocity(&self, velocity: Vec3) {
+        *self.networked(|networked| networked.base_player.velocity) = velocity;
+    }
+
+    /// only for players
+    #[inline]
+    pub fn eye_angle(&self) -> Vec3 {
+        *self.networked(|networked| networked.player.eye_angle)
+    }
+
     /// only for players
     #[inline]
     pub fn is_scoped(&self) -> bool {
@@ -164,6 +211,12 @@ impl Entity {
         *self.networked(|networked| networked.player.flags)
     }
 
+    /// only for players
+    #[inline]
+    pub fn set_flags(&self, flags: i32) {
+        *self.networked(|networked| networked.player.flags) = flags;
+    }
+
     /// only for players
     #[inline]
     pub fn armor(&self) -> i32 {
@@ -176,6 +229,34 @@ impl Entity {
         *self.networked(|networked| networked.player.has_helmet)
     }
 
+    /// only for players
+    #[inline]
+    pub fn active_weapon_handle(&self) -> EntityHandle {
+        *self.networked(|networked| networked.player.weapon)
+    }
+
+    /// only for players
+    #[inline]
+    pub fn active_weapon<'a>(&self, entity_list: &'a EntityList) -> Option<&'a Weapon> {
+        let weapon = entity_list.resolve(self.active_weapon_handle())?;
+
+        Some(unsafe { weapon.cast() })
+    }
+
+    /// only for players
+    #[inline]
+    pub fn observer_target_handle(&self) -> EntityHandle {
+        *self.networked(|networked| networked.player.observer)
+    }
+
+    /// only for players
+    #[inline]
+    pub fn observer_target<'a>(&self, entity_list: &'a EntityList) -> Option<&'a Entity> {
+        let target = entity_list.resolve(self.observer_target_handle())?;
+
+        Some(unsafe { target.cast() })
+    }
+
     /// only for base players
     #[inline]
     pub fn view_offset(&self) -> Vec3 {
@@ -2,20 +2,9 @@ use crate::{Entity, State};
 use elysium_math::Vec3;
 use elysium_sdk::convar::Vars;
 use elysium_sdk::entity::{Networkable, ObserverMode, Renderable};
-use elysium_sdk::{Command, EntityList, HitGroup};
+use elysium_sdk::{Buttons, Command, EntityList, HitGroup, WeaponInfo};
 use std::arch::asm;
 
-const IN_FORWARD: i32 = 1 << 3;
-const IN_BACKWARD: i32 = 1 << 4;
-
-// yes.
-const IN_LEFTWARD: i32 = 1 << 7;
-const IN_RIGHTWARD: i32 = 1 << 8;
-
-const IN_ATTACK: i32 = 1 << 0;
-const IN_BULLRUSH: i32 = 1 << 22;
-const IN_JUMP: i32 = 1 << 1;
-
 const ON_GROUND: i32 = 1 << 0;
 
 fn normalize(vec: &mut Vec3) {
@@ -61,30 +50,42 @@ fn fix_movement(command: &mut Command, wish_angle: Vec3) {
 }
 
 // TODO: find out how the fuck to fix the legs being spaz
-//
-// also seems like left/right doesnt work
 #[inline]
 fn leg_animation_walk(command: &mut Command) {
-    command.state ^= IN_FORWARD | IN_BACKWARD | IN_RIGHTWARD | IN_LEFTWARD;
+    let mut buttons = command.buttons();
+
+    buttons.toggle(Buttons::ANY_MOVE);
+    command.set_buttons(buttons);
 }
 
+/// damage dealt to `entity` by a bullet of `weapon_info` hitting `group`
 #[allow(dead_code)]
 #[inline]
 fn scale_damage(
     entity: &Entity,
     group: HitGroup,
-    weapon_armor_ratio: f32,
-    mut base_damage: f32,
+    weapon_info: &WeaponInfo,
+    mut damage: f32,
 ) -> f32 {
-    base_damage *= group.damage_modifier();
+    // kevlar absorbs half of what it doesn't let through, until it runs out
+    const ARMOR_BONUS: f32 = 0.5;
 
-    if entity.armor() > 0 {
-        if group.is_head() && entity.has_helmet() {
-            base_damage *= weapon_armor_ratio * 0.5;
+    damage *= group.damage_modifier();
+
+    let armor = entity.armor() as f32;
+
+    if armor > 0.0 && group.is_armored(entity.has_helmet()) {
+        let armor_ratio = weapon_info.armor_ratio * 0.5;
+        let mut new_damage = damage * armor_ratio;
+
+        if (damage - new_damage) * ARMOR_BONUS > armor {
+            new_damage = damage - armor / ARMOR_BONUS;
         }
+
+        damage = new_damage;
     }
 
-    base_damage
+    damage
 }
 
 #[allow(dead_code)]
@@ -115,12 +116,14 @@ unsafe fn do_create_move(command: &mut Command, local: &Entity, send_packet: &mu
         return;
     }
 
+    let mut buttons = command.buttons();
+
     if local_vars.was_attacking {
-        command.state &= !IN_ATTACK;
+        buttons.remove(Buttons::ATTACK);
     }
 
-    let do_attack = (command.state & IN_ATTACK) != 0;
-    let do_jump = (command.state & IN_JUMP) != 0;
+    let do_attack = buttons.in_attack();
+    let do_jump = buttons.in_jump();
     let on_ground = (local.flags() & ON_GROUND) != 0;
 
     local_vars.was_attacking = do_attack;
@@ -128,10 +131,12 @@ unsafe fn do_create_move(command: &mut Command, local: &Entity, send_packet: &mu
 
     if do_jump {
         if !on_ground && !local_vars.was_on_ground {
-            command.state &= !IN_JUMP;
+            buttons.remove(Buttons::JUMP);
         }
     }
 
+    command.set_buttons(buttons);
+
     let side = if command.command % 3 != 0 { 1.0 } else { -1.0 };
 
     if (local.flags() & ON_GROUND) == 0 {
@@ -211,7 +216,8 @@ unsafe fn do_create_move(command: &mut Command, local: &Entity, send_packet: &mu
     }
 
     command.view_angle = command.view_angle.sanitize_angle();
-    command.state |= IN_BULLRUSH;
+    buttons.insert(Buttons::BULLRUSH);
+    command.set_buttons(buttons);
 
     fix_movement(command, state.view_angle);
     //leg_animation_walk(command);
@@ -239,6 +245,8 @@ pub unsafe extern "C" fn create_move(
         return false;
     }
 
+    crate::record::update(command, local);
+
     let rbp: *mut *mut bool;
 
     asm!("mov {}, rbp", out(reg) rbp, options(nostack));
@@ -247,7 +255,30 @@ pub unsafe extern "C" fn create_move(
 
     *send_packet = command.command % 2 != 0;
 
-    do_create_move(command, local, send_packet);
+    // auto strafe and the movement fix rewrite this, and the stats are of the player's strafes
+    let side_move = command.movement.y;
+
+    // playback replays commands as they were sent, our edits included
+    if !state.recorder.is_playing() {
+        do_create_move(command, local, send_packet);
+    }
+
+    crate::record::push(command, local);
+
+    let predicted = crate::prediction::run(command, local);
+
+    if let Some(interfaces) = state.interfaces.as_ref() {
+        crate::movement::update(
+            &mut state.movement,
+            &mut state.velocity_graph,
+            state.menu.as_mut(),
+            &interfaces.trace,
+            command,
+            side_move,
+            predicted,
+            local,
+        );
+    }
 
     if *send_packet {
         state.local.view_angle = command.view_angle;
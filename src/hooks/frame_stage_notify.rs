@@ -1,64 +1,66 @@
-use crate::state::Local;
+use crate::state::{self, Local, Players, Record, VarOverrides, ANIMATION_LAYERS};
 use crate::{Entity, State};
 use elysium_math::Vec3;
-use elysium_sdk::client::Class;
 use elysium_sdk::convar::Vars;
-use elysium_sdk::entity::EntityId;
-use elysium_sdk::{Engine, EntityList, Frame, Globals, Input, Interfaces};
+use elysium_sdk::entity::{FogController, TonemapController};
+use elysium_sdk::model::UsedBy;
+use elysium_sdk::{AnimationLayer, Engine, EntityList, Frame, Globals, Input, Interfaces};
+use std::mem;
 
-fn update_vars(vars: &Vars, engine: &Engine) {
+/// Force config variables, remembering what the player had them set to.
+fn update_vars(overrides: &mut VarOverrides, vars: &Vars, engine: &Engine) {
     // misc
-    vars.allow_developer.write(true);
-    vars.fast_render.write(!engine.is_in_game());
-    vars.cheats.write(true);
-    vars.developer.write(true);
+    overrides.set(vars.allow_developer, true);
+    overrides.set(vars.fast_render, !engine.is_in_game());
+    overrides.set(vars.cheats, true);
+    overrides.set(vars.developer, true);
 
     // useful
-    vars.show_grenade_path.write(true);
+    overrides.set(vars.show_grenade_path, true);
 
     // annoying
-    vars.auto_help.write(false);
-    vars.show_help.write(false);
+    overrides.set(vars.auto_help, false);
+    overrides.set(vars.show_help, false);
 
     // these disable when true
-    vars.engine_sleep.write(true);
-    vars.html_motd.write(true);
-    vars.freeze_cam.write(true);
-    vars.panorama_blur.write(true);
+    overrides.set(vars.engine_sleep, true);
+    overrides.set(vars.html_motd, true);
+    overrides.set(vars.freeze_cam, true);
+    overrides.set(vars.panorama_blur, true);
 
     // p100
-    //vars.hud.write(false);
+    //overrides.set(vars.hud, false);
 
     // shadows
-    //vars.csm.write(false);
-    vars.csm_shadows.write(false);
-    vars.feet_shadows.write(false);
-    vars.prop_shadows.write(false);
-    vars.rope_shadows.write(false);
-    vars.shadows.write(false);
-    vars.skybox3d.write(false);
-    vars.viewmodel_shadows.write(false);
-    vars.world_shadows.write(false);
+    //overrides.set(vars.csm, false);
+    overrides.set(vars.csm_shadows, false);
+    overrides.set(vars.feet_shadows, false);
+    overrides.set(vars.prop_shadows, false);
+    overrides.set(vars.rope_shadows, false);
+    overrides.set(vars.shadows, false);
+    overrides.set(vars.skybox3d, false);
+    overrides.set(vars.viewmodel_shadows, false);
+    overrides.set(vars.world_shadows, false);
 
     // useless objects
-    vars.ropes.write(false);
-    vars.sprites.write(false);
+    overrides.set(vars.ropes, false);
+    overrides.set(vars.sprites, false);
 
     // translucent things
-    vars.water_fog.write(false);
+    overrides.set(vars.water_fog, false);
 
     // overlay
-    vars.underwater_overlay.write(false);
+    overrides.set(vars.underwater_overlay, false);
 
     // effects
-    vars.alien_blood.write(false);
-    vars.human_blood.write(false);
-    vars.decals.write(false);
-    vars.jiggle_bones.write(false);
-    //vars.rain.write(false);
+    overrides.set(vars.alien_blood, false);
+    overrides.set(vars.human_blood, false);
+    overrides.set(vars.decals, false);
+    overrides.set(vars.jiggle_bones, false);
+    //overrides.set(vars.rain, false);
 
     // phsyics
-    vars.physics_timescale.write(0.5);
+    overrides.set(vars.physics_timescale, 0.5);
 }
 
 /// Override fog controller properties.
@@ -111,26 +113,108 @@ fn update_thirdperson(globals: &Globals, input: &Input, local_vars: &mut Local,
 /// Iterate entities and update entity specific things.
 #[inline]
 unsafe fn update_entities(entity_list: &EntityList) {
-    for index in entity_list.non_player_range() {
-        let entity = entity_list.entity(index);
+    for entity in entity_list.iter().non_players() {
+        if entity.is::<FogController>() {
+            update_fog(entity.cast());
+        } else if entity.is::<TonemapController>() {
+            update_tonemap(entity.cast());
+        }
+    }
+}
 
-        if entity.is_null() {
-            continue;
+/// Snapshot every player slot, so everything else reads one consistent view of the frame.
+#[inline]
+unsafe fn update_players(
+    players: &mut Players,
+    engine: &Engine,
+    entity_list: &EntityList,
+    globals: &Globals,
+) {
+    for index in entity_list.player_range() {
+        let player = match players.get_mut(index) {
+            Some(player) => player,
+            None => continue,
+        };
+
+        let entity = entity_list.entity(index).cast::<Entity>();
+        let info = engine.get_player_info(index);
+
+        let (entity, info) = match (entity.as_ref(), info) {
+            (Some(entity), Some(info)) => (entity, info),
+            // player left, or the slot was never used
+            _ => {
+                if player.exists {
+                    player.clear();
+                }
+
+                continue;
+            }
+        };
+
+        // someone else took the slot
+        if player.exists && player.id != info.steam_id {
+            player.clear();
         }
 
-        let entity = &*entity.cast::<Entity>();
-        let class = entity.client_class();
+        player.exists = true;
+        player.index = index;
+        player.id = info.steam_id;
+        player.is_bot = info.fake_player;
+        player.is_dormant = entity.is_dormant();
 
-        if class.is_null() {
+        if player.name != info.name() {
+            player.name.clear();
+            player.name.push_str(info.name());
+        }
+
+        // dormant players keep their last known state
+        if player.is_dormant {
             continue;
         }
 
-        let class = &*class.cast::<Class>();
+        player.health = entity.health();
+        player.origin = entity.origin();
+        player.update_time = globals.current_time;
 
-        match class.entity_id {
-            EntityId::CFogController => update_fog(entity),
-            EntityId::CEnvTonemapController => update_tonemap(entity),
-            _ => {}
+        if let Some(weapon) = entity.active_weapon(entity_list) {
+            player.magazine_ammo = weapon.magazine();
+            player.total_ammo = weapon.reserve_ammo();
+            player.next_attack_available_after = weapon.next_attack_available_after();
+            player.revolver_cock_time = weapon.revolver_cock_time();
+        }
+
+        entity.setup_bones(
+            &mut player.bones[..],
+            UsedBy::Anything as i32,
+            globals.current_time,
+        );
+
+        // only record actual server updates
+        let simulation_time = entity.simulation_time();
+        let tick = state::time_to_ticks(simulation_time, globals.interval_per_tick);
+        let is_new = player
+            .history
+            .latest()
+            .map(|record| record.simulation_time != simulation_time)
+            .unwrap_or(true);
+
+        if is_new {
+            let mut layers = [AnimationLayer::zero(); ANIMATION_LAYERS];
+
+            for (layer, current) in layers.iter_mut().zip(entity.animation_layers()) {
+                *layer = *current;
+            }
+
+            player.history.push(Record {
+                tick,
+                simulation_time,
+                origin: player.origin,
+                velocity: entity.velocity(),
+                eye_angle: entity.eye_angle(),
+                flags: entity.flags(),
+                bones: player.bones,
+                layers,
+            });
         }
     }
 }
@@ -148,6 +232,15 @@ pub unsafe extern "C" fn frame_stage_notify(this: *const u8, frame: i32) {
     } = state.interfaces.as_ref().unwrap_unchecked();
 
     let hooks = state.hooks.as_ref().unwrap_unchecked();
+
+    // requested from within a console command, which can't unregister itself
+    if mem::take(&mut state.unload_requested) {
+        crate::unload();
+        (hooks.frame_stage_notify)(this, frame);
+
+        return;
+    }
+
     let globals = state.globals.as_mut().unwrap_unchecked();
     let input = state.input.as_mut().unwrap_unchecked();
     let vars = state.vars.as_ref().unwrap_unchecked();
@@ -157,8 +250,18 @@ pub unsafe extern "C" fn frame_stage_notify(this: *const u8, frame: i32) {
 
     state.view_angle = engine.view_angle();
 
-    // force vars
-    update_vars(vars, engine);
+    let force_vars = state
+        .menu
+        .as_ref()
+        .map(|menu| menu.controls().force_vars())
+        .unwrap_or(true);
+
+    // force vars, or put back what the player had if that's been turned off
+    if force_vars {
+        update_vars(&mut state.var_overrides, vars, engine);
+    } else {
+        state.var_overrides.restore_all();
+    }
 
     if engine.is_in_game() {
         input_system.enable_input(is_menu_open);
@@ -173,6 +276,20 @@ pub unsafe extern "C" fn frame_stage_notify(this: *const u8, frame: i32) {
         input_system.cursor_visible(true);
     }
 
+    crate::session::update(engine);
+    crate::network::update(
+        &mut state.network,
+        state.menu.as_mut(),
+        engine,
+        globals.tick_count,
+    );
+
+    if !engine.is_in_game() {
+        state.players.clear();
+    } else if let Frame::RenderStart = frame {
+        update_players(&mut state.players, engine, entity_list, globals);
+    }
+
     local_vars.player = entity_list.local_player(engine).cast();
 
     if local_vars.player.is_null() {
@@ -49,14 +49,28 @@ pub unsafe extern "C" fn swap_window(window: *mut sdl2_sys::SDL_Window) {
         .menu
         .get_or_insert_with(|| Menu::new(context, viewport.clone()));
 
-    if state.menu_open.0 {
-        context.viewport(
-            0,
-            0,
-            state.window_size.width as i32,
-            state.window_size.height as i32,
-        );
+    context.viewport(
+        0,
+        0,
+        state.window_size.width as i32,
+        state.window_size.height as i32,
+    );
+
+    menu.draw_feed(context, viewport.clone());
 
+    if menu.controls().net_graph() {
+        menu.draw_net_graph(context, viewport.clone());
+    }
+
+    if menu.controls().movement() {
+        menu.draw_movement(context, viewport.clone());
+    }
+
+    if menu.controls().velocity() {
+        menu.draw_velocity(context, viewport.clone());
+    }
+
+    if state.menu_open.0 {
         menu.update(viewport.clone(), state.cursor_position);
         menu.draw(context, viewport);
     }
@@ -10,7 +10,8 @@
 // todo remove
 #![feature(const_maybe_uninit_zeroed)]
 
-use elysium_sdk::{LibraryKind, Vars};
+use elysium_sdk::convar::VarKind;
+use elysium_sdk::{Console, Events, LibraryKind, Vars};
 use state::Hooks;
 use std::path::Path;
 use std::{mem, thread};
@@ -18,8 +19,16 @@ use std::{mem, thread};
 pub use entity::Entity;
 pub use networked::Networked;
 pub use state::State;
+pub use weapon::Weapon;
 
 mod entity;
+mod log;
+mod movement;
+mod network;
+mod prediction;
+mod record;
+mod session;
+mod weapon;
 
 pub mod hooks;
 pub mod library;
@@ -56,12 +65,327 @@ unsafe extern "C" fn bootstrap() {
     thread::spawn(main);
 }
 
+// this is called by glibc before the library is unloaded from a process
+#[link_section = ".fini_array"]
+#[used]
+static SHUTDOWN: unsafe extern "C" fn() = shutdown;
+
+unsafe extern "C" fn shutdown() {
+    let state = State::get();
+
+    // the engine shuts its console down before the process exits, while on a `dlclose` it still
+    // holds our callbacks, commands and cvars, which are about to be unmapped
+    let is_console_alive = state
+        .interfaces
+        .as_ref()
+        .map(|interfaces| interfaces.console.base("echo").is_some())
+        .unwrap_or(false);
+
+    if is_console_alive {
+        unload();
+    } else {
+        // the rest of the game may already be gone, only put back what we've written over
+        unhook();
+    }
+}
+
+/// Undo everything we've done to the game.
+///
+/// Our memory stays mapped, as the game may still be executing our code.
+unsafe fn unload() {
+    let state = State::get();
+
+    // nothing to undo if we never got as far as loading interfaces
+    let interfaces = match state.interfaces.as_ref() {
+        Some(interfaces) => interfaces,
+        None => return,
+    };
+
+    let console = &interfaces.console;
+
+    state.var_overrides.restore_all();
+    state.var_callbacks.uninstall(console);
+    state.commands.unregister_all(console);
+    state.listeners.remove_all(&interfaces.events);
+
+    unhook();
+}
+
+#[inline]
+fn unhooked(name: &str) {
+    println!("elysium | unhooked \x1b[38;5;2m{name}\x1b[m");
+}
+
+/// Put `original` back at `ptr`, if `ptr` still points to `hook`.
+#[inline]
+unsafe fn restore<T>(ptr: *mut T, hook: T, original: T, name: &str) {
+    if mem::transmute_copy::<T, usize>(&ptr.read()) == mem::transmute_copy::<T, usize>(&hook) {
+        ptr.write(original);
+        unhooked(name);
+    }
+}
+
+/// Restore every function we've hooked.
+unsafe fn unhook() {
+    use state::{CreateMove, DrawModel, FrameStageNotify, OverrideView, PollEvent, SwapWindow};
+
+    let state = State::get();
+
+    let (hooks, interfaces) = match (state.hooks.as_ref(), state.interfaces.as_ref()) {
+        (Some(hooks), Some(interfaces)) => (hooks, interfaces),
+        _ => return,
+    };
+
+    let client = &interfaces.client;
+    let model_render = &interfaces.model_render;
+
+    let ptr = client.create_move_address().cast::<CreateMove>();
+
+    elysium_mem::unprotect(ptr, |ptr, prot| {
+        restore(ptr, hooks::create_move, hooks.create_move, "CreateMove");
+        prot
+    });
+
+    let ptr = model_render.draw_model_address().cast::<DrawModel>();
+
+    elysium_mem::unprotect(ptr, |ptr, prot| {
+        restore(ptr, hooks::draw_model, hooks.draw_model, "DrawModelExecute");
+        prot
+    });
+
+    let ptr = client
+        .frame_stage_notify_address()
+        .cast::<FrameStageNotify>();
+
+    elysium_mem::unprotect(ptr, |ptr, prot| {
+        restore(
+            ptr,
+            hooks::frame_stage_notify,
+            hooks.frame_stage_notify,
+            "FrameStageNotify",
+        );
+        prot
+    });
+
+    let ptr = client.override_view_address().cast::<OverrideView>();
+
+    elysium_mem::unprotect(ptr, |ptr, prot| {
+        restore(
+            ptr,
+            hooks::override_view,
+            hooks.override_view,
+            "OverrideView",
+        );
+        prot
+    });
+
+    // SDL may already be unloaded at exit, taking our hooks with it
+    let sdl = match link::Library::load("libSDL2-2.0.so.0") {
+        Ok(sdl) => sdl,
+        Err(_) => return,
+    };
+
+    let swap_window: *const SwapWindow = sdl.symbol_ptr("SDL_GL_SwapWindow").unwrap();
+    let swap_window = elysium_mem::next_abs_addr_mut(swap_window.as_mut());
+
+    restore(
+        swap_window,
+        hooks::swap_window,
+        hooks.swap_window,
+        "SDL_GL_SwapWindow",
+    );
+
+    let poll_event: *const PollEvent = sdl.symbol_ptr("SDL_PollEvent").unwrap();
+    let poll_event = elysium_mem::next_abs_addr_mut(poll_event.as_mut());
+
+    restore(
+        poll_event,
+        hooks::poll_event,
+        hooks.poll_event,
+        "SDL_PollEvent",
+    );
+}
+
+/// Register our console commands.
+fn register_commands(console: &Console) {
+    let state = State::get();
+
+    state.commands.command(
+        console,
+        "elysium_dump_netvars",
+        "Print networked variable offsets, optionally only for tables containing the argument.",
+        |args| {
+            let state = State::get();
+            let interfaces = unsafe { state.interfaces.as_ref().unwrap_unchecked() };
+            let filter = args.get(1).unwrap_or("");
+
+            networked::dump(&interfaces.client, &interfaces.console, filter);
+        },
+    );
+
+    state.commands.command(
+        console,
+        "elysium_refresh",
+        "Re-read networked variable offsets and take new snapshots of forced config variables.",
+        |_args| {
+            let state = State::get();
+            let interfaces = unsafe { state.interfaces.as_ref().unwrap_unchecked() };
+
+            state.networked.update(&interfaces.client);
+
+            // snapshots are taken again the next time vars are forced
+            state.var_overrides.restore_all();
+        },
+    );
+
+    state.commands.command(
+        console,
+        "elysium_thirdperson",
+        "Toggle thirdperson.",
+        |_args| {
+            let state = State::get();
+
+            state.local.thirdperson.0 ^= true;
+        },
+    );
+
+    state.network.loss_alert = Some(state.commands.var(
+        console,
+        "elysium_net_loss_alert",
+        "Packet loss percentage over which to alert, 0 to disable.",
+        "5",
+    ));
+
+    state.network.choke_alert = Some(state.commands.var(
+        console,
+        "elysium_net_choke_alert",
+        "Packet choke percentage over which to alert, 0 to disable.",
+        "10",
+    ));
+
+    state.recorder.max_drift = Some(state.commands.var(
+        console,
+        "elysium_playback_max_drift",
+        "How far, in units, playback may drift from the recorded path before it stops.",
+        "32",
+    ));
+
+    state.velocity_graph.width = Some(state.commands.var(
+        console,
+        "elysium_velocity_graph_width",
+        "Width of the velocity graph, in pixels.",
+        "320",
+    ));
+
+    state.velocity_graph.height = Some(state.commands.var(
+        console,
+        "elysium_velocity_graph_height",
+        "Height of the velocity graph, in pixels.",
+        "80",
+    ));
+
+    state.velocity_graph.x = Some(state.commands.var(
+        console,
+        "elysium_velocity_graph_x",
+        "Horizontal centre of the velocity graph, from 0 (left) to 1 (right).",
+        "0.5",
+    ));
+
+    state.velocity_graph.y = Some(state.commands.var(
+        console,
+        "elysium_velocity_graph_y",
+        "Vertical centre of the velocity graph, from 0 (top) to 1 (bottom).",
+        "0.8",
+    ));
+
+    state.velocity_graph.history = Some(state.commands.var(
+        console,
+        "elysium_velocity_graph_history",
+        "How many ticks of speed the velocity graph shows.",
+        "128",
+    ));
+
+    state.commands.command(
+        console,
+        "elysium_record",
+        "Record movement input to elysium/recordings/<name>.rec, until elysium_stop.",
+        |args| record::record(args.get(1).unwrap_or("practice")),
+    );
+
+    state.commands.command(
+        console,
+        "elysium_play",
+        "Play back elysium/recordings/<name>.rec, starting from where it was recorded.",
+        |args| record::play(args.get(1).unwrap_or("practice")),
+    );
+
+    state.commands.command(
+        console,
+        "elysium_stop",
+        "Stop recording, saving it, or stop playback.",
+        |_args| record::stop(),
+    );
+
+    state.commands.command(
+        console,
+        "elysium_unload",
+        "Restore everything elysium has changed and remove its hooks.",
+        |_args| State::get().unload_requested = true,
+    );
+}
+
+/// React to game events.
+fn add_listeners(events: &Events) {
+    let state = State::get();
+
+    state.listeners.insert(events, session::on_event);
+
+    state.listeners.insert(events, log::on_event);
+}
+
 #[inline]
 fn hooked(name: &str) {
     println!("elysium | hooked \x1b[38;5;2m{name}\x1b[m");
 }
 
+/// Print `line` to the console and the feed.
+fn notify(line: String) {
+    let state = State::get();
+
+    println!("elysium | {line}");
+
+    if let Some(menu) = state.menu.as_mut() {
+        menu.push_feed(line);
+    }
+}
+
 #[inline]
+/// Create our materials, replacing any previous ones.
+unsafe fn create_materials() {
+    use elysium_sdk::material::{Material, MaterialKind};
+    use std::ptr;
+
+    let state = State::get();
+    let hooks = state.hooks.as_ref().unwrap_unchecked();
+    let interfaces = state.interfaces.as_ref().unwrap_unchecked();
+    let material_system = &interfaces.material_system;
+
+    println!("create gold");
+    state.materials.gold = Some({
+        let material = MaterialKind::Glow;
+        let vdf = &*(hooks.vdf_from_bytes)(material.base_ptr(), material.vdf_ptr(), ptr::null());
+
+        let material = &*material_system
+            .create(material.name(), vdf)
+            .cast::<Material>();
+
+        println!("name = {:?}", material.name());
+        println!("texture_group = {:?}", material.texture_group());
+
+        material
+    });
+}
+
 fn main() {
     unsafe {
         library::wait_for_serverbrowser();
@@ -70,12 +394,12 @@ fn main() {
         let state = State::get();
 
         state.interfaces = Some(interfaces);
+        state.key_values_system = Some(library::load_key_values_system());
 
         let interfaces = state.interfaces.as_ref().unwrap_unchecked();
         let console = &interfaces.console;
         let client = &interfaces.client;
         let model_render = &interfaces.model_render;
-        let material_system = &interfaces.material_system;
 
         let globals = &mut *client.globals().as_mut().cast();
         let input = &mut *client.input().as_mut().cast();
@@ -94,13 +418,42 @@ fn main() {
 
         state.globals = Some(globals);
         state.input = Some(input);
+
+        match pattern::get_unique(LibraryKind::Client, &pattern::MOVE_HELPER) {
+            Some(bytes) => {
+                // the displacement follows the leading zero and `48 89 3D`
+                let base = bytes.as_ptr().byte_add(4).cast::<i32>();
+                let address = base.byte_add(4).byte_offset(base.read_unaligned() as isize);
+
+                state.move_helper = address.cast();
+            }
+            None => println!("elysium | unable to find the \x1b[38;5;2mmove helper\x1b[m"),
+        }
+
         state.vars = Some(vars);
+
+        for var_kind in [VarKind::cheats, VarKind::interp, VarKind::recoil_scale] {
+            state
+                .var_callbacks
+                .insert(console, var_kind.as_str(), |var, old_string, _old_value| {
+                    let name = var.name();
+                    let new_string = var.string();
+
+                    println!("elysium | config variable \x1b[38;5;2m{name}\x1b[m changed from \x1b[38;5;3m{old_string:?}\x1b[m to \x1b[38;5;3m{new_string:?}\x1b[m");
+                });
+        }
         state.networked.update(client);
 
-        /*let bytes = pattern::get(LibraryKind::Client, &pattern::ANIMATION_LAYERS).unwrap();
-        let _animation_layers = bytes.as_ptr().byte_add(35).cast::<u32>().read();
+        match pattern::get(LibraryKind::Client, &pattern::ANIMATION_LAYERS) {
+            Some(bytes) => {
+                let animation_layers = bytes.as_ptr().byte_add(35).cast::<u32>().read_unaligned();
 
-        let bytes = pattern::get(LibraryKind::Client, &pattern::ANIMATION_STATE).unwrap();
+                state.networked.base_animating.animation_layers = animation_layers as usize;
+            }
+            None => println!("elysium | unable to find \x1b[38;5;2manimation layers\x1b[m"),
+        }
+
+        /*let bytes = pattern::get(LibraryKind::Client, &pattern::ANIMATION_STATE).unwrap();
         let _animation_state = bytes.as_ptr().byte_add(52).cast::<u32>().read();*/
 
         // TODO: clean this up (remove maybeuninit).
@@ -174,25 +527,9 @@ fn main() {
 
         state.hooks = Some(hooks.assume_init());
 
-        println!("create gold");
-        state.materials.gold = Some({
-            use elysium_sdk::material::{Material, MaterialKind};
-            use std::ptr;
-
-            let state = State::get();
-            let hooks = state.hooks.as_ref().unwrap_unchecked();
-            let material = MaterialKind::Glow;
-            let vdf =
-                &*(hooks.vdf_from_bytes)(material.base_ptr(), material.vdf_ptr(), ptr::null());
-
-            let material = &*material_system
-                .create(material.name(), vdf)
-                .cast::<Material>();
-
-            println!("name = {:?}", material.name());
-            println!("texture_group = {:?}", material.texture_group());
+        register_commands(console);
+        add_listeners(&interfaces.events);
 
-            material
-        });
+        create_materials();
     }
 }
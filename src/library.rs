@@ -136,6 +136,32 @@ pub fn load_interfaces() -> elysium_sdk::Interfaces {
     }
 }
 
+/// Load the engine's key values system from `libvstdlib_client.so`.
+#[inline]
+pub fn load_key_values_system() -> &'static elysium_sdk::KeyValuesSystem {
+    type KeyValuesSystemFn = unsafe extern "C" fn() -> *const elysium_sdk::KeyValuesSystem;
+
+    unsafe {
+        let library_kind = elysium_sdk::LibraryKind::VStdLib;
+        let library = match Library::load(library_kind.as_nul_str()) {
+            Ok(library) => library,
+            Err(error) => panic!("Failed to load library: {library_kind:?}: {error:?}"),
+        };
+
+        let symbol: *const u8 = match library.symbol_ptr("KeyValuesSystem") {
+            Some(symbol) => symbol,
+            None => panic!("Failed to find KeyValuesSystem within library: {library_kind:?}"),
+        };
+
+        let key_values_system: KeyValuesSystemFn = std::mem::transmute(symbol);
+        let key_values_system = &*key_values_system();
+
+        println!("elysium | loaded \x1b[38;5;2mKeyValuesSystem\x1b[m within \x1b[38;5;2m{library_kind:?}\x1b[m at \x1b[38;5;3m{key_values_system:?}\x1b[m", key_values_system = key_values_system as *const _);
+
+        key_values_system
+    }
+}
+
 #[inline]
 pub fn wait_for_serverbrowser() {
     // `serverbrowser_client.so` is the last library to be loaded.
@@ -0,0 +1,183 @@
+//! Record hits and kills, and export them once the match is over.
+
+use crate::{Entity, State};
+use elysium_log::{Entry, Kind, Log, Player};
+use elysium_menu::Menu;
+use elysium_sdk::event::GameEvent;
+use elysium_sdk::{Engine, EntityList, ItemKind};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+
+/// Where match logs are written, relative to the game directory.
+const LOG_DIR: &str = "elysium/logs";
+
+/// Log a hit, kill or round change.
+pub fn on_event(event: &GameEvent) {
+    let state = State::get();
+    let interfaces = match state.interfaces.as_ref() {
+        Some(interfaces) => interfaces,
+        None => return,
+    };
+
+    let engine = &interfaces.engine;
+    let entity_list = &interfaces.entity_list;
+    let log = &mut state.log;
+    let time = state
+        .globals
+        .as_ref()
+        .map(|globals| globals.current_time)
+        .unwrap_or(0.0);
+
+    let entry = match *event {
+        GameEvent::RoundStart { .. } => {
+            log.start_round();
+            return;
+        }
+        GameEvent::PlayerHurt {
+            user_id,
+            attacker,
+            weapon,
+            damage_health,
+            damage_armor,
+            hit_group,
+            ..
+        } => Entry {
+            kind: Kind::Hit,
+            round: log.round(),
+            time,
+            attacker: player(engine, attacker),
+            victim: player(engine, user_id).unwrap_or_default(),
+            weapon: weapon_label(engine, entity_list, attacker, weapon),
+            hit_group: hit_group
+                .map(|hit_group| hit_group.label())
+                .unwrap_or("body")
+                .into(),
+            damage: damage_health,
+            armor_damage: damage_armor,
+            headshot: hit_group
+                .map(|hit_group| hit_group.is_head())
+                .unwrap_or(false),
+        },
+        GameEvent::PlayerDeath {
+            user_id,
+            attacker,
+            weapon,
+            headshot,
+            ..
+        } => Entry {
+            kind: Kind::Kill,
+            round: log.round(),
+            time,
+            attacker: player(engine, attacker),
+            victim: player(engine, user_id).unwrap_or_default(),
+            weapon: weapon_label(engine, entity_list, attacker, weapon),
+            hit_group: String::new(),
+            damage: 0,
+            armor_damage: 0,
+            headshot,
+        },
+        _ => return,
+    };
+
+    // only our own hits and deaths make it to the feed, everything is logged
+    let local_user_id = engine
+        .get_player_info(engine.local_player_index())
+        .map(|info| info.user_id);
+
+    let is_local = match *event {
+        GameEvent::PlayerHurt {
+            user_id, attacker, ..
+        }
+        | GameEvent::PlayerDeath {
+            user_id, attacker, ..
+        } => local_user_id == Some(user_id) || local_user_id == Some(attacker),
+        _ => false,
+    };
+
+    let entry = log.push(entry);
+
+    if is_local {
+        if let Some(menu) = state.menu.as_mut() {
+            menu.push_feed(entry.to_string());
+        }
+    }
+}
+
+/// The player with `user_id`, `None` for the world.
+fn player(engine: &Engine, user_id: i32) -> Option<Player> {
+    let index = engine.get_player_for_user_id(user_id)?;
+    let info = engine.get_player_info(index)?;
+
+    Some(Player {
+        name: info.name().into(),
+        steam_id: info.steam_id,
+    })
+}
+
+/// The label of the event's `weapon`, falling back to the weapon `user_id` is holding, then the
+/// event's weapon name itself.
+///
+/// The held weapon is only a guess, as grenades and fire keep hurting after a switch.
+fn weapon_label(engine: &Engine, entity_list: &EntityList, user_id: i32, weapon: &str) -> String {
+    ItemKind::from_name(weapon)
+        .and_then(|item_kind| item_kind.label())
+        .or_else(|| {
+            engine
+                .get_player_for_user_id(user_id)
+                .and_then(|index| unsafe { entity_list.entity(index).cast::<Entity>().as_ref() })
+                .and_then(|entity| entity.active_weapon(entity_list))
+                .and_then(|weapon| weapon.item_kind())
+                .and_then(|item_kind| item_kind.label())
+        })
+        .unwrap_or(weapon)
+        .into()
+}
+
+/// Start a new match log when the map changes, exporting the previous one.
+pub fn update_map(log: &mut Log, menu: Option<&mut Menu>, map: &str) {
+    if log.map() == map {
+        return;
+    }
+
+    if !log.is_empty() {
+        match export(log) {
+            Ok(path) => println!(
+                "elysium | exported match log to \x1b[38;5;2m{}\x1b[m",
+                path.display()
+            ),
+            Err(error) => println!("elysium | unable to export match log: {error}"),
+        }
+    }
+
+    log.start_match(map);
+
+    if let Some(menu) = menu {
+        menu.clear_feed();
+    }
+}
+
+/// Write `log` as `<time>_<map>.json` and `<time>_<map>.csv`, returning the directory.
+fn export(log: &Log) -> io::Result<&'static Path> {
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    let map = if log.map().is_empty() {
+        "unknown"
+    } else {
+        log.map()
+    };
+
+    let dir = Path::new(LOG_DIR);
+
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join(format!("{time}_{map}.json")),
+        log.json().to_string(),
+    )?;
+    fs::write(dir.join(format!("{time}_{map}.csv")), log.csv().to_string())?;
+
+    Ok(dir)
+}
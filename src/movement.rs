@@ -0,0 +1,162 @@
+//! Track jumps and speed of the local player, showing their stats and a velocity graph on screen.
+
+use crate::prediction::Predicted;
+use crate::state::VelocityGraph;
+use crate::Entity;
+use elysium_math::Vec3;
+use elysium_menu::velocity::{Mark, Sample};
+use elysium_menu::Menu;
+use elysium_movement::{Event, Tick, Tracker, HULL_WIDTH};
+use elysium_sdk::trace::{Mask, Ray};
+use elysium_sdk::{Command, Filter, Trace, TraceKind};
+
+/// `FL_ONGROUND`
+const ON_GROUND: i32 = 1 << 0;
+
+/// How far ahead of the hull an edge is searched for.
+const MAX_EDGE: f32 = 32.0;
+
+/// Only hit the world.
+struct WorldOnly;
+
+impl Filter for WorldOnly {
+    fn should_hit_entity(&self, _entity: *const (), _mask: i32) -> bool {
+        false
+    }
+
+    fn get_trace_kind(&self) -> TraceKind {
+        TraceKind::WorldOnly
+    }
+}
+
+/// Track `command`, after our movement features have changed it.
+///
+/// `side_move` is the player's own, from before auto strafe and the movement fix. `predicted` is
+/// where `command` takes the player, if prediction could be run.
+pub fn update(
+    tracker: &mut Tracker,
+    graph: &mut VelocityGraph,
+    menu: Option<&mut Menu>,
+    trace: &Trace,
+    command: &Command,
+    side_move: f32,
+    predicted: Option<Predicted>,
+    local: &Entity,
+) {
+    // ladders and noclip
+    if matches!(local.move_kind(), 8 | 9) {
+        tracker.reset();
+        return;
+    }
+
+    let Predicted {
+        origin,
+        velocity,
+        flags,
+    } = predicted.unwrap_or_else(|| Predicted {
+        origin: local.origin(),
+        velocity: local.velocity(),
+        flags: local.flags(),
+    });
+
+    let event = tracker.update(Tick {
+        origin,
+        velocity,
+        on_ground: flags & ON_GROUND != 0,
+        jumping: command.buttons().in_jump(),
+        side_move,
+    });
+
+    let mark = match event {
+        Some(Event::Takeoff) => Some(Mark::Jump),
+        Some(Event::Landed(_)) => Some(Mark::Land),
+        None => None,
+    };
+
+    let jump = match event {
+        Some(Event::Takeoff) => {
+            let edge = tracker
+                .takeoff()
+                .and_then(|takeoff| edge(trace, takeoff, velocity));
+
+            tracker.set_edge(edge);
+
+            None
+        }
+        Some(Event::Landed(jump)) => Some(jump),
+        None => None,
+    };
+
+    if let Some(menu) = menu {
+        menu.update_speed(tracker.speed());
+
+        // the graph only handles messages while it's shown, so only send the config then too
+        if menu.controls().velocity() {
+            let config = graph.read();
+
+            if graph.config != Some(config) {
+                graph.config = Some(config);
+                menu.configure_velocity(config);
+            }
+
+            menu.push_velocity(Sample {
+                speed: tracker.speed(),
+                mark,
+            });
+        }
+
+        if let Some(jump) = jump {
+            menu.push_jump(jump.to_string());
+        }
+    }
+}
+
+/// Whether there's ground within a step below `origin`.
+fn has_ground(trace: &Trace, origin: Vec3) -> bool {
+    let start = origin + Vec3::from_xyz(0.0, 0.0, 2.0);
+    let end = origin - Vec3::from_xyz(0.0, 0.0, 4.0);
+
+    trace
+        .trace(Ray::new(start, end), Mask::PLAYERSOLID.0, WorldOnly)
+        .fraction
+        < 1.0
+}
+
+/// Distance from the front of the hull at `takeoff` to the edge of the ground, heading along
+/// `velocity`.
+fn edge(trace: &Trace, takeoff: Vec3, velocity: Vec3) -> Option<f32> {
+    let speed = velocity.magnitude2d();
+
+    if speed < 1.0 {
+        return None;
+    }
+
+    let direction = Vec3::from_xy(velocity.x / speed, velocity.y / speed);
+
+    // how far the square hull reaches ahead of its origin, heading this way
+    let reach = HULL_WIDTH / 2.0 / direction.x.abs().max(direction.y.abs());
+    let point = |distance: f32| takeoff + direction * Vec3::splat(distance);
+
+    if !has_ground(trace, takeoff) {
+        return None;
+    }
+
+    // step out a unit at a time, then narrow down between the last two steps
+    let mut outside = (1..=(reach + MAX_EDGE) as u32)
+        .map(|distance| distance as f32)
+        .find(|distance| !has_ground(trace, point(*distance)))?;
+
+    let mut inside = outside - 1.0;
+
+    for _ in 0..6 {
+        let middle = (inside + outside) / 2.0;
+
+        if has_ground(trace, point(middle)) {
+            inside = middle;
+        } else {
+            outside = middle;
+        }
+    }
+
+    Some(inside - reach)
+}
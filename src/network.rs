@@ -0,0 +1,92 @@
+//! Sample the network channel each tick, for the net graph and loss and choke alerts.
+
+use crate::state::{Network, Sample};
+use elysium_menu::Menu;
+use elysium_sdk::convar::Var;
+use elysium_sdk::{Engine, Flow};
+
+/// Sample the network channel, and update the net graph if anything changed.
+pub fn update(network: &mut Network, menu: Option<&mut Menu>, engine: &Engine, tick: i32) {
+    let channel = match engine.network_channel() {
+        Some(channel) if engine.is_in_game() => channel,
+        _ => {
+            if !network.is_empty() {
+                network.clear();
+
+                if let Some(menu) = menu {
+                    menu.clear_net_graph();
+                }
+            }
+
+            return;
+        }
+    };
+
+    let sample = Sample {
+        tick,
+        latency: channel.get_latency(Flow::Outgoing),
+        loss: channel.get_avg_loss(Flow::Incoming),
+        choke: channel.get_avg_choke(Flow::Incoming),
+        incoming: channel.get_avg_data(Flow::Incoming),
+        outgoing: channel.get_avg_data(Flow::Outgoing),
+    };
+
+    if !network.push(sample) {
+        return;
+    }
+
+    let mut stats = match network.stats() {
+        Some(stats) => stats,
+        None => return,
+    };
+
+    let loss_alert = threshold(network.loss_alert);
+    let choke_alert = threshold(network.choke_alert);
+
+    stats.loss_alert = loss_alert
+        .map(|threshold| stats.loss > threshold)
+        .unwrap_or(false);
+
+    stats.choke_alert = choke_alert
+        .map(|threshold| stats.choke > threshold)
+        .unwrap_or(false);
+
+    let mut alerts = Vec::new();
+
+    if stats.loss_alert && !network.is_loss_alerting {
+        alerts.push(alert("packet loss", stats.loss, loss_alert));
+    }
+
+    if stats.choke_alert && !network.is_choke_alerting {
+        alerts.push(alert("packet choke", stats.choke, choke_alert));
+    }
+
+    network.is_loss_alerting = stats.loss_alert;
+    network.is_choke_alerting = stats.choke_alert;
+
+    for line in alerts.iter() {
+        println!("elysium | \x1b[38;5;1m{line}\x1b[m");
+    }
+
+    if let Some(menu) = menu {
+        for line in alerts {
+            menu.push_feed(line);
+        }
+
+        menu.update_net_graph(stats);
+    }
+}
+
+/// The alert threshold of `var`, `None` if it's disabled.
+#[inline]
+fn threshold(var: Option<&Var<f32>>) -> Option<f32> {
+    var.map(|var| var.read())
+        .filter(|threshold| *threshold > 0.0)
+}
+
+#[inline]
+fn alert(what: &str, percent: f32, threshold: Option<f32>) -> String {
+    let threshold = threshold.unwrap_or(0.0);
+
+    format!("{what} at {percent:.1}%, over {threshold:.1}%")
+}
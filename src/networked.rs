@@ -1,15 +1,18 @@
 use core::mem::MaybeUninit;
 use elysium_sdk::client::{Client, Table};
-use elysium_sdk::{Class, Entry};
+use elysium_sdk::{Class, Console, Entry};
 
 #[derive(Debug)]
 pub struct BaseAnimating {
+    /// not networked, found by pattern (`pattern::ANIMATION_LAYERS`)
+    pub animation_layers: usize,
     pub client_side_animation: usize,
 }
 
 #[derive(Debug)]
 pub struct BaseEntity {
     pub render_mode: usize,
+    pub simulation_time: usize,
     pub team: usize,
 }
 
@@ -28,6 +31,7 @@ pub struct BasePlayer {
 pub struct BaseWeapon {
     pub next_attack_available_after: usize,
     pub magazine: usize,
+    pub reserve_ammo: usize,
 }
 
 #[derive(Debug)]
@@ -130,6 +134,7 @@ fn insert_entry(this: &mut Networked, class: Class, entry: Entry, offset: usize)
 
         // base_entity
         (Class::BaseEntity, Entry::RenderMode) => this.base_entity.render_mode = offset,
+        (Class::BaseEntity, Entry::SimulationTime) => this.base_entity.simulation_time = offset,
         (Class::BaseEntity, Entry::Team) => this.base_entity.team = offset,
 
         // base_player
@@ -146,6 +151,7 @@ fn insert_entry(this: &mut Networked, class: Class, entry: Entry, offset: usize)
             this.base_weapon.next_attack_available_after = offset
         }
         (Class::BaseWeapon, Entry::Magazine) => this.base_weapon.magazine = offset,
+        (Class::BaseWeapon, Entry::ReserveAmmo) => this.base_weapon.reserve_ammo = offset,
 
         // fog
         (Class::Fog, Entry::FogColorPrimary) => this.fog.color_primary = offset,
@@ -211,3 +217,33 @@ fn iterate_table(this: &mut Networked, table: &'static Table, class: Class, base
         }
     }
 }
+
+/// Write every networked variable and its offset to the console, optionally only for tables whose
+/// name contains `filter`.
+#[inline]
+pub fn dump(client: &Client, console: &Console, filter: &str) {
+    for class in client.get_all_classes().iter() {
+        if let Some(table) = class.table {
+            if table.name().contains(filter) {
+                dump_table(console, table, table.name(), 0);
+            }
+        }
+    }
+}
+
+/// Dump a networked table, recursing into sub-tables.
+#[inline]
+fn dump_table(console: &Console, table: &'static Table, path: &str, base_offset: usize) {
+    for property in table.properties().iter() {
+        let name = property.name();
+        let offset = base_offset + property.offset as usize;
+
+        if let Some(sub_table) = property.data_table() {
+            dump_table(console, sub_table, &format!("{path}.{name}"), offset);
+        } else {
+            let kind = property.kind;
+
+            console.write(format!("{path}.{name} = {offset:#x} ({kind:?})\n"));
+        }
+    }
+}
@@ -39,6 +39,15 @@ pub const WRITE_USER_COMMAND: Pattern<68> =
 pub const WRITE_USER_COMMAND_DELTA_TO_BUFFER: Pattern<72> =
     Pattern::new("55 48 8D 05 38 BC 68 01 41 89 F2 48 89 E5 41 57");
 
+/// `IMoveHelper::SetSingleton`, `mov [rip + sm_pSingleton], rdi; ret`, which `CMoveHelperClient`'s
+/// constructor calls
+///
+/// The signature Fuzion and Osiris use on Linux. Short enough that another setter could match it
+/// after a game update, so it's looked up with [`get_unique`].
+///
+/// [game/shared/imovehelper.h](https://github.com/VSES/SourceEngine2007/blob/master/se2007/game/shared/imovehelper.h)
+pub const MOVE_HELPER: Pattern<32> = Pattern::new("00 48 89 3D ?? ?? ?? ?? C3");
+
 pub const VDF_FROM_BYTES: Pattern<44> = Pattern::new("E8 ?? ?? ?? ?? 48 89 DF 48 89 45 E0");
 
 #[inline]
@@ -57,3 +66,28 @@ pub fn get<const N: usize>(library: LibraryKind, pattern: &Pattern<N>) -> Option
         .find(bytes)
         .map(|found| &bytes[found.start()..])
 }
+
+/// Like [`get`], but `None` unless `pattern` matches exactly once.
+#[inline]
+pub fn get_unique<const N: usize>(
+    library: LibraryKind,
+    pattern: &Pattern<N>,
+) -> Option<&'static [u8]> {
+    let name = library.as_str();
+
+    println!("elysium | find unique pattern {pattern:?} in {name}");
+
+    let library = unsafe { Library::load(library.as_nul_str()).ok()? };
+    let bytes = unsafe { library.bytes() };
+    let mut found = pattern.regex().find_iter(bytes);
+
+    match (found.next(), found.next()) {
+        (Some(found), None) => Some(&bytes[found.start()..]),
+        (Some(_), Some(_)) => {
+            println!("elysium | pattern {pattern:?} matches more than once in {name}");
+
+            None
+        }
+        _ => None,
+    }
+}
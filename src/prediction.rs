@@ -0,0 +1,74 @@
+//! Run the game's own movement code on a command, to see where it takes the local player.
+
+use crate::{Entity, State};
+use elysium_math::Vec3;
+use elysium_sdk::{Command, MoveData};
+use std::ptr;
+
+/// Where a command leaves the player.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Predicted {
+    pub origin: Vec3,
+    pub velocity: Vec3,
+    pub flags: i32,
+}
+
+/// Run `command` for `local` through game movement, as prediction would.
+///
+/// The move isn't finished, and whatever movement changed on `local` is put back, so the game's
+/// own prediction of `command` is unaffected. `None` if the move helper wasn't found, or the
+/// client hasn't set it yet.
+pub fn run(command: &Command, local: &Entity) -> Option<Predicted> {
+    let state = State::get();
+    let interfaces = state.interfaces.as_ref()?;
+    let globals = state.globals.as_mut()?;
+    let move_helper = unsafe { state.move_helper.as_ref()?.as_ref()? };
+    let player = (local as *const Entity).cast::<()>();
+
+    // movement is timed by these, make them this command's
+    let current_time = globals.current_time;
+    let frame_time = globals.frame_time;
+
+    globals.current_time = local.tick_base() as f32 * globals.interval_per_tick;
+    globals.frame_time = globals.interval_per_tick;
+
+    // processing movement changes these
+    let flags = local.flags();
+    let origin = local.origin();
+    let velocity = local.velocity();
+
+    // the game predicts this command again, leave sounds and effects to then
+    let prediction = &interfaces.prediction;
+    let in_prediction = prediction.in_prediction();
+    let is_first_time_predicted = prediction.is_first_time_predicted();
+
+    prediction.set_in_prediction(true);
+    prediction.set_first_time_predicted(false);
+
+    let mut move_data = MoveData::zeroed();
+
+    move_helper.set_host(player);
+    interfaces.movement.start_track_prediction_errors(player);
+    prediction.setup_move(player, command, move_helper, &mut move_data);
+    interfaces.movement.process_movement(player, &mut move_data);
+    interfaces.movement.finish_track_prediction_errors(player);
+    move_helper.set_host(ptr::null());
+
+    let predicted = Predicted {
+        origin: move_data.abs_origin,
+        velocity: move_data.velocity,
+        flags: local.flags(),
+    };
+
+    local.set_flags(flags);
+    local.set_origin(origin);
+    local.set_velocity(velocity);
+
+    prediction.set_in_prediction(in_prediction);
+    prediction.set_first_time_predicted(is_first_time_predicted);
+
+    globals.current_time = current_time;
+    globals.frame_time = frame_time;
+
+    Some(predicted)
+}
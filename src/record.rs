@@ -0,0 +1,205 @@
+//! Record movement input and play it back, for practising jump spots and strafe lines.
+
+use crate::state::{Recorder, RecorderMode};
+use crate::{notify, Entity, State};
+use elysium_menu::RecorderAction;
+use elysium_record::{Playback, Recording, Step};
+use elysium_sdk::Command;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where recordings are saved, relative to the game directory.
+const RECORDING_DIR: &str = "elysium/recordings";
+
+/// How far off the recorded path playback may drift, if the config variable isn't registered.
+const DEFAULT_MAX_DRIFT: f32 = 32.0;
+
+/// The file recording `name` is saved to, `None` if `name` could reach outside the directory.
+fn path(name: &str) -> Option<PathBuf> {
+    let is_valid = !name.is_empty()
+        && !name.contains("..")
+        && !name.contains(|c: char| matches!(c, '/' | '\\' | '\0'));
+
+    is_valid.then(|| Path::new(RECORDING_DIR).join(format!("{name}.rec")))
+}
+
+/// Start recording to `<name>.rec`, from where the local player is now.
+pub fn record(name: &str) {
+    let state = State::get();
+
+    if path(name).is_none() {
+        return notify(format!("unable to record, {name:?} isn't a valid name"));
+    }
+
+    let local = unsafe { state.local.player.as_ref() };
+
+    let (interfaces, globals, local) =
+        match (state.interfaces.as_ref(), state.globals.as_ref(), local) {
+            (Some(interfaces), Some(globals), Some(local)) => (interfaces, globals, local),
+            _ => return notify("unable to record, not in game".into()),
+        };
+
+    let recording = Recording::new(
+        &state.session.map,
+        globals.interval_per_tick,
+        local.origin(),
+        interfaces.engine.view_angle(),
+    );
+
+    state.recorder.mode = RecorderMode::Recording {
+        name: name.into(),
+        recording,
+    };
+
+    notify(format!("recording {name:?}"));
+}
+
+/// Play `<name>.rec` back, if the local player is standing where it starts.
+pub fn play(name: &str) {
+    let state = State::get();
+
+    let local = match unsafe { state.local.player.as_ref() } {
+        Some(local) => local,
+        None => return notify("unable to play, not in game".into()),
+    };
+
+    let path = match path(name) {
+        Some(path) => path,
+        None => return notify(format!("unable to play, {name:?} isn't a valid name")),
+    };
+
+    let recording = match fs::read(path) {
+        Ok(bytes) => Recording::from_bytes(&bytes).map_err(|error| error.to_string()),
+        Err(error) => Err(error.to_string()),
+    };
+
+    let recording = match recording {
+        Ok(recording) => recording,
+        Err(error) => return notify(format!("unable to load recording {name:?}: {error}")),
+    };
+
+    if recording.map != state.session.map {
+        return notify(format!(
+            "unable to play {name:?}, it was recorded on {}",
+            recording.map
+        ));
+    }
+
+    let max_drift = max_drift(&state.recorder);
+    let playback = Playback::new(recording, max_drift);
+    let distance = playback.distance_from_start(local.origin());
+
+    if distance > max_drift {
+        return notify(format!(
+            "unable to play {name:?}, you're {distance:.0} units from where it starts"
+        ));
+    }
+
+    if let Some(interfaces) = state.interfaces.as_ref() {
+        interfaces
+            .engine
+            .set_view_angle(playback.recording().view_angle);
+    }
+
+    state.recorder.mode = RecorderMode::Playing(playback);
+
+    notify(format!("playing {name:?}"));
+}
+
+/// Stop recording, saving it, or stop playback.
+pub fn stop() {
+    let state = State::get();
+    let mode = std::mem::replace(&mut state.recorder.mode, RecorderMode::Idle);
+
+    match mode {
+        RecorderMode::Idle => {}
+        RecorderMode::Recording { name, recording } => save(&name, &recording),
+        RecorderMode::Playing(playback) => notify(format!(
+            "stopped playback after {} of {} ticks",
+            playback.tick(),
+            playback.recording().len()
+        )),
+    }
+}
+
+fn save(name: &str, recording: &Recording) {
+    if recording.is_empty() {
+        return notify(format!("nothing recorded for {name:?}"));
+    }
+
+    // checked when recording started
+    let path = match path(name) {
+        Some(path) => path,
+        None => return,
+    };
+
+    let result =
+        fs::create_dir_all(RECORDING_DIR).and_then(|_| fs::write(&path, recording.to_bytes()));
+
+    match result {
+        Ok(()) => notify(format!(
+            "saved {} ticks ({:.1}s) to {}",
+            recording.len(),
+            recording.duration(),
+            path.display()
+        )),
+        Err(error) => notify(format!("unable to save recording {name:?}: {error}")),
+    }
+}
+
+/// Record `command` as it's sent, after our movement features have changed it.
+pub fn push(command: &Command, local: &Entity) {
+    let state = State::get();
+
+    if let RecorderMode::Recording { recording, .. } = &mut state.recorder.mode {
+        recording.push(local.origin(), command);
+    }
+}
+
+#[inline]
+fn max_drift(recorder: &Recorder) -> f32 {
+    recorder
+        .max_drift
+        .map(|var| var.read())
+        .unwrap_or(DEFAULT_MAX_DRIFT)
+}
+
+/// Handle menu button presses, then play back into `command`.
+///
+/// Recordings hold commands as they were sent, so our movement features are skipped during
+/// playback.
+pub fn update(command: &mut Command, local: &Entity) {
+    let state = State::get();
+    let action = state
+        .menu
+        .as_ref()
+        .and_then(|menu| menu.controls().take_recorder_action());
+
+    match action {
+        Some(RecorderAction::Record(name)) => record(&name),
+        Some(RecorderAction::Play(name)) => play(&name),
+        Some(RecorderAction::Stop) => stop(),
+        None => {}
+    }
+
+    let origin = local.origin();
+    let step = match &mut state.recorder.mode {
+        RecorderMode::Idle | RecorderMode::Recording { .. } => return,
+        RecorderMode::Playing(playback) => playback.next(origin),
+    };
+
+    match step {
+        // the recorded angles are the ones sent, anti-aim included, so the camera is left alone
+        Step::Play(frame) => frame.apply(command),
+        Step::Finished => {
+            state.recorder.mode = RecorderMode::Idle;
+            notify("playback finished".into());
+        }
+        Step::Drifted { tick, distance } => {
+            state.recorder.mode = RecorderMode::Idle;
+            notify(format!(
+                "stopped playback at tick {tick}, {distance:.0} units off the recorded path"
+            ));
+        }
+    }
+}
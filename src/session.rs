@@ -0,0 +1,86 @@
+//! Notice connecting, disconnecting, map changes and round starts, resetting per-map state.
+
+use crate::state::SessionEvent;
+use crate::{notify, State};
+use elysium_sdk::event::GameEvent;
+use elysium_sdk::Engine;
+use std::path::Path;
+use std::time::Instant;
+
+/// The current map's name, without its directory or extension.
+fn map_name(engine: &Engine) -> &str {
+    Path::new(engine.get_level_name())
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("")
+}
+
+/// Check for connects, disconnects and map changes.
+pub fn update(engine: &Engine) {
+    let state = State::get();
+
+    // still connected while loading the next map, though not in game
+    let server = match engine.network_channel() {
+        Some(channel) if engine.is_connected() => Some(channel.get_address()),
+        _ => None,
+    };
+
+    let map = if engine.is_in_game() {
+        map_name(engine)
+    } else {
+        ""
+    };
+
+    for event in state.session.update(server, map, Instant::now()) {
+        match event {
+            SessionEvent::Connect => notify(format!("connected to {}", state.session.server)),
+            SessionEvent::Disconnect => {
+                reset();
+                notify("disconnected".into());
+            }
+            SessionEvent::MapChange => {
+                reset();
+                unsafe { crate::create_materials() };
+                notify(format!("playing {}", state.session.map));
+            }
+        }
+    }
+}
+
+/// Count rounds, and forget records from the previous one.
+pub fn on_event(event: &GameEvent) {
+    if let GameEvent::RoundStart { .. } = event {
+        let state = State::get();
+
+        state.session.start_round(Instant::now());
+
+        // records from the previous round are meaningless after everyone respawns
+        for player in state.players.occupied_mut() {
+            player.history.clear();
+        }
+    }
+}
+
+/// Forget everything tied to the previous map.
+///
+/// Our materials are dropped too, as the material system may have freed them while unloading the
+/// map; a map change creates them again.
+fn reset() {
+    let state = State::get();
+
+    // finish the match log first, as starting a new one clears the feed
+    crate::log::update_map(&mut state.log, state.menu.as_mut(), &state.session.map);
+    crate::record::stop();
+
+    state.players.clear();
+    state.local.reset();
+    state.network.clear();
+    state.movement.reset();
+    state.materials.gold = None;
+
+    if let Some(menu) = state.menu.as_mut() {
+        menu.clear_net_graph();
+        menu.clear_movement();
+        menu.clear_velocity();
+    }
+}
@@ -1,21 +1,41 @@
 use crate::Networked;
+use elysium_log::Log;
 use elysium_math::Vec3;
 use elysium_menu::Menu;
-use elysium_sdk::{Globals, Input, Interfaces, Vars};
+use elysium_movement::Tracker;
+use elysium_sdk::{Globals, Input, Interfaces, KeyValuesSystem, MoveHelper, Vars};
 use iced_glow::glow;
 use iced_native::{Point, Size};
 use std::cell::SyncUnsafeCell;
 use std::ptr;
 
 pub use cache::{Player, Players};
+pub use callbacks::VarCallbacks;
+pub use commands::Commands;
+pub use history::{time_to_ticks, History, Record, ANIMATION_LAYERS, HISTORY_LEN};
 pub use hooks::*;
+pub use listeners::Listeners;
 pub use local::Local;
 pub use materials::Materials;
+pub use network::{Network, Sample};
+pub use overrides::VarOverrides;
+pub use recorder::{Mode as RecorderMode, Recorder};
+pub use session::{Event as SessionEvent, Session};
+pub use velocity::VelocityGraph;
 
 mod cache;
+mod callbacks;
+mod commands;
+mod history;
 mod hooks;
+mod listeners;
 mod local;
 mod materials;
+mod network;
+mod overrides;
+mod recorder;
+mod session;
+mod velocity;
 
 #[repr(transparent)]
 struct Wrap(State);
@@ -34,9 +54,22 @@ const NEW: State = State {
     hooks: None,
     networked: Networked::new(),
     vars: None,
+    var_callbacks: VarCallbacks::new(),
+    var_overrides: VarOverrides::new(),
+    commands: Commands::new(),
+    unload_requested: false,
+    listeners: Listeners::new(),
+    log: Log::new(),
+    network: Network::new(),
+    recorder: Recorder::new(),
+    movement: Tracker::new(),
+    velocity_graph: VelocityGraph::new(),
+    session: Session::new(),
     interfaces: None,
+    key_values_system: None,
     globals: None,
     input: None,
+    move_helper: ptr::null(),
     players: Players::new(),
     local: Local::new(),
     materials: Materials::new(),
@@ -65,12 +98,38 @@ pub struct State {
     pub networked: Networked,
     /// cvars
     pub vars: Option<Vars>,
+    /// cvar change callbacks
+    pub var_callbacks: VarCallbacks,
+    /// cvars we've forced, and their original values
+    pub var_overrides: VarOverrides,
+    /// our console commands and cvars
+    pub commands: Commands,
+    /// `elysium_unload` was run, unload on the next frame
+    pub unload_requested: bool,
+    /// game event callbacks
+    pub listeners: Listeners,
+    /// hits and kills of the current match
+    pub log: Log,
+    /// network channel samples, for the net graph
+    pub network: Network,
+    /// movement input recording and playback
+    pub recorder: Recorder,
+    /// local player's jumps
+    pub movement: Tracker,
+    /// velocity graph config variables
+    pub velocity_graph: VelocityGraph,
+    /// server and map being played
+    pub session: Session,
     /// source engine interfaces
     pub interfaces: Option<Interfaces>,
+    /// engine key values symbol table
+    pub key_values_system: Option<&'static KeyValuesSystem>,
     /// globals
     pub globals: Option<&'static mut Globals>,
     /// cinput
     pub input: Option<&'static mut Input>,
+    /// `IMoveHelper::sm_pSingleton`, which the client sets once it's initialized
+    pub move_helper: *const *const MoveHelper,
     /// efficient cache of players and their data (btw why is entitylist a linked list?)
     pub players: Players,
     pub materials: Materials,
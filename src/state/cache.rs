@@ -1,47 +1,88 @@
+use super::History;
 use core::ops::{Deref, DerefMut};
+use elysium_math::Vec3;
 use providence_model::Bones;
 
 /// A cached player.
 pub struct Player {
     pub bones: Bones,
+    /// Whether the slot is occupied.
+    pub exists: bool,
+    /// Recent server updates.
+    pub history: History,
     pub id: u64,
     pub index: i32,
+    /// Whether the server stopped sending updates for this player.
+    pub is_dormant: bool,
+    pub is_bot: bool,
     pub health: i32,
     pub magazine_ammo: i32,
     pub name: String,
     pub next_attack_available_after: f32,
+    pub origin: Vec3,
     pub revolver_cock_time: f32,
     pub total_ammo: i32,
+    /// When this snapshot was taken (`Globals::current_time`).
+    pub update_time: f32,
 }
 
 impl Player {
     pub const fn new() -> Player {
         let bones = Bones::zero();
+        let exists = false;
+        let history = History::new();
         let id = 0;
         let index = 0;
+        let is_dormant = false;
+        let is_bot = false;
         let health = 0;
         let magazine_ammo = 0;
         let name = String::new();
         let next_attack_available_after = 0.0;
+        let origin = Vec3::zero();
         let revolver_cock_time = 0.0;
         let total_ammo = 0;
+        let update_time = 0.0;
 
         Self {
             bones,
+            exists,
             health,
+            history,
             id,
             index,
+            is_bot,
+            is_dormant,
             magazine_ammo,
             name,
             next_attack_available_after,
+            origin,
             revolver_cock_time,
             total_ammo,
+            update_time,
         }
     }
 
     pub fn name(&self) -> &str {
         &*self.name
     }
+
+    pub const fn is_alive(&self) -> bool {
+        self.exists && self.health > 0
+    }
+
+    /// Mark the slot as empty, keeping the name and history allocations around.
+    pub fn clear(&mut self) {
+        let mut name = core::mem::take(&mut self.name);
+        let mut history = core::mem::replace(&mut self.history, History::new());
+
+        name.clear();
+        history.clear();
+
+        *self = Player::new();
+        self.name = name;
+        self.history = history;
+    }
 }
 
 /// An array of cached players.
@@ -57,6 +98,35 @@ impl Players {
 
         Self { players }
     }
+
+    /// The player at entity `index` (`1..=64`).
+    pub fn get(&self, index: i32) -> Option<&Player> {
+        let index = usize::try_from(index).ok()?.checked_sub(1)?;
+
+        self.players.get(index)
+    }
+
+    /// The player at entity `index` (`1..=64`).
+    pub fn get_mut(&mut self, index: i32) -> Option<&mut Player> {
+        let index = usize::try_from(index).ok()?.checked_sub(1)?;
+
+        self.players.get_mut(index)
+    }
+
+    /// Occupied slots.
+    pub fn occupied(&self) -> impl Iterator<Item = &Player> {
+        self.players.iter().filter(|player| player.exists)
+    }
+
+    /// Occupied slots, mutably.
+    pub fn occupied_mut(&mut self) -> impl Iterator<Item = &mut Player> {
+        self.players.iter_mut().filter(|player| player.exists)
+    }
+
+    /// Empty every slot, such as when leaving a server.
+    pub fn clear(&mut self) {
+        self.players.iter_mut().for_each(Player::clear);
+    }
 }
 
 impl Deref for Players {
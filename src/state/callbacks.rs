@@ -0,0 +1,76 @@
+use crate::State;
+use elysium_sdk::convar::{Interface, Var};
+use elysium_sdk::{ffi, Console};
+
+type Callback = Box<dyn FnMut(&Var<()>, &str, f32)>;
+
+const NEW: VarCallbacks = VarCallbacks {
+    installed: false,
+    callbacks: Vec::new(),
+};
+
+/// Rust closures invoked when config variables change.
+///
+/// A single global change callback is installed with the console, which dispatches to every
+/// closure registered for the variable's name.
+pub struct VarCallbacks {
+    installed: bool,
+    callbacks: Vec<(String, Callback)>,
+}
+
+impl VarCallbacks {
+    #[inline]
+    pub const fn new() -> Self {
+        NEW
+    }
+
+    /// Invoke `callback` with the variable, its old string and its old value whenever `name`
+    /// changes.
+    #[inline]
+    pub fn insert<F>(&mut self, console: &Console, name: &str, callback: F)
+    where
+        F: FnMut(&Var<()>, &str, f32) + 'static,
+    {
+        if !self.installed {
+            console.install_global_change_callback(on_change);
+            self.installed = true;
+        }
+
+        self.callbacks.push((name.into(), Box::new(callback)));
+    }
+
+    /// Remove every callback registered for `name`.
+    #[inline]
+    pub fn remove(&mut self, name: &str) {
+        self.callbacks
+            .retain(|(target, _callback)| !target.eq_ignore_ascii_case(name));
+    }
+
+    /// Remove the global change callback and drop every registered closure.
+    #[inline]
+    pub fn uninstall(&mut self, console: &Console) {
+        if self.installed {
+            console.remove_global_change_callback(on_change);
+            self.installed = false;
+        }
+
+        self.callbacks.clear();
+    }
+}
+
+unsafe extern "C" fn on_change(var: *const Interface, old_string: *const u8, old_value: f32) {
+    let var = match var.as_ref() {
+        Some(var) => var.var(),
+        None => return,
+    };
+
+    let state = State::get();
+    let name = var.name();
+
+    for (target, callback) in state.var_callbacks.callbacks.iter_mut() {
+        // a variable's first value has no old string to speak of, only read it for our targets
+        if target.eq_ignore_ascii_case(name) {
+            callback(var, ffi::str_from_ptr_nullable(old_string), old_value);
+        }
+    }
+}
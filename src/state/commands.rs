@@ -0,0 +1,130 @@
+use crate::State;
+use elysium_sdk::convar::{Args, Base, Command, Flags, Kind, Var};
+use elysium_sdk::Console;
+use std::ffi::{CStr, CString};
+
+type Callback = Box<dyn FnMut(&Args)>;
+
+const NEW: Commands = Commands {
+    registered: false,
+    commands: Vec::new(),
+    vars: Vec::new(),
+};
+
+/// Console commands and config variables we've registered with the engine.
+pub struct Commands {
+    registered: bool,
+    commands: Vec<(&'static Command, Callback)>,
+    vars: Vec<&'static Base>,
+}
+
+impl Commands {
+    #[inline]
+    pub const fn new() -> Self {
+        NEW
+    }
+
+    /// Register a console command named `name` which runs `callback`.
+    #[inline]
+    pub fn command<F>(&mut self, console: &Console, name: &str, help: &str, callback: F)
+    where
+        F: FnMut(&Args) + 'static,
+    {
+        // every command shares the same vtable, so borrow one from the engine
+        let template = console.base("echo").expect("`echo` is missing");
+        let command = Command::new(
+            template,
+            leak_cstr(name),
+            leak_cstr(help),
+            Flags::CLIENT_DLL,
+            dispatch,
+        );
+
+        let command = &*Box::leak(command);
+
+        console.register(command);
+        self.registered = true;
+        self.commands.push((command, Box::new(callback)));
+    }
+
+    /// Register a config variable named `name`, initially set to `default_value`.
+    #[inline]
+    pub fn var<T>(
+        &mut self,
+        console: &Console,
+        name: &str,
+        help: &str,
+        default_value: &str,
+    ) -> &'static Var<T>
+    where
+        T: Kind,
+    {
+        // every config variable shares the same vtables, so borrow them from the engine
+        let template = console
+            .base("developer")
+            .and_then(Base::as_var)
+            .expect("`developer` is missing");
+
+        let var = Var::<T>::new(
+            template,
+            leak_cstr(name),
+            leak_cstr(help),
+            Flags::CLIENT_DLL | Flags::ARCHIVE,
+            leak_cstr(default_value),
+        );
+
+        let var = &*Box::leak(var);
+
+        console.register(var.as_base());
+        self.registered = true;
+        self.vars.push(var.as_base());
+
+        var
+    }
+
+    /// Unregister everything we've registered.
+    ///
+    /// Commands and their callbacks are kept alive, in case one of them is still running.
+    #[inline]
+    pub fn unregister_all(&mut self, console: &Console) {
+        if !self.registered {
+            return;
+        }
+
+        for (command, _callback) in self.commands.iter() {
+            console.unregister(command);
+        }
+
+        for var in self.vars.iter() {
+            console.unregister(var);
+        }
+
+        self.registered = false;
+    }
+}
+
+#[inline]
+fn leak_cstr(string: &str) -> &'static CStr {
+    let cstr = CString::new(string).expect("string contains a nul byte");
+
+    Box::leak(cstr.into_boxed_c_str())
+}
+
+unsafe extern "C" fn dispatch(args: *const Args) {
+    let args = match args.as_ref() {
+        Some(args) => args,
+        None => return,
+    };
+
+    let state = State::get();
+    let name = args.command();
+    let callback = state
+        .commands
+        .commands
+        .iter_mut()
+        .find(|(command, _callback)| command.name().eq_ignore_ascii_case(name));
+
+    if let Some((_command, callback)) = callback {
+        callback(args);
+    }
+}
@@ -0,0 +1,189 @@
+use elysium_math::Vec3;
+use elysium_sdk::AnimationLayer;
+use providence_model::Bones;
+
+/// How many records are kept per player (half a second at 64 tick).
+pub const HISTORY_LEN: usize = 32;
+
+/// How many animation layers a player has.
+pub const ANIMATION_LAYERS: usize = 13;
+
+/// A player's state as of a server update.
+#[derive(Clone, Copy, Debug)]
+pub struct Record {
+    /// Server tick of the simulation time.
+    pub tick: i32,
+    pub simulation_time: f32,
+    pub origin: Vec3,
+    pub velocity: Vec3,
+    pub eye_angle: Vec3,
+    pub flags: i32,
+    pub bones: Bones,
+    pub layers: [AnimationLayer; ANIMATION_LAYERS],
+}
+
+/// Ring buffer of a player's most recent records, oldest first.
+pub struct History {
+    records: Vec<Record>,
+    /// Slot the next record is written to, which is the oldest record once full.
+    next: usize,
+}
+
+/// Convert a time to a server tick (`TIME_TO_TICKS`).
+#[inline]
+pub fn time_to_ticks(time: f32, interval_per_tick: f32) -> i32 {
+    (0.5 + time / interval_per_tick) as i32
+}
+
+impl History {
+    pub const fn new() -> History {
+        let records = Vec::new();
+        let next = 0;
+
+        Self { records, next }
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Forget every record, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.records.clear();
+        self.next = 0;
+    }
+
+    /// Append a record, replacing the oldest once full.
+    ///
+    /// Records older than the latest (such as after a tick base reset) clear the history first.
+    pub fn push(&mut self, record: Record) {
+        if let Some(latest) = self.latest() {
+            if record.tick < latest.tick {
+                self.clear();
+            }
+        }
+
+        if self.records.len() < HISTORY_LEN {
+            self.records.push(record);
+        } else {
+            self.records[self.next] = record;
+        }
+
+        self.next = (self.next + 1) % HISTORY_LEN;
+    }
+
+    /// The most recent record.
+    pub fn latest(&self) -> Option<&Record> {
+        self.iter().next_back()
+    }
+
+    /// The oldest record.
+    pub fn oldest(&self) -> Option<&Record> {
+        self.iter().next()
+    }
+
+    /// Records, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Record> {
+        let (newer, older) = self.records.split_at(self.next.min(self.records.len()));
+
+        older.iter().chain(newer.iter())
+    }
+
+    /// The most recent record at or before `tick`.
+    pub fn at_or_before(&self, tick: i32) -> Option<&Record> {
+        self.iter().rev().find(|record| record.tick <= tick)
+    }
+
+    /// The oldest record at or after `tick`.
+    pub fn at_or_after(&self, tick: i32) -> Option<&Record> {
+        self.iter().find(|record| record.tick >= tick)
+    }
+
+    /// The records either side of `tick`, for interpolating between them.
+    pub fn around(&self, tick: i32) -> Option<(&Record, &Record)> {
+        Some((self.at_or_before(tick)?, self.at_or_after(tick)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(tick: i32) -> Record {
+        Record {
+            tick,
+            simulation_time: tick as f32 / 64.0,
+            origin: Vec3::zero(),
+            velocity: Vec3::zero(),
+            eye_angle: Vec3::zero(),
+            flags: 0,
+            bones: Bones::zero(),
+            layers: [AnimationLayer::zero(); ANIMATION_LAYERS],
+        }
+    }
+
+    fn ticks(history: &History) -> Vec<i32> {
+        history.iter().map(|record| record.tick).collect()
+    }
+
+    #[test]
+    fn push() {
+        let mut history = History::new();
+
+        assert!(history.latest().is_none());
+
+        for tick in 1..=3 {
+            history.push(record(tick));
+        }
+
+        assert_eq!(ticks(&history), [1, 2, 3]);
+        assert_eq!(history.oldest().map(|record| record.tick), Some(1));
+        assert_eq!(history.latest().map(|record| record.tick), Some(3));
+        assert_eq!(
+            history.around(2).map(|(a, b)| (a.tick, b.tick)),
+            Some((2, 2))
+        );
+        assert!(history.at_or_after(4).is_none());
+    }
+
+    #[test]
+    fn expiry() {
+        let mut history = History::new();
+        let len = HISTORY_LEN as i32;
+
+        for tick in 0..len + 5 {
+            history.push(record(tick));
+        }
+
+        assert_eq!(history.len(), HISTORY_LEN);
+        assert_eq!(ticks(&history), (5..len + 5).collect::<Vec<_>>());
+        assert!(history.at_or_before(4).is_none());
+
+        // a tick base reset drops everything newer
+        history.push(record(2));
+
+        assert_eq!(ticks(&history), [2]);
+    }
+
+    #[test]
+    fn clear() {
+        let mut history = History::new();
+
+        for tick in 0..HISTORY_LEN as i32 + 1 {
+            history.push(record(tick));
+        }
+
+        history.clear();
+
+        assert!(history.is_empty());
+        assert!(history.latest().is_none());
+
+        history.push(record(7));
+
+        assert_eq!(ticks(&history), [7]);
+    }
+}
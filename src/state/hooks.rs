@@ -1,6 +1,6 @@
 use elysium_math::Matrix3x4;
 use elysium_sdk::model::{DrawModelState, ModelRender, ModelRenderInfo};
-use elysium_sdk::{Command, Vdf, View};
+use elysium_sdk::{Command, VdfFromBytes, View};
 use sdl2_sys::{SDL_Event, SDL_Window};
 
 pub type ClMove = unsafe extern "C" fn(extra_samples: f32, final_tick: bool);
@@ -23,9 +23,6 @@ pub type SwapWindow = unsafe extern "C" fn(window: *mut SDL_Window);
 pub type WriteUserCommand =
     unsafe extern "C" fn(buffer: *mut u8, from: *const u8, to: *const u8) -> bool;
 
-pub type VdfFromBytes =
-    unsafe extern "C" fn(name: *const u8, value: *const u8, _unk1: *const u8) -> *const Vdf;
-
 pub struct Hooks {
     pub cl_move: ClMove,
     pub cl_send_move: ClSendMove,
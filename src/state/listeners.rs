@@ -0,0 +1,71 @@
+use crate::State;
+use elysium_sdk::event::{Event, GameEvent, Listener};
+use elysium_sdk::Events;
+
+type Callback = Box<dyn FnMut(&GameEvent)>;
+
+static LISTENER: Listener = Listener::new(on_event);
+
+const NEW: Listeners = Listeners {
+    added: false,
+    callbacks: Vec::new(),
+};
+
+/// Rust closures invoked when game events fire.
+///
+/// A single listener is added for every event in `GameEvent::NAMES`, which dispatches to every
+/// registered closure.
+pub struct Listeners {
+    added: bool,
+    callbacks: Vec<Callback>,
+}
+
+impl Listeners {
+    #[inline]
+    pub const fn new() -> Self {
+        NEW
+    }
+
+    /// Invoke `callback` with every typed game event.
+    #[inline]
+    pub fn insert<F>(&mut self, events: &Events, callback: F)
+    where
+        F: FnMut(&GameEvent) + 'static,
+    {
+        if !self.added {
+            for name in GameEvent::NAMES {
+                if !events.add_listener(&LISTENER, name) {
+                    println!("elysium | unable to listen to \x1b[38;5;2m{name}\x1b[m");
+                }
+            }
+
+            self.added = true;
+        }
+
+        self.callbacks.push(Box::new(callback));
+    }
+
+    /// Remove the listener and drop every registered closure.
+    #[inline]
+    pub fn remove_all(&mut self, events: &Events) {
+        if self.added {
+            events.remove_listener(&LISTENER);
+            self.added = false;
+        }
+
+        self.callbacks.clear();
+    }
+}
+
+unsafe extern "C" fn on_event(event: *const Event) {
+    let event = match event.as_ref().and_then(GameEvent::from_event) {
+        Some(event) => event,
+        None => return,
+    };
+
+    let state = State::get();
+
+    for callback in state.listeners.callbacks.iter_mut() {
+        callback(&event);
+    }
+}
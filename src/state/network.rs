@@ -0,0 +1,130 @@
+use elysium_menu::net_graph::Stats;
+use elysium_sdk::convar::Var;
+
+/// How many ticks of samples are kept (two seconds at 64 tick).
+pub const NETWORK_LEN: usize = 128;
+
+/// The network channel's state as of a tick.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sample {
+    pub tick: i32,
+    /// Outgoing latency, in seconds.
+    pub latency: f32,
+    /// Incoming packet loss, from 0 to 1.
+    pub loss: f32,
+    /// Incoming packet choke, from 0 to 1.
+    pub choke: f32,
+    /// Incoming bytes per second.
+    pub incoming: f32,
+    /// Outgoing bytes per second.
+    pub outgoing: f32,
+}
+
+/// Ring buffer of network channel samples, and when to alert about them.
+pub struct Network {
+    samples: Vec<Sample>,
+    /// Slot the next sample is written to, which is the oldest sample once full.
+    next: usize,
+    /// Packet loss percentage to alert at, zero disables.
+    pub loss_alert: Option<&'static Var<f32>>,
+    /// Packet choke percentage to alert at, zero disables.
+    pub choke_alert: Option<&'static Var<f32>>,
+    /// Whether loss is currently over the threshold, so each spike alerts once.
+    pub is_loss_alerting: bool,
+    /// Whether choke is currently over the threshold.
+    pub is_choke_alerting: bool,
+}
+
+const NEW: Network = Network {
+    samples: Vec::new(),
+    next: 0,
+    loss_alert: None,
+    choke_alert: None,
+    is_loss_alerting: false,
+    is_choke_alerting: false,
+};
+
+impl Network {
+    #[inline]
+    pub const fn new() -> Self {
+        NEW
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Forget every sample, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.next = 0;
+        self.is_loss_alerting = false;
+        self.is_choke_alerting = false;
+    }
+
+    /// Append a sample, replacing the oldest once full.
+    ///
+    /// Returns false, ignoring the sample, if one was already taken this tick.
+    pub fn push(&mut self, sample: Sample) -> bool {
+        if let Some(latest) = self.latest() {
+            if sample.tick == latest.tick {
+                return false;
+            }
+
+            // the tick went backwards, so we've reconnected
+            if sample.tick < latest.tick {
+                self.clear();
+            }
+        }
+
+        if self.samples.len() < NETWORK_LEN {
+            self.samples.push(sample);
+        } else {
+            self.samples[self.next] = sample;
+        }
+
+        self.next = (self.next + 1) % NETWORK_LEN;
+
+        true
+    }
+
+    /// The most recent sample.
+    pub fn latest(&self) -> Option<&Sample> {
+        self.iter().next_back()
+    }
+
+    /// Samples, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Sample> {
+        let (newer, older) = self.samples.split_at(self.next.min(self.samples.len()));
+
+        older.iter().chain(newer.iter())
+    }
+
+    /// Averages over every sample, without alerts set.
+    pub fn stats(&self) -> Option<Stats> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let len = self.samples.len() as f32;
+        let mean = |f: fn(&Sample) -> f32| self.samples.iter().map(f).sum::<f32>() / len;
+
+        // mean change between consecutive samples
+        let jitter = self
+            .iter()
+            .zip(self.iter().skip(1))
+            .map(|(previous, sample)| (sample.latency - previous.latency).abs())
+            .sum::<f32>()
+            / (len - 1.0).max(1.0);
+
+        Some(Stats {
+            ping: mean(|sample| sample.latency) * 1000.0,
+            jitter: jitter * 1000.0,
+            loss: mean(|sample| sample.loss) * 100.0,
+            choke: mean(|sample| sample.choke) * 100.0,
+            incoming: mean(|sample| sample.incoming),
+            outgoing: mean(|sample| sample.outgoing),
+            ..Stats::default()
+        })
+    }
+}
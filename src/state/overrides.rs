@@ -0,0 +1,81 @@
+use elysium_sdk::convar::{Kind, Var};
+use std::ffi::CString;
+use std::ptr;
+
+const NEW: VarOverrides = VarOverrides {
+    originals: Vec::new(),
+};
+
+/// Config variables we've forced to a value, with the value the player had before.
+pub struct VarOverrides {
+    /// Originals are kept as strings, as the engine stores them, so they come back exactly.
+    originals: Vec<(&'static Var<()>, CString)>,
+}
+
+impl VarOverrides {
+    #[inline]
+    pub const fn new() -> Self {
+        NEW
+    }
+
+    /// Force `var` to `value`.
+    ///
+    /// The original value is remembered the first time a variable is overridden, and the
+    /// variable is only written when its current value differs.
+    #[inline]
+    pub fn set<T>(&mut self, var: &'static Var<T>, value: T)
+    where
+        T: Kind,
+    {
+        if !self.is_overridden(var) {
+            // read from a nul-terminated string, so there's no nul within
+            let original = CString::new(var.string()).unwrap_or_default();
+
+            self.originals.push((var.cast(), original));
+        }
+
+        if var.read() != value {
+            var.write(value);
+        }
+    }
+
+    /// Whether `var` is currently overridden.
+    #[inline]
+    pub fn is_overridden<T>(&self, var: &Var<T>) -> bool {
+        self.position(var).is_some()
+    }
+
+    /// Restore `var` to its original value and stop tracking it.
+    #[inline]
+    pub fn restore<T>(&mut self, var: &Var<T>) {
+        if let Some(index) = self.position(var) {
+            let (var, original) = self.originals.swap_remove(index);
+
+            restore(var, &original);
+        }
+    }
+
+    /// Restore every overridden variable to its original value.
+    #[inline]
+    pub fn restore_all(&mut self) {
+        for (var, original) in self.originals.drain(..) {
+            restore(var, &original);
+        }
+    }
+
+    #[inline]
+    fn position<T>(&self, var: &Var<T>) -> Option<usize> {
+        let var = (var as *const Var<T>).cast::<Var<()>>();
+
+        self.originals
+            .iter()
+            .position(|(overridden, _original)| ptr::eq(*overridden, var))
+    }
+}
+
+#[inline]
+fn restore(var: &Var<()>, original: &CString) {
+    if var.string().as_bytes() != original.as_bytes() {
+        var.write_string(original);
+    }
+}
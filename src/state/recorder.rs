@@ -0,0 +1,38 @@
+use elysium_record::{Playback, Recording};
+use elysium_sdk::convar::Var;
+
+/// What the recorder is doing.
+pub enum Mode {
+    Idle,
+    /// Recording to `<name>.rec`.
+    Recording {
+        name: String,
+        recording: Recording,
+    },
+    Playing(Playback),
+}
+
+/// Movement input recorder and playback.
+pub struct Recorder {
+    pub mode: Mode,
+    /// How far off the recorded path playback may drift before it stops.
+    pub max_drift: Option<&'static Var<f32>>,
+}
+
+const NEW: Recorder = Recorder {
+    mode: Mode::Idle,
+    max_drift: None,
+};
+
+impl Recorder {
+    #[inline]
+    pub const fn new() -> Self {
+        NEW
+    }
+
+    /// Whether commands are being replaced by a recording's.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        matches!(self.mode, Mode::Playing(_))
+    }
+}
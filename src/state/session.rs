@@ -0,0 +1,175 @@
+use std::time::Instant;
+
+/// A transition of the session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// Joined a server, see [`Session::server`].
+    Connect,
+    /// Left the server, the session has been cleared.
+    Disconnect,
+    /// A new map loaded, see [`Session::map`].
+    MapChange,
+}
+
+/// The server and map being played, and since when.
+pub struct Session {
+    /// Address of the server, `loopback` for a local server, empty when disconnected.
+    pub server: String,
+    /// Name of the map, without its directory or extension, empty while loading.
+    pub map: String,
+    pub connected_at: Option<Instant>,
+    pub map_started_at: Option<Instant>,
+    /// Rounds started on this map.
+    pub round: u32,
+    pub round_started_at: Option<Instant>,
+}
+
+const NEW: Session = Session {
+    server: String::new(),
+    map: String::new(),
+    connected_at: None,
+    map_started_at: None,
+    round: 0,
+    round_started_at: None,
+};
+
+impl Session {
+    #[inline]
+    pub const fn new() -> Self {
+        NEW
+    }
+
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.connected_at.is_some()
+    }
+
+    /// Compare against the current `server` and `map`, returning what changed in order.
+    ///
+    /// `server` is `None` once disconnected, and `map` is empty while loading.
+    pub fn update(&mut self, server: Option<&str>, map: &str, now: Instant) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        let server = match server {
+            Some(server) => server,
+            None => {
+                if self.is_connected() {
+                    self.disconnect();
+                    events.push(Event::Disconnect);
+                }
+
+                return events;
+            }
+        };
+
+        // went straight to another server
+        if self.is_connected() && self.server != server {
+            self.disconnect();
+            events.push(Event::Disconnect);
+        }
+
+        if !self.is_connected() {
+            self.server.push_str(server);
+            self.connected_at = Some(now);
+            events.push(Event::Connect);
+        }
+
+        if !map.is_empty() && self.map != map {
+            self.map.clear();
+            self.map.push_str(map);
+            self.map_started_at = Some(now);
+            self.round = 0;
+            self.round_started_at = None;
+            events.push(Event::MapChange);
+        }
+
+        events
+    }
+
+    #[inline]
+    pub fn start_round(&mut self, now: Instant) {
+        self.round += 1;
+        self.round_started_at = Some(now);
+    }
+
+    /// Clear everything, keeping the allocations.
+    #[inline]
+    fn disconnect(&mut self) {
+        let mut server = core::mem::take(&mut self.server);
+        let mut map = core::mem::take(&mut self.map);
+
+        server.clear();
+        map.clear();
+
+        *self = NEW;
+        self.server = server;
+        self.map = map;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVER: &str = "192.168.1.2:27015";
+
+    #[test]
+    fn map_changes() {
+        let now = Instant::now();
+        let mut session = Session::new();
+
+        assert_eq!(session.update(None, "", now), []);
+        assert_eq!(session.update(Some(SERVER), "", now), [Event::Connect]);
+        assert!(session.is_connected());
+        assert_eq!(session.server, SERVER);
+
+        assert_eq!(
+            session.update(Some(SERVER), "de_dust2", now),
+            [Event::MapChange]
+        );
+
+        assert_eq!(session.map, "de_dust2");
+        assert_eq!(session.update(Some(SERVER), "de_dust2", now), []);
+
+        session.start_round(now);
+        session.start_round(now);
+
+        assert_eq!(session.round, 2);
+
+        // loading the next map on the same server
+        assert_eq!(session.update(Some(SERVER), "", now), []);
+        assert_eq!(session.map, "de_dust2");
+        assert_eq!(
+            session.update(Some(SERVER), "de_mirage", now),
+            [Event::MapChange]
+        );
+
+        assert_eq!(session.map, "de_mirage");
+        assert_eq!(session.round, 0);
+        assert_eq!(session.round_started_at, None);
+
+        assert_eq!(session.update(None, "", now), [Event::Disconnect]);
+        assert!(!session.is_connected());
+        assert_eq!(session.server, "");
+        assert_eq!(session.map, "");
+        assert_eq!(session.update(None, "", now), []);
+    }
+
+    #[test]
+    fn server_changes() {
+        let now = Instant::now();
+        let mut session = Session::new();
+
+        assert_eq!(
+            session.update(Some(SERVER), "de_dust2", now),
+            [Event::Connect, Event::MapChange]
+        );
+
+        assert_eq!(
+            session.update(Some("loopback"), "de_dust2", now),
+            [Event::Disconnect, Event::Connect, Event::MapChange]
+        );
+
+        assert_eq!(session.server, "loopback");
+    }
+}
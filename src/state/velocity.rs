@@ -0,0 +1,51 @@
+use elysium_menu::velocity::Config;
+use elysium_sdk::convar::Var;
+
+/// Config variables of the velocity graph.
+pub struct VelocityGraph {
+    pub width: Option<&'static Var<f32>>,
+    pub height: Option<&'static Var<f32>>,
+    /// Horizontal centre, as a fraction of the screen's width.
+    pub x: Option<&'static Var<f32>>,
+    /// Vertical centre, as a fraction of the screen's height.
+    pub y: Option<&'static Var<f32>>,
+    /// How many ticks are graphed.
+    pub history: Option<&'static Var<i32>>,
+    /// Config last sent to the graph, so it's only resent when a variable changes.
+    pub config: Option<Config>,
+}
+
+const NEW: VelocityGraph = VelocityGraph {
+    width: None,
+    height: None,
+    x: None,
+    y: None,
+    history: None,
+    config: None,
+};
+
+impl VelocityGraph {
+    #[inline]
+    pub const fn new() -> Self {
+        NEW
+    }
+
+    /// The graph's config as the variables currently read, defaulting any that aren't registered.
+    pub fn read(&self) -> Config {
+        let default = Config::default();
+        let read = |var: Option<&'static Var<f32>>, default: f32| {
+            var.map(|var| var.read()).unwrap_or(default)
+        };
+
+        Config {
+            width: read(self.width, default.width).max(1.0),
+            height: read(self.height, default.height).max(1.0),
+            x: read(self.x, default.x),
+            y: read(self.y, default.y),
+            history: self
+                .history
+                .map(|var| var.read().max(2) as usize)
+                .unwrap_or(default.history),
+        }
+    }
+}
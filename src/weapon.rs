@@ -0,0 +1,95 @@
+use crate::Entity;
+use cake::ffi::vtable;
+use core::ops::Deref;
+use elysium_sdk::{vtable_validate, ItemKind, WeaponInfo};
+
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<529>,
+    weapon_info: unsafe extern "thiscall" fn(this: *const Weapon) -> *const WeaponInfo,
+}
+
+vtable_validate! {
+    weapon_info => 529,
+}
+
+/// `C_WeaponCSBase`
+#[repr(C)]
+pub struct Weapon {
+    vtable: &'static VTable,
+}
+
+impl Weapon {
+    #[inline]
+    pub fn as_entity(&self) -> &Entity {
+        unsafe { &*(self as *const Self).cast() }
+    }
+
+    /// the item definition index
+    #[inline]
+    pub fn item_index(&self) -> i32 {
+        // `m_iItemDefinitionIndex` is a short
+        *self
+            .as_entity()
+            .networked::<i16, _>(|networked| networked.item.index) as i32
+    }
+
+    /// the kind of item, if known
+    #[inline]
+    pub fn item_kind(&self) -> Option<ItemKind> {
+        ItemKind::from_raw(self.item_index())
+    }
+
+    /// the weapon's script (`GetCSWpnData`)
+    #[inline]
+    pub fn weapon_info(&self) -> Option<&WeaponInfo> {
+        unsafe { (self.vtable.weapon_info)(self).as_ref() }
+    }
+
+    /// rounds in the magazine
+    #[inline]
+    pub fn magazine(&self) -> i32 {
+        *self
+            .as_entity()
+            .networked(|networked| networked.base_weapon.magazine)
+    }
+
+    /// rounds in reserve
+    #[inline]
+    pub fn reserve_ammo(&self) -> i32 {
+        *self
+            .as_entity()
+            .networked(|networked| networked.base_weapon.reserve_ammo)
+    }
+
+    /// server time the weapon can next fire at
+    #[inline]
+    pub fn next_attack_available_after(&self) -> f32 {
+        *self
+            .as_entity()
+            .networked(|networked| networked.base_weapon.next_attack_available_after)
+    }
+
+    /// only for the r8 revolver
+    #[inline]
+    pub fn revolver_cock_time(&self) -> f32 {
+        *self
+            .as_entity()
+            .networked(|networked| networked.weapon.revolver_cock_time)
+    }
+
+    /// whether the weapon can fire at `time`
+    #[inline]
+    pub fn can_attack(&self, time: f32) -> bool {
+        self.magazine() > 0 && self.next_attack_available_after() <= time
+    }
+}
+
+impl Deref for Weapon {
+    type Target = Entity;
+
+    #[inline]
+    fn deref(&self) -> &Entity {
+        self.as_entity()
+    }
+}